@@ -1,17 +1,23 @@
 use crate::{First, Main, MainSchedulePlugin, Plugin, Plugins, StateTransition};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    event::EventRetention,
+    index::{refresh_index, ComponentIndex, IndexStorage},
     prelude::*,
     schedule::{
-        apply_state_transition, common_conditions::run_once as run_once_condition,
-        run_enter_schedule, BoxedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs,
-        ScheduleLabel,
+        apply_computed_state, apply_state_transition, apply_sub_state,
+        common_conditions::run_once as run_once_condition, run_enter_schedule, BoxedScheduleLabel,
+        ComputedStates, IntoSystemConfigs, IntoSystemSetConfigs, ScheduleLabel, StateScopedCleanup,
+        StateSet, StateTransitionSet, SubStates,
     },
+    system::{DefaultErrorHandler, ErrorHandler},
 };
 use bevy_utils::{tracing::debug, HashMap, HashSet};
 use std::{
     fmt::Debug,
+    fs, io,
     panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    path::Path,
 };
 
 #[cfg(feature = "trace")]
@@ -326,7 +332,7 @@ impl App {
         self.plugin_registry = plugin_registry;
     }
 
-    /// Adds [`State<S>`] and [`NextState<S>`] resources, [`OnEnter`] and [`OnExit`] schedules
+    /// Adds [`State<S>`], [`NextState<S>`] and [`StateStack<S>`] resources, [`OnEnter`] and [`OnExit`] schedules
     /// for each state variant (if they don't already exist), an instance of [`apply_state_transition::<S>`] in
     /// [`StateTransition`] so that transitions happen before [`Update`](crate::Update) and
     /// a instance of [`run_enter_schedule::<S>`] in [`StateTransition`] with a
@@ -338,16 +344,18 @@ impl App {
     ///
     /// Note that you can also apply state transitions at other points in the schedule
     /// by adding the [`apply_state_transition`] system manually.
-    pub fn add_state<S: States>(&mut self) -> &mut Self {
+    pub fn add_state<S: States + Default>(&mut self) -> &mut Self {
         self.init_resource::<State<S>>()
             .init_resource::<NextState<S>>()
+            .init_resource::<StateStack<S>>()
             .add_systems(
                 StateTransition,
                 (
                     run_enter_schedule::<S>.run_if(run_once_condition()),
                     apply_state_transition::<S>,
                 )
-                    .chain(),
+                    .chain()
+                    .in_set(StateTransitionSet::<S>::default()),
             );
 
         // The OnEnter, OnExit, and OnTransition schedules are lazily initialized
@@ -357,6 +365,35 @@ impl App {
         self
     }
 
+    /// Adds an instance of [`apply_computed_state::<S>`] to [`StateTransition`], after the
+    /// transitions of the [`SourceStates`](ComputedStates::SourceStates) of `S`.
+    ///
+    /// [`State<S>`] is inserted and removed as the sources of `S` change, so the sources must be
+    /// added to the app as well, with [`App::add_state`] or as other computed or sub states.
+    pub fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
+        self.add_systems(
+            StateTransition,
+            S::SourceStates::after_sources(
+                apply_computed_state::<S>.in_set(StateTransitionSet::<S>::default()),
+            ),
+        )
+    }
+
+    /// Adds the [`NextState<S>`] resource and an instance of [`apply_sub_state::<S>`] to
+    /// [`StateTransition`], after the transitions of the [`SourceStates`](SubStates::SourceStates)
+    /// of `S`.
+    ///
+    /// [`State<S>`] is inserted and removed as the sources of `S` change, so the sources must be
+    /// added to the app as well, with [`App::add_state`] or as other computed or sub states.
+    pub fn add_sub_state<S: SubStates>(&mut self) -> &mut Self {
+        self.init_resource::<NextState<S>>().add_systems(
+            StateTransition,
+            S::SourceStates::after_sources(
+                apply_sub_state::<S>.in_set(StateTransitionSet::<S>::default()),
+            ),
+        )
+    }
+
     /// Adds a system to the given schedule in this app's [`Schedules`].
     ///
     /// # Examples
@@ -456,6 +493,119 @@ impl App {
         self
     }
 
+    /// Setup the application to manage events of type `T` like [`App::add_event`], keeping them
+    /// according to the given [`EventRetention`] instead of dropping them after two updates.
+    ///
+    /// If the events are already managed by the application, their retention is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{event::EventRetention, prelude::*};
+    /// #
+    /// # #[derive(Event)]
+    /// # struct Damage;
+    /// # #[derive(Event)]
+    /// # struct ChatMessage;
+    /// # let mut app = App::new();
+    /// #
+    /// // keep the events until every system reading them has run, even in `FixedUpdate`
+    /// app.add_event_with_retention::<Damage>(EventRetention::UntilConsumed);
+    /// // keep the last 100 messages
+    /// app.add_event_with_retention::<ChatMessage>(EventRetention::RingBuffer(100));
+    /// ```
+    pub fn add_event_with_retention<T>(&mut self, retention: EventRetention) -> &mut Self
+    where
+        T: Event,
+    {
+        match self.world.get_resource_mut::<Events<T>>() {
+            Some(mut events) => events.set_retention(retention),
+            None => {
+                self.insert_resource(Events::<T>::with_retention(retention))
+                    .add_systems(First, Events::<T>::update_system);
+            }
+        }
+        self
+    }
+
+    /// Setup the application to manage events of type `T` with [`App::add_event`], and clears
+    /// them whenever `state` is exited, so that events sent while in a state are not read after
+    /// it.
+    ///
+    /// The events are cleared in the [`OnExit`] schedule of `state`, in the [`StateScopedCleanup`]
+    /// set, which runs after the other systems of the schedule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    /// # enum GameState {
+    /// #     #[default]
+    /// #     InGame,
+    /// # }
+    /// # #[derive(Event)]
+    /// # struct ScoreChanged;
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_state::<GameState>()
+    ///     .add_state_scoped_event::<ScoreChanged, _>(GameState::InGame);
+    /// ```
+    pub fn add_state_scoped_event<T, S>(&mut self, state: S) -> &mut Self
+    where
+        T: Event,
+        S: States,
+    {
+        self.add_event::<T>()
+            .add_systems(
+                OnExit(state.clone()),
+                (|mut events: ResMut<Events<T>>| events.clear()).in_set(StateScopedCleanup),
+            )
+            .edit_schedule(OnExit(state), |schedule| {
+                schedule.order_last(StateScopedCleanup);
+            })
+    }
+
+    /// Initializes the [`ComponentIndex`] `I`, and adds [`refresh_index::<I>`] to [`First`] so
+    /// that changes made in place to the indexed component are picked up every update.
+    ///
+    /// Systems reading the index through [`EntityIndex`](bevy_ecs::index::EntityIndex) see
+    /// insertions and removals right away, and in-place changes from the previous update.
+    ///
+    /// If the index was already initialized, no system is added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{index::ComponentIndex, prelude::*};
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Player(u32);
+    /// struct ByPlayerId;
+    ///
+    /// impl ComponentIndex for ByPlayerId {
+    ///     type Component = Player;
+    ///     type Key = u32;
+    ///
+    ///     fn key(player: &Player) -> u32 {
+    ///         player.0
+    ///     }
+    /// }
+    ///
+    /// App::new().add_index::<ByPlayerId>();
+    /// ```
+    pub fn add_index<I: ComponentIndex>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<IndexStorage<I>>() {
+            self.world.init_index::<I>();
+            self.add_systems(First, refresh_index::<I>);
+        }
+        self
+    }
+
     /// Inserts a [`Resource`] to the current [`App`] and overwrites any [`Resource`] previously added of the same type.
     ///
     /// A [`Resource`] in Bevy represents globally unique data. [`Resource`]s must be added to Bevy apps
@@ -550,6 +700,34 @@ impl App {
         self
     }
 
+    /// Sets the [`ErrorHandler`] receiving the errors returned by fallible systems and run
+    /// conditions, in every schedule that doesn't set its own handler with
+    /// [`Schedule::set_error_handler`].
+    ///
+    /// This only affects the systems initialized afterwards, so it should be called while
+    /// building the app.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::ErrorHandler;
+    /// #
+    /// fn fallible_system() -> Result<(), String> {
+    ///     Err("something went wrong".to_string())
+    /// }
+    ///
+    /// App::new()
+    ///     .set_error_handler(ErrorHandler::WarnOnce)
+    ///     .add_systems(Update, fallible_system)
+    ///     .update();
+    /// ```
+    pub fn set_error_handler(&mut self, handler: ErrorHandler) -> &mut Self {
+        self.world.insert_resource(DefaultErrorHandler(handler));
+        self
+    }
+
     /// Sets the function that will be called when the app is run.
     ///
     /// The runner function `run_fn` is called only once by [`App::run`]. If the
@@ -848,6 +1026,52 @@ impl App {
 
         self
     }
+
+    /// Writes the graph of every [`Schedule`] of the [`App`] to the directory `dir`, which is
+    /// created if needed.
+    ///
+    /// Each schedule is initialized, so that ambiguities are detected, and written both as
+    /// Graphviz DOT and as JSON, in files named after its label: `Update.dot` and `Update.json`.
+    /// Characters of the label that aren't alphanumeric are replaced with `_`, and labels whose
+    /// file names would collide get a numeric suffix, like `Update_2.dot`, in the order of their
+    /// [`Debug`] representations.
+    /// See [`Schedule::export_graph`] for the contents of the files.
+    ///
+    /// Schedules of sub-apps are not included; call this method on them if needed.
+    ///
+    /// Returns an error if a file cannot be written or a schedule fails to build.
+    pub fn dump_schedules(&mut self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        self.world
+            .resource_scope(|world, mut schedules: Mut<Schedules>| {
+                let mut schedules: Vec<_> = schedules
+                    .iter_mut()
+                    .map(|(label, schedule)| (format!("{label:?}"), schedule))
+                    .collect();
+                schedules.sort_by(|(a, _), (b, _)| a.cmp(b));
+                let mut names = HashSet::new();
+                for (label, schedule) in schedules {
+                    schedule
+                        .initialize(world)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                    let export = schedule.export_graph(world.components());
+                    let base: String = label
+                        .chars()
+                        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                        .collect();
+                    let mut name = base.clone();
+                    let mut index = 1;
+                    while !names.insert(name.clone()) {
+                        index += 1;
+                        name = format!("{base}_{index}");
+                    }
+                    fs::write(dir.join(format!("{name}.dot")), export.to_dot())?;
+                    fs::write(dir.join(format!("{name}.json")), export.to_json())?;
+                }
+                Ok(())
+            })
+    }
 }
 
 fn run_once(mut app: App) {
@@ -877,11 +1101,15 @@ pub struct AppExit;
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
-        schedule::{OnEnter, States},
-        system::Commands,
+        event::{Event, EventReader, EventRetention, Events},
+        schedule::{
+            apply_state_transition, ComputedStates, IntoSystemConfigs, NextState, OnEnter, OnExit,
+            State, States, SubStates,
+        },
+        system::{Commands, Res, ResMut, Resource},
     };
 
-    use crate::{App, Plugin};
+    use crate::{App, Plugin, StateTransition, Update};
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -963,6 +1191,142 @@ mod tests {
         assert_eq!(app.world.entities().len(), 2);
     }
 
+    #[test]
+    fn computed_and_sub_states() {
+        #[derive(States, PartialEq, Eq, Debug, Hash, Clone)]
+        enum InMenu {
+            Yes,
+        }
+
+        impl ComputedStates for InMenu {
+            type SourceStates = AppState;
+
+            fn compute(state: AppState) -> Option<Self> {
+                (state == AppState::MainMenu).then_some(InMenu::Yes)
+            }
+        }
+
+        #[derive(States, PartialEq, Eq, Debug, Hash, Clone)]
+        enum MenuPage {
+            Title,
+            Options,
+        }
+
+        impl SubStates for MenuPage {
+            type SourceStates = InMenu;
+
+            fn should_exist(_: InMenu) -> Option<Self> {
+                Some(MenuPage::Title)
+            }
+        }
+
+        let mut app = App::new();
+        app.add_sub_state::<MenuPage>()
+            .add_computed_state::<InMenu>()
+            .add_state::<AppState>()
+            .add_systems(OnEnter(MenuPage::Title), foo);
+
+        app.world.run_schedule(StateTransition);
+        assert_eq!(app.world.resource::<State<InMenu>>().get(), &InMenu::Yes);
+        assert_eq!(
+            app.world.resource::<State<MenuPage>>().get(),
+            &MenuPage::Title
+        );
+        assert_eq!(app.world.entities().len(), 1);
+
+        app.world
+            .resource_mut::<NextState<MenuPage>>()
+            .set(MenuPage::Options);
+        app.world.run_schedule(StateTransition);
+        assert_eq!(
+            app.world.resource::<State<MenuPage>>().get(),
+            &MenuPage::Options
+        );
+    }
+
+    #[test]
+    fn state_scoped_events() {
+        #[derive(Event)]
+        struct TestEvent;
+
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+        enum GameState {
+            #[default]
+            MainMenu,
+            InGame,
+        }
+
+        #[derive(Resource, Default)]
+        struct ReadOnExit(usize);
+
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .add_state_scoped_event::<TestEvent, _>(GameState::MainMenu)
+            .init_resource::<ReadOnExit>()
+            // the events are cleared after the other `OnExit` systems
+            .add_systems(
+                OnExit(GameState::MainMenu),
+                |mut events: EventReader<TestEvent>, mut read: ResMut<ReadOnExit>| {
+                    read.0 += events.iter().count();
+                },
+            );
+        app.world.run_schedule(StateTransition);
+        app.world.send_event(TestEvent);
+
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        apply_state_transition::<GameState>(&mut app.world);
+        assert!(app.world.resource::<Events<TestEvent>>().is_empty());
+        assert_eq!(app.world.resource::<ReadOnExit>().0, 1);
+
+        // Events are only cleared when exiting the scoped state.
+        app.world.send_event(TestEvent);
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::MainMenu);
+        apply_state_transition::<GameState>(&mut app.world);
+        assert!(!app.world.resource::<Events<TestEvent>>().is_empty());
+    }
+
+    #[test]
+    fn events_retained_until_consumed() {
+        #[derive(Event)]
+        struct TestEvent;
+
+        #[derive(Resource, Default)]
+        struct Received {
+            enabled: bool,
+            count: usize,
+        }
+
+        fn receive(mut events: EventReader<TestEvent>, mut received: ResMut<Received>) {
+            received.count += events.iter().count();
+        }
+
+        let mut app = App::new();
+        app.add_event_with_retention::<TestEvent>(EventRetention::UntilConsumed)
+            .init_resource::<Received>()
+            .add_systems(
+                Update,
+                receive.run_if(|received: Res<Received>| received.enabled),
+            );
+        app.update();
+
+        app.world.send_event(TestEvent);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Received>().count, 0);
+
+        app.world.resource_mut::<Received>().enabled = true;
+        app.update();
+        assert_eq!(app.world.resource::<Received>().count, 1);
+        app.update();
+        app.update();
+        assert!(app.world.resource::<Events<TestEvent>>().is_empty());
+    }
+
     #[test]
     fn add_systems_should_create_schedule_if_it_does_not_exist2() {
         let mut app = App::new();
@@ -972,4 +1336,79 @@ mod tests {
         app.world.run_schedule(OnEnter(AppState::MainMenu));
         assert_eq!(app.world.entities().len(), 2);
     }
+
+    #[test]
+    fn dump_schedules() {
+        let dir = std::env::temp_dir().join(format!("bevy_dump_schedules_{}", std::process::id()));
+        let mut app = App::new();
+        app.add_systems(Update, (foo, bar).chain());
+        app.dump_schedules(&dir).unwrap();
+
+        let dot = std::fs::read_to_string(dir.join("Update.dot")).unwrap();
+        assert!(dot.contains("label=\"foo\""));
+        assert!(dot.contains("system_0 -> system_1;"));
+        let json = std::fs::read_to_string(dir.join("Update.json")).unwrap();
+        assert!(json.contains("\"name\": \"bevy_app::app::tests::bar\""));
+        assert!(dir.join("Main.json").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn dump_schedules_with_colliding_names() {
+        use bevy_ecs::schedule::ScheduleLabel;
+
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct Phase(&'static str);
+
+        let dir = std::env::temp_dir().join(format!(
+            "bevy_dump_schedules_collisions_{}",
+            std::process::id()
+        ));
+        let mut app = App::new();
+        app.add_systems(Phase("a b"), foo)
+            .add_systems(Phase("a-b"), bar);
+        app.dump_schedules(&dir).unwrap();
+
+        let first = std::fs::read_to_string(dir.join("Phase__a_b__.json")).unwrap();
+        let second = std::fs::read_to_string(dir.join("Phase__a_b___2.json")).unwrap();
+        assert!(first.contains("bevy_app::app::tests::foo"));
+        assert!(second.contains("bevy_app::app::tests::bar"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn add_index_refreshes_in_place_changes() {
+        use bevy_ecs::{
+            component::Component,
+            index::{ComponentIndex, EntityIndex},
+            system::SystemState,
+        };
+
+        #[derive(Component)]
+        struct Player(u32);
+
+        struct ByPlayerId;
+
+        impl ComponentIndex for ByPlayerId {
+            type Component = Player;
+            type Key = u32;
+
+            fn key(player: &Player) -> u32 {
+                player.0
+            }
+        }
+
+        let mut app = App::new();
+        app.add_index::<ByPlayerId>().add_index::<ByPlayerId>();
+        let player = app.world.spawn(Player(1)).id();
+        app.update();
+
+        app.world.get_mut::<Player>(player).unwrap().0 = 2;
+        app.update();
+
+        let mut state = SystemState::<EntityIndex<ByPlayerId>>::new(&mut app.world);
+        let index = state.get(&app.world);
+        assert!(index.get(&1).is_empty());
+        assert_eq!(index.get(&2), [player]);
+    }
 }
@@ -1,15 +1,21 @@
 use super::{Diagnostic, DiagnosticId, DiagnosticsStore};
 use bevy_app::prelude::*;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, schedule::SystemStats};
 use bevy_log::{debug, info};
 use bevy_time::{Time, Timer, TimerMode};
-use bevy_utils::Duration;
+use bevy_utils::{get_short_name, Duration};
 
 /// An App Plugin that logs diagnostics to the console
 pub struct LogDiagnosticsPlugin {
     pub debug: bool,
     pub wait_duration: Duration,
     pub filter: Option<Vec<DiagnosticId>>,
+    /// The number of systems to log, with the longest total time first, from the [`SystemStats`]
+    /// recorded during each interval. This requires the
+    /// [`SystemStatsDiagnosticsPlugin`](crate::SystemStatsDiagnosticsPlugin).
+    ///
+    /// The statistics are cleared after being logged, so that each log covers one interval.
+    pub slowest_systems: Option<usize>,
 }
 
 /// State used by the [`LogDiagnosticsPlugin`]
@@ -17,6 +23,7 @@ pub struct LogDiagnosticsPlugin {
 struct LogDiagnosticsState {
     timer: Timer,
     filter: Option<Vec<DiagnosticId>>,
+    slowest_systems: Option<usize>,
 }
 
 impl Default for LogDiagnosticsPlugin {
@@ -25,6 +32,7 @@ impl Default for LogDiagnosticsPlugin {
             debug: false,
             wait_duration: Duration::from_secs(1),
             filter: None,
+            slowest_systems: None,
         }
     }
 }
@@ -34,6 +42,7 @@ impl Plugin for LogDiagnosticsPlugin {
         app.insert_resource(LogDiagnosticsState {
             timer: Timer::new(self.wait_duration, TimerMode::Repeating),
             filter: self.filter.clone(),
+            slowest_systems: self.slowest_systems,
         });
 
         if self.debug {
@@ -52,6 +61,36 @@ impl LogDiagnosticsPlugin {
         }
     }
 
+    /// Creates a plugin that also logs the `count` systems with the longest total time of each
+    /// interval.
+    pub fn slowest_systems(count: usize) -> Self {
+        LogDiagnosticsPlugin {
+            slowest_systems: Some(count),
+            ..Default::default()
+        }
+    }
+
+    fn log_slowest_systems(stats: &mut SystemStats, count: usize, debug: bool) {
+        for (name, system) in stats.slowest(count) {
+            if debug {
+                debug!("{name}: {:#?}\n", system);
+            } else {
+                info!(
+                    target: "bevy diagnostic",
+                    "{name:<name_width$}: {total:>11.6}ms total (mean {mean:.6}ms, max {max:.6}ms, {runs} runs, {skips} skipped)",
+                    name = get_short_name(name),
+                    total = system.total_time.as_secs_f64() * 1000.0,
+                    mean = system.mean_time().as_secs_f64() * 1000.0,
+                    max = system.max_time.as_secs_f64() * 1000.0,
+                    runs = system.runs,
+                    skips = system.skips,
+                    name_width = crate::MAX_DIAGNOSTIC_NAME_WIDTH,
+                );
+            }
+        }
+        stats.clear();
+    }
+
     fn log_diagnostic(diagnostic: &Diagnostic) {
         if let Some(value) = diagnostic.smoothed() {
             if diagnostic.get_max_history_length() > 1 {
@@ -84,8 +123,12 @@ impl LogDiagnosticsPlugin {
         mut state: ResMut<LogDiagnosticsState>,
         time: Res<Time>,
         diagnostics: Res<DiagnosticsStore>,
+        system_stats: Option<ResMut<SystemStats>>,
     ) {
         if state.timer.tick(time.raw_delta()).finished() {
+            if let (Some(count), Some(mut stats)) = (state.slowest_systems, system_stats) {
+                Self::log_slowest_systems(&mut stats, count, false);
+            }
             if let Some(ref filter) = state.filter {
                 for diagnostic in filter.iter().flat_map(|id| {
                     diagnostics
@@ -109,8 +152,12 @@ impl LogDiagnosticsPlugin {
         mut state: ResMut<LogDiagnosticsState>,
         time: Res<Time>,
         diagnostics: Res<DiagnosticsStore>,
+        system_stats: Option<ResMut<SystemStats>>,
     ) {
         if state.timer.tick(time.raw_delta()).finished() {
+            if let (Some(count), Some(mut stats)) = (state.slowest_systems, system_stats) {
+                Self::log_slowest_systems(&mut stats, count, true);
+            }
             if let Some(ref filter) = state.filter {
                 for diagnostic in filter.iter().flat_map(|id| {
                     diagnostics
@@ -0,0 +1,16 @@
+use bevy_app::prelude::*;
+use bevy_ecs::schedule::SystemStats;
+
+/// Records the wall time, run count and skip count of every system in a [`SystemStats`] resource.
+///
+/// Add [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) with
+/// [`slowest_systems`](crate::LogDiagnosticsPlugin::slowest_systems) set to log the slowest
+/// systems of each interval.
+#[derive(Default)]
+pub struct SystemStatsDiagnosticsPlugin;
+
+impl Plugin for SystemStatsDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SystemStats>();
+    }
+}
@@ -1,7 +1,13 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, DeriveInput, Ident, LitStr, Path, Result};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    DeriveInput, Expr, Ident, LitStr, Path, Result, Token, Type,
+};
 
 pub fn derive_event(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
@@ -50,6 +56,23 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
 
+    let register_required = attrs.requires.iter().map(|Require { ty, constructor }| {
+        let constructor = match constructor {
+            Some(constructor) => quote! { #constructor },
+            None => quote! { <#ty as ::core::default::Default>::default },
+        };
+        quote! { required.register::<#ty>(#constructor); }
+    });
+    let register_required_components = (!attrs.requires.is_empty()).then(|| {
+        quote! {
+            fn register_required_components(
+                required: &mut #bevy_ecs_path::component::RequiredComponents,
+            ) {
+                #(#register_required)*
+            }
+        }
+    });
+
     ast.generics
         .make_where_clause()
         .predicates
@@ -61,15 +84,37 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             type Storage = #storage;
+
+            #register_required_components
         }
     })
 }
 
 pub const COMPONENT: &str = "component";
 pub const STORAGE: &str = "storage";
+pub const REQUIRE: &str = "require";
 
 struct Attrs {
     storage: StorageTy,
+    requires: Vec<Require>,
+}
+
+/// A required component, like `Transform` or `Visibility = visible`.
+struct Require {
+    ty: Type,
+    constructor: Option<Expr>,
+}
+
+impl Parse for Require {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ty = input.parse()?;
+        let constructor = if input.parse::<Option<Token![=]>>()?.is_some() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Require { ty, constructor })
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -85,6 +130,7 @@ const SPARSE_SET: &str = "SparseSet";
 fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        requires: Vec::new(),
     };
 
     for meta in ast.attrs.iter().filter(|a| a.path().is_ident(COMPONENT)) {
@@ -100,6 +146,15 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                     }
                 };
                 Ok(())
+            } else if nested.path.is_ident(REQUIRE) {
+                let content;
+                parenthesized!(content in nested.input);
+                attrs
+                    .requires
+                    .extend(Punctuated::<Require, Token![,]>::parse_terminated(
+                        &content,
+                    )?);
+                Ok(())
             } else {
                 Err(nested.error("Unsupported attribute"))
             }
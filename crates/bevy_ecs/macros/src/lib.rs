@@ -202,6 +202,9 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
                         let #param = #param::init_state(world, &mut system_meta.clone());
                     )*
                     #(
+                        if #meta.has_deferred() {
+                            system_meta.set_has_deferred();
+                        }
                         system_meta
                             .component_access_set
                             .extend(#meta.component_access_set);
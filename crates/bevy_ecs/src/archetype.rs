@@ -20,11 +20,13 @@
 //! [`World::archetypes`]: crate::world::World::archetypes
 
 use crate::{
-    bundle::BundleId,
-    component::{ComponentId, StorageType},
+    bundle::{Bundle, BundleId},
+    component::{Component, ComponentId, Components, StorageType},
     entity::{Entity, EntityLocation},
     storage::{ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId, TableRow},
+    world::World,
 };
+use bevy_utils::tracing::warn;
 use std::{
     hash::Hash,
     ops::{Index, IndexMut},
@@ -103,6 +105,9 @@ pub(crate) enum ComponentStatus {
 pub(crate) struct AddBundle {
     pub archetype_id: ArchetypeId,
     pub bundle_status: Vec<ComponentStatus>,
+    /// Whether each of the bundle's required components is missing from the source archetype,
+    /// and has to be constructed.
+    pub added_required_components: Vec<bool>,
 }
 
 /// This trait is used to report the status of [`Bundle`](crate::bundle::Bundle) components
@@ -115,6 +120,14 @@ pub(crate) trait BundleComponentStatus {
     /// Callers must ensure that index is always a valid bundle index for the
     /// Bundle associated with this [`BundleComponentStatus`]
     unsafe fn get_status(&self, index: usize) -> ComponentStatus;
+
+    /// Returns `true` if the Bundle's required component at the given index is missing, and
+    /// has to be constructed
+    ///
+    /// # Safety
+    /// Callers must ensure that index is always a valid index in the required components of the
+    /// Bundle associated with this [`BundleComponentStatus`]
+    unsafe fn is_required_component_added(&self, index: usize) -> bool;
 }
 
 impl BundleComponentStatus for AddBundle {
@@ -123,6 +136,12 @@ impl BundleComponentStatus for AddBundle {
         // SAFETY: caller has ensured index is a valid bundle index for this bundle
         *self.bundle_status.get_unchecked(index)
     }
+
+    #[inline]
+    unsafe fn is_required_component_added(&self, index: usize) -> bool {
+        // SAFETY: caller has ensured index is a valid required component index for this bundle
+        *self.added_required_components.get_unchecked(index)
+    }
 }
 
 pub(crate) struct SpawnBundleStatus;
@@ -133,6 +152,12 @@ impl BundleComponentStatus for SpawnBundleStatus {
         // Components added during a spawn call are always treated as added
         ComponentStatus::Added
     }
+
+    #[inline]
+    unsafe fn is_required_component_added(&self, _index: usize) -> bool {
+        // A spawned entity has none of the required components yet
+        true
+    }
 }
 
 /// Archetypes and bundles form a graph. Adding or removing a bundle moves
@@ -186,12 +211,14 @@ impl Edges {
         bundle_id: BundleId,
         archetype_id: ArchetypeId,
         bundle_status: Vec<ComponentStatus>,
+        added_required_components: Vec<bool>,
     ) {
         self.add_bundle.insert(
             bundle_id,
             AddBundle {
                 archetype_id,
                 bundle_status,
+                added_required_components,
             },
         );
     }
@@ -604,6 +631,10 @@ pub struct Archetypes {
     pub(crate) archetypes: Vec<Archetype>,
     pub(crate) archetype_component_count: usize,
     archetype_ids: bevy_utils::HashMap<ArchetypeIdentity, ArchetypeId>,
+    invariants: Vec<ArchetypeInvariant>,
+    invariant_policy: ArchetypeInvariantPolicy,
+    /// The indices of the invariants violated by each archetype, for the archetypes violating any.
+    invariant_violations: bevy_utils::HashMap<ArchetypeId, Vec<usize>>,
 }
 
 impl Archetypes {
@@ -612,6 +643,9 @@ impl Archetypes {
             archetypes: Vec::new(),
             archetype_ids: Default::default(),
             archetype_component_count: 0,
+            invariants: Vec::new(),
+            invariant_policy: ArchetypeInvariantPolicy::default(),
+            invariant_violations: Default::default(),
         };
         archetypes.get_id_or_insert(TableId::empty(), Vec::new(), Vec::new());
         archetypes
@@ -698,30 +732,136 @@ impl Archetypes {
         };
 
         let archetypes = &mut self.archetypes;
+        let archetype_count = archetypes.len();
         let archetype_component_count = &mut self.archetype_component_count;
-        *self
-            .archetype_ids
-            .entry(archetype_identity)
-            .or_insert_with(move || {
-                let id = ArchetypeId::new(archetypes.len());
-                let table_start = *archetype_component_count;
-                *archetype_component_count += table_components.len();
-                let table_archetype_components =
-                    (table_start..*archetype_component_count).map(ArchetypeComponentId);
-                let sparse_start = *archetype_component_count;
-                *archetype_component_count += sparse_set_components.len();
-                let sparse_set_archetype_components =
-                    (sparse_start..*archetype_component_count).map(ArchetypeComponentId);
-                archetypes.push(Archetype::new(
-                    id,
-                    table_id,
-                    table_components.into_iter().zip(table_archetype_components),
-                    sparse_set_components
-                        .into_iter()
-                        .zip(sparse_set_archetype_components),
-                ));
-                id
-            })
+        let archetype_id =
+            *self
+                .archetype_ids
+                .entry(archetype_identity)
+                .or_insert_with(move || {
+                    let id = ArchetypeId::new(archetypes.len());
+                    let table_start = *archetype_component_count;
+                    *archetype_component_count += table_components.len();
+                    let table_archetype_components =
+                        (table_start..*archetype_component_count).map(ArchetypeComponentId);
+                    let sparse_start = *archetype_component_count;
+                    *archetype_component_count += sparse_set_components.len();
+                    let sparse_set_archetype_components =
+                        (sparse_start..*archetype_component_count).map(ArchetypeComponentId);
+                    archetypes.push(Archetype::new(
+                        id,
+                        table_id,
+                        table_components.into_iter().zip(table_archetype_components),
+                        sparse_set_components
+                            .into_iter()
+                            .zip(sparse_set_archetype_components),
+                    ));
+                    id
+                });
+        if self.archetypes.len() > archetype_count {
+            self.check_invariants(archetype_id);
+        }
+        archetype_id
+    }
+
+    /// Returns the invariants that every archetype must satisfy.
+    pub fn invariants(&self) -> &[ArchetypeInvariant] {
+        &self.invariants
+    }
+
+    /// Returns what happens when an archetype violates one of the [`invariants`](Self::invariants).
+    pub fn invariant_policy(&self) -> ArchetypeInvariantPolicy {
+        self.invariant_policy
+    }
+
+    pub(crate) fn set_invariant_policy(&mut self, policy: ArchetypeInvariantPolicy) {
+        self.invariant_policy = policy;
+    }
+
+    /// Adds an invariant, and checks it against the existing archetypes.
+    ///
+    /// Returns the ids of the existing archetypes violating it.
+    pub(crate) fn add_invariant(&mut self, invariant: ArchetypeInvariant) -> Vec<ArchetypeId> {
+        let index = self.invariants.len();
+        let violating = self
+            .archetypes
+            .iter()
+            .filter(|archetype| !invariant.is_satisfied_by(archetype))
+            .map(Archetype::id)
+            .collect::<Vec<_>>();
+        self.invariants.push(invariant);
+        for &archetype_id in &violating {
+            self.invariant_violations
+                .entry(archetype_id)
+                .or_default()
+                .push(index);
+        }
+        violating
+    }
+
+    fn check_invariants(&mut self, archetype_id: ArchetypeId) {
+        let archetype = &self.archetypes[archetype_id.index()];
+        let violated = self
+            .invariants
+            .iter()
+            .enumerate()
+            .filter(|(_, invariant)| !invariant.is_satisfied_by(archetype))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if !violated.is_empty() {
+            self.invariant_violations.insert(archetype_id, violated);
+        }
+    }
+
+    /// Returns `true` if the archetype violates any of the [`invariants`](Self::invariants).
+    #[inline]
+    pub(crate) fn violates_invariants(&self, archetype_id: ArchetypeId) -> bool {
+        !self.invariant_violations.is_empty()
+            && self.invariant_violations.contains_key(&archetype_id)
+    }
+
+    /// Reports the invariants violated by `entity`, which has just been moved to the archetype
+    /// `archetype_id`, according to the [`ArchetypeInvariantPolicy`].
+    ///
+    /// This must be called every time an entity is moved to another archetype, once the
+    /// structural change is complete and its hooks have run.
+    #[inline]
+    pub(crate) fn report_invariant_violations(
+        &self,
+        entity: Entity,
+        archetype_id: ArchetypeId,
+        components: &Components,
+    ) {
+        if self.violates_invariants(archetype_id) {
+            self.report_invariant_violations_slow(entity, archetype_id, components);
+        }
+    }
+
+    #[cold]
+    fn report_invariant_violations_slow(
+        &self,
+        entity: Entity,
+        archetype_id: ArchetypeId,
+        components: &Components,
+    ) {
+        let archetype = &self.archetypes[archetype_id.index()];
+        let component_names = archetype
+            .components()
+            .map(|id| components.get_name(id).unwrap_or("<unknown>"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        for &index in &self.invariant_violations[&archetype_id] {
+            let message = format!(
+                "Entity {:?} violates the archetype invariant `{}`: it has the components [{}].",
+                entity,
+                self.invariants[index].describe(components),
+                component_names,
+            );
+            match self.invariant_policy {
+                ArchetypeInvariantPolicy::Panic => panic!("{message}"),
+                ArchetypeInvariantPolicy::Warn => warn!("{message}"),
+            }
+        }
     }
 
     /// Returns the number of components that are stored in archetypes.
@@ -754,3 +894,270 @@ impl IndexMut<ArchetypeId> for Archetypes {
         &mut self.archetypes[index.index()]
     }
 }
+
+/// A rule on the components that can be found together in an [`Archetype`].
+///
+/// Invariants are added with [`World::add_archetype_invariant`]. They are checked once whenever a
+/// new archetype is created, and every entity moved to an archetype violating them is reported
+/// according to the [`ArchetypeInvariantPolicy`] of the world, after the hooks of the structural
+/// change have run.
+///
+/// ```
+/// # use bevy_ecs::{archetype::ArchetypeInvariant, prelude::*};
+/// #
+/// # #[derive(Component)]
+/// # struct Player;
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// # #[derive(Component)]
+/// # struct Health;
+/// #
+/// let mut world = World::new();
+/// let invariant = ArchetypeInvariant::forbids::<Player, Enemy>(&mut world);
+/// world.add_archetype_invariant(invariant);
+/// let invariant = ArchetypeInvariant::requires::<Player, Health>(&mut world);
+/// world.add_archetype_invariant(invariant);
+///
+/// world.spawn((Player, Health));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArchetypeInvariant {
+    /// Archetypes containing `component` must also contain all of the `required` components.
+    Requires {
+        /// The component with requirements.
+        component: ComponentId,
+        /// The components that must be present along `component`.
+        required: Vec<ComponentId>,
+    },
+    /// Archetypes containing `component` must contain none of the `forbidden` components.
+    Forbids {
+        /// The component excluding others.
+        component: ComponentId,
+        /// The components that can't be present along `component`.
+        forbidden: Vec<ComponentId>,
+    },
+    /// Archetypes must contain at most one of the components.
+    AtMostOneOf(Vec<ComponentId>),
+}
+
+impl ArchetypeInvariant {
+    /// Creates an invariant requiring every archetype containing `C` to contain the components of
+    /// the bundle `B`.
+    pub fn requires<C: Component, B: Bundle>(world: &mut World) -> Self {
+        Self::Requires {
+            component: world.init_component::<C>(),
+            required: Self::bundle_components::<B>(world),
+        }
+    }
+
+    /// Creates an invariant preventing every archetype containing `C` from containing any of the
+    /// components of the bundle `B`.
+    pub fn forbids<C: Component, B: Bundle>(world: &mut World) -> Self {
+        Self::Forbids {
+            component: world.init_component::<C>(),
+            forbidden: Self::bundle_components::<B>(world),
+        }
+    }
+
+    /// Creates an invariant preventing archetypes from containing more than one of the components
+    /// of the bundle `B`.
+    pub fn at_most_one_of<B: Bundle>(world: &mut World) -> Self {
+        Self::AtMostOneOf(Self::bundle_components::<B>(world))
+    }
+
+    fn bundle_components<B: Bundle>(world: &mut World) -> Vec<ComponentId> {
+        let mut ids = Vec::new();
+        B::component_ids(&mut world.components, &mut world.storages, &mut |id| {
+            ids.push(id);
+        });
+        ids
+    }
+
+    /// Returns `true` if the `archetype` satisfies this invariant.
+    pub fn is_satisfied_by(&self, archetype: &Archetype) -> bool {
+        match self {
+            Self::Requires {
+                component,
+                required,
+            } => {
+                !archetype.contains(*component) || required.iter().all(|&id| archetype.contains(id))
+            }
+            Self::Forbids {
+                component,
+                forbidden,
+            } => {
+                !archetype.contains(*component)
+                    || !forbidden.iter().any(|&id| archetype.contains(id))
+            }
+            Self::AtMostOneOf(ids) => ids.iter().filter(|&&id| archetype.contains(id)).count() <= 1,
+        }
+    }
+
+    /// Describes the invariant with the names of its components.
+    fn describe(&self, components: &Components) -> String {
+        let name = |id: &ComponentId| components.get_name(*id).unwrap_or("<unknown>");
+        let names = |ids: &[ComponentId]| ids.iter().map(name).collect::<Vec<_>>().join(", ");
+        match self {
+            Self::Requires {
+                component,
+                required,
+            } => format!("{} requires [{}]", name(component), names(required)),
+            Self::Forbids {
+                component,
+                forbidden,
+            } => format!("{} forbids [{}]", name(component), names(forbidden)),
+            Self::AtMostOneOf(ids) => format!("at most one of [{}]", names(ids)),
+        }
+    }
+}
+
+/// What happens when an entity is moved to an archetype violating an [`ArchetypeInvariant`].
+///
+/// The policy of a world is set with [`World::set_archetype_invariant_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchetypeInvariantPolicy {
+    /// Panics with the entity and the names of the components involved.
+    #[default]
+    Panic,
+    /// Logs a warning with the entity and the names of the components involved.
+    Warn,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArchetypeInvariant, ArchetypeInvariantPolicy};
+    use crate::{self as bevy_ecs, component::Component, world::World};
+
+    #[derive(Component)]
+    struct A;
+    #[derive(Component)]
+    struct B;
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct C;
+
+    #[test]
+    #[should_panic(
+        expected = "bevy_ecs::archetype::tests::A forbids [bevy_ecs::archetype::tests::B]"
+    )]
+    fn forbids_violation_panics() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::forbids::<A, B>(&mut world);
+        world.add_archetype_invariant(invariant);
+
+        let entity = world.spawn(A).id();
+        world.entity_mut(entity).insert(B);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "bevy_ecs::archetype::tests::A requires [bevy_ecs::archetype::tests::B, bevy_ecs::archetype::tests::C]"
+    )]
+    fn requires_violation_panics() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::requires::<A, (B, C)>(&mut world);
+        world.add_archetype_invariant(invariant);
+
+        world.spawn((A, B, C));
+        world.spawn((A, B));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "bevy_ecs::archetype::tests::A requires [bevy_ecs::archetype::tests::B]"
+    )]
+    fn requires_checked_on_removal() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::requires::<A, B>(&mut world);
+        world.add_archetype_invariant(invariant);
+
+        let entity = world.spawn((A, B)).id();
+        world.entity_mut(entity).remove::<B>();
+    }
+
+    #[test]
+    #[should_panic(expected = "violates the archetype invariant")]
+    fn every_moved_entity_is_reported() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::forbids::<A, B>(&mut world);
+        world.add_archetype_invariant(invariant);
+        world.set_archetype_invariant_policy(ArchetypeInvariantPolicy::Warn);
+        world.spawn((A, B));
+
+        // The archetype already exists, but the second entity moved to it is reported too.
+        world.set_archetype_invariant_policy(ArchetypeInvariantPolicy::Panic);
+        world.spawn((A, B));
+    }
+
+    #[test]
+    #[should_panic(expected = "violates the archetype invariant")]
+    fn spawn_batch_is_reported() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::forbids::<A, B>(&mut world);
+        world.add_archetype_invariant(invariant);
+
+        world.spawn_batch([(A, B), (A, B)]).for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "violates the archetype invariant")]
+    fn insert_or_spawn_batch_is_reported() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::forbids::<A, B>(&mut world);
+        world.add_archetype_invariant(invariant);
+
+        let entity = world.spawn(A).id();
+        let _ = world.insert_or_spawn_batch([(entity, B)]);
+    }
+
+    #[test]
+    fn checked_after_insert_hooks() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::requires::<A, B>(&mut world);
+        world.add_archetype_invariant(invariant);
+        world
+            .register_component_hooks::<A>()
+            .on_add(|world, entity, _| {
+                world.entity_mut(entity).insert(B);
+            });
+
+        let entity = world.spawn(A).id();
+        assert!(world.entity(entity).contains::<B>());
+    }
+
+    #[test]
+    #[should_panic(expected = "at most one of")]
+    fn at_most_one_of_violation_panics() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::at_most_one_of::<(A, B, C)>(&mut world);
+        world.add_archetype_invariant(invariant);
+
+        world.spawn(A);
+        world.spawn(C);
+        world.spawn((B, C));
+    }
+
+    #[test]
+    #[should_panic(expected = "violates the archetype invariant")]
+    fn existing_archetypes_are_checked() {
+        let mut world = World::new();
+        world.spawn((A, B));
+
+        let invariant = ArchetypeInvariant::forbids::<A, B>(&mut world);
+        world.add_archetype_invariant(invariant);
+    }
+
+    #[test]
+    fn warn_policy_does_not_panic() {
+        let mut world = World::new();
+        world.set_archetype_invariant_policy(ArchetypeInvariantPolicy::Warn);
+        let invariant = ArchetypeInvariant::forbids::<A, B>(&mut world);
+        world.add_archetype_invariant(invariant);
+
+        let entity = world.spawn((A, B)).id();
+        assert!(world.entity(entity).contains::<B>());
+        assert!(world
+            .archetypes()
+            .violates_invariants(world.entity(entity).archetype().id()));
+    }
+}
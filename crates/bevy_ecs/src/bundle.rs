@@ -10,7 +10,10 @@ use crate::{
         Archetype, ArchetypeId, Archetypes, BundleComponentStatus, ComponentStatus,
         SpawnBundleStatus,
     },
-    component::{Component, ComponentId, ComponentStorage, Components, StorageType, Tick},
+    component::{
+        Component, ComponentId, ComponentStorage, Components, RequiredComponentConstructor,
+        StorageType, Tick,
+    },
     entity::{Entities, Entity, EntityLocation},
     query::DebugCheckedUnwrap,
     storage::{SparseSetIndex, SparseSets, Storages, Table, TableRow},
@@ -284,6 +287,16 @@ pub struct BundleInfo {
     // must have its storage initialized (i.e. columns created in tables, sparse set created),
     // and must be in the same order as the source bundle type writes its components in.
     component_ids: Vec<ComponentId>,
+    // The components required by `component_ids` which are not part of the bundle, in the same
+    // order as the end of `contributed_component_ids`. Their storage must be initialized too.
+    required_components: Vec<BundleRequiredComponent>,
+    // `component_ids`, followed by the ids of `required_components`.
+    contributed_component_ids: Vec<ComponentId>,
+}
+
+struct BundleRequiredComponent {
+    storage_type: StorageType,
+    constructor: RequiredComponentConstructor,
 }
 
 impl BundleInfo {
@@ -326,11 +339,33 @@ impl BundleInfo {
             panic!("Bundle {bundle_type_name} has duplicate components: {names}");
         }
 
+        let mut contributed_component_ids = component_ids.clone();
+        let required_components = components
+            .collect_required_components(&component_ids)
+            .into_iter()
+            .map(|(required_id, constructor)| {
+                contributed_component_ids.push(required_id);
+                BundleRequiredComponent {
+                    // SAFETY: required components were initialized along with the components
+                    // requiring them.
+                    storage_type: unsafe { components.get_info_unchecked(required_id) }
+                        .storage_type(),
+                    constructor,
+                }
+            })
+            .collect();
+
         // SAFETY: The caller ensures that component_ids:
         // - is valid for the associated world
         // - has had its storage initialized
         // - is in the same order as the source bundle type
-        BundleInfo { id, component_ids }
+        // Required components were initialized, with their storage, by `Components`.
+        BundleInfo {
+            id,
+            component_ids,
+            required_components,
+            contributed_component_ids,
+        }
     }
 
     /// Returns a value identifying the associated [`Bundle`] type.
@@ -345,6 +380,22 @@ impl BundleInfo {
         &self.component_ids
     }
 
+    /// Returns the [ID](ComponentId) of each component that is
+    /// [required](Component#required-components) by this bundle's components, but not part of
+    /// the bundle itself.
+    #[inline]
+    pub fn required_components(&self) -> &[ComponentId] {
+        &self.contributed_component_ids[self.component_ids.len()..]
+    }
+
+    /// Returns the [ID](ComponentId) of each component that inserting this bundle may add to an
+    /// entity: the bundle's components, followed by their
+    /// [required components](Self::required_components).
+    #[inline]
+    pub fn contributed_components(&self) -> &[ComponentId] {
+        &self.contributed_component_ids
+    }
+
     pub(crate) fn get_bundle_inserter<'a, 'b>(
         &'b self,
         entities: &'a mut Entities,
@@ -441,6 +492,9 @@ impl BundleInfo {
     /// to look up the [`AddBundle`](crate::archetype::AddBundle) in the archetype graph, which requires
     /// ownership of the entity's current archetype.
     ///
+    /// The required components that `bundle_component_status` reports as added are constructed
+    /// and written as well.
+    ///
     /// `table` must be the "new" table for `entity`. `table_row` must have space allocated for the
     /// `entity`, `bundle` must match this [`BundleInfo`]'s type
     #[inline]
@@ -486,6 +540,34 @@ impl BundleInfo {
             }
             bundle_component += 1;
         });
+
+        for (index, required) in self.required_components.iter().enumerate() {
+            // SAFETY: index is a valid required component index for this bundle
+            if !unsafe { bundle_component_status.is_required_component_added(index) } {
+                continue;
+            }
+            let component_id = *self
+                .contributed_component_ids
+                .get_unchecked(self.component_ids.len() + index);
+            required.constructor.construct(&mut |component_ptr| {
+                match required.storage_type {
+                    StorageType::Table => {
+                        let column =
+                            // SAFETY: If the required component is added, the target table
+                            // contains it, as it is added to the archetype along with the bundle.
+                            unsafe { table.get_column_mut(component_id).debug_checked_unwrap() };
+                        column.initialize(table_row, component_ptr, change_tick);
+                    }
+                    StorageType::SparseSet => {
+                        let sparse_set =
+                            // SAFETY: The sparse set of a required component is created when the
+                            // component is initialized.
+                            unsafe { sparse_sets.get_mut(component_id).debug_checked_unwrap() };
+                        sparse_set.insert(entity, component_ptr, change_tick);
+                    }
+                }
+            });
+        }
     }
 
     /// Adds a bundle to the given archetype and returns the resulting archetype. This could be the
@@ -520,10 +602,32 @@ impl BundleInfo {
             }
         }
 
+        // required components are only added when missing, and never overwritten
+        let mut added_required_components = Vec::with_capacity(self.required_components.len());
+        for (&component_id, required) in self
+            .required_components()
+            .iter()
+            .zip(&self.required_components)
+        {
+            let added = !current_archetype.contains(component_id);
+            if added {
+                match required.storage_type {
+                    StorageType::Table => new_table_components.push(component_id),
+                    StorageType::SparseSet => new_sparse_set_components.push(component_id),
+                }
+            }
+            added_required_components.push(added);
+        }
+
         if new_table_components.is_empty() && new_sparse_set_components.is_empty() {
             let edges = current_archetype.edges_mut();
             // the archetype does not change when we add this bundle
-            edges.insert_add_bundle(self.id, archetype_id, bundle_status);
+            edges.insert_add_bundle(
+                self.id,
+                archetype_id,
+                bundle_status,
+                added_required_components,
+            );
             archetype_id
         } else {
             let table_id;
@@ -566,6 +670,7 @@ impl BundleInfo {
                 self.id,
                 new_archetype_id,
                 bundle_status,
+                added_required_components,
             );
             new_archetype_id
         }
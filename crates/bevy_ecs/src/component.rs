@@ -3,6 +3,7 @@
 use crate::{
     self as bevy_ecs,
     change_detection::MAX_CHANGE_AGE,
+    entity::Entity,
     storage::{SparseSetIndex, Storages},
     system::{Local, Resource, SystemParam},
     world::{FromWorld, World},
@@ -10,13 +11,16 @@ use crate::{
 };
 pub use bevy_ecs_macros::Component;
 use bevy_ptr::{OwningPtr, UnsafeCellDeref};
+use bevy_utils::HashSet;
 use std::cell::UnsafeCell;
 use std::{
     alloc::Layout,
     any::{Any, TypeId},
     borrow::Cow,
+    collections::VecDeque,
     marker::PhantomData,
     mem::needs_drop,
+    sync::Arc,
 };
 
 /// A data type that can be used to store data for an [entity].
@@ -89,6 +93,41 @@ use std::{
 /// [`Table`]: crate::storage::Table
 /// [`SparseSet`]: crate::storage::SparseSet
 ///
+/// # Required components
+///
+/// A component can require other components, which are inserted along with it whenever they are
+/// missing from the entity, through [`World::spawn`], [`EntityMut::insert`], [`Commands`] or any
+/// other way of inserting components. Required components are listed in the
+/// `#[component(require(...))]` attribute, either on their own, in which case their [`Default`]
+/// value is inserted, or with a constructor for their value:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component, Default, Debug, PartialEq)]
+/// struct Position(f32, f32);
+///
+/// #[derive(Component, Debug, PartialEq)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// #[component(require(Position, Health = || Health(100)))]
+/// struct Player;
+///
+/// let mut world = World::new();
+/// let player = world.spawn((Player, Position(1.0, 2.0))).id();
+/// // components that are already part of the bundle are not overwritten
+/// assert_eq!(world.get::<Position>(player), Some(&Position(1.0, 2.0)));
+/// assert_eq!(world.get::<Health>(player), Some(&Health(100)));
+/// ```
+///
+/// Requirements are transitive: the components required by a required component are inserted
+/// too. A component cannot require itself, directly or transitively, which panics when the
+/// component is initialized.
+///
+/// [`World::spawn`]: crate::world::World::spawn
+/// [`EntityMut::insert`]: crate::world::EntityMut::insert
+/// [`Commands`]: crate::system::Commands
+///
 /// # Implementing the trait for foreign types
 ///
 /// As a consequence of the [orphan rule], it is not possible to separate into two different crates the implementation of `Component` from the definition of a type.
@@ -150,6 +189,11 @@ pub trait Component: Send + Sync + 'static {
     /// A marker type indicating the storage type used for this component.
     /// This must be either [`TableStorage`] or [`SparseStorage`].
     type Storage: ComponentStorage;
+
+    /// Registers the components that are inserted along with this component when they are
+    /// missing. See the [required components](Component#required-components) section for more
+    /// details.
+    fn register_required_components(_required: &mut RequiredComponents) {}
 }
 
 /// Marker type for components stored in a [`Table`](crate::storage::Table).
@@ -201,11 +245,145 @@ pub enum StorageType {
     SparseSet,
 }
 
+/// A function that is run when a component is added to, inserted on, replaced on or removed from
+/// an entity.
+///
+/// Hooks are given exclusive access to the [`World`], along with the entity and the
+/// [`ComponentId`] of the component that triggered them.
+/// See [`World::register_component_hooks`] for more details.
+pub type ComponentHook = fn(&mut World, Entity, ComponentId);
+
+/// The lifecycle hooks of a component, registered through [`World::register_component_hooks`].
+///
+/// Hooks run synchronously, as part of the operation that triggered them:
+/// - `on_add` runs after the component was added to an entity that did not have it before.
+/// - `on_insert` runs after the component was inserted on an entity, whether or not the entity
+///   already had it. When both apply, `on_add` runs first.
+/// - `on_replace` runs before the value of the component is overwritten by an insertion, or
+///   before it is removed, so the hook can still read the previous value.
+/// - `on_remove` runs before the component is removed from an entity, or before the entity is
+///   despawned, so the hook can still read the component's value. It runs after `on_replace`.
+///
+/// A component can have any number of hooks of each kind, which run in the order they were
+/// registered in.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentHooks {
+    pub(crate) on_add: Vec<ComponentHook>,
+    pub(crate) on_insert: Vec<ComponentHook>,
+    pub(crate) on_replace: Vec<ComponentHook>,
+    pub(crate) on_remove: Vec<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Registers a [`ComponentHook`] that runs when this component is added to an entity.
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_add.push(hook);
+        self
+    }
+
+    /// Registers a [`ComponentHook`] that runs when this component is inserted on an entity,
+    /// including when it overwrites a previous value.
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_insert.push(hook);
+        self
+    }
+
+    /// Registers a [`ComponentHook`] that runs when the value of this component is about to be
+    /// overwritten by an insertion or removed from an entity, including when the entity is
+    /// despawned.
+    pub fn on_replace(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_replace.push(hook);
+        self
+    }
+
+    /// Registers a [`ComponentHook`] that runs when this component is removed from an entity,
+    /// including when the entity is despawned.
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_remove.push(hook);
+        self
+    }
+
+    /// Returns `true` if no hooks have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.on_add.is_empty()
+            && self.on_insert.is_empty()
+            && self.on_replace.is_empty()
+            && self.on_remove.is_empty()
+    }
+}
+
+/// A type-erased constructor for the value of a required component.
+#[derive(Clone)]
+pub struct RequiredComponentConstructor(Arc<dyn Fn(&mut dyn FnMut(OwningPtr<'_>)) + Send + Sync>);
+
+impl RequiredComponentConstructor {
+    /// Creates a constructor for the component `C`.
+    pub fn new<C: Component>(constructor: impl Fn() -> C + Send + Sync + 'static) -> Self {
+        Self(Arc::new(move |func| {
+            OwningPtr::make(constructor(), func);
+        }))
+    }
+
+    /// Constructs a new value and passes it to `func`, which must take ownership of it.
+    pub(crate) fn construct(&self, func: &mut dyn FnMut(OwningPtr<'_>)) {
+        (self.0)(func);
+    }
+}
+
+impl std::fmt::Debug for RequiredComponentConstructor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequiredComponentConstructor")
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone)]
+struct RequiredComponent {
+    init: fn(&mut Components, &mut Storages) -> ComponentId,
+    constructor: RequiredComponentConstructor,
+}
+
+/// The components required by a component, which are inserted along with it when they are
+/// missing from an entity.
+///
+/// See the [required components](Component#required-components) section of [`Component`] for
+/// more details.
+#[derive(Clone, Default)]
+pub struct RequiredComponents {
+    components: Vec<RequiredComponent>,
+}
+
+impl RequiredComponents {
+    /// Requires the component `C`, using `constructor` to create its value when it is missing.
+    pub fn register<C: Component>(
+        &mut self,
+        constructor: impl Fn() -> C + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.components.push(RequiredComponent {
+            init: |components, storages| components.init_component::<C>(storages),
+            constructor: RequiredComponentConstructor::new(constructor),
+        });
+        self
+    }
+
+    /// Returns the number of required components.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns `true` if no components are required.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
 /// Stores metadata for a type of component or resource stored in a specific [`World`].
 #[derive(Debug)]
 pub struct ComponentInfo {
     id: ComponentId,
     descriptor: ComponentDescriptor,
+    hooks: ComponentHooks,
+    required_components: Vec<(ComponentId, RequiredComponentConstructor)>,
 }
 
 impl ComponentInfo {
@@ -259,9 +437,27 @@ impl ComponentInfo {
         self.descriptor.is_send_and_sync
     }
 
+    /// Returns the lifecycle hooks of the current component.
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    /// Returns the components directly required by the current component.
+    ///
+    /// See [`Components::required_components`] for the transitive requirements.
+    pub fn required_components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.required_components.iter().map(|(id, _)| *id)
+    }
+
     /// Create a new [`ComponentInfo`].
     pub(crate) fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
-        ComponentInfo { id, descriptor }
+        ComponentInfo {
+            id,
+            descriptor,
+            hooks: ComponentHooks::default(),
+            required_components: Vec::new(),
+        }
     }
 }
 
@@ -329,6 +525,7 @@ pub struct ComponentDescriptor {
     // this descriptor describes.
     // None if the underlying type doesn't need to be dropped
     drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+    required: RequiredComponents,
 }
 
 // We need to ignore the `drop` field in our `Debug` impl
@@ -340,6 +537,7 @@ impl std::fmt::Debug for ComponentDescriptor {
             .field("is_send_and_sync", &self.is_send_and_sync)
             .field("type_id", &self.type_id)
             .field("layout", &self.layout)
+            .field("required_components", &self.required.len())
             .finish()
     }
 }
@@ -352,6 +550,8 @@ impl ComponentDescriptor {
 
     /// Create a new `ComponentDescriptor` for the type `T`.
     pub fn new<T: Component>() -> Self {
+        let mut required = RequiredComponents::default();
+        T::register_required_components(&mut required);
         Self {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type: T::Storage::STORAGE_TYPE,
@@ -359,6 +559,7 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            required,
         }
     }
 
@@ -380,6 +581,7 @@ impl ComponentDescriptor {
             type_id: None,
             layout,
             drop,
+            required: RequiredComponents::default(),
         }
     }
 
@@ -396,6 +598,7 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            required: RequiredComponents::default(),
         }
     }
 
@@ -407,9 +610,23 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            required: RequiredComponents::default(),
         }
     }
 
+    /// Requires the component `C`, which is inserted along with the described component when it
+    /// is missing, using `constructor` to create its value.
+    ///
+    /// The requirements of [`Component`] types are declared through
+    /// [`Component::register_required_components`] instead.
+    pub fn with_required<C: Component>(
+        mut self,
+        constructor: impl Fn() -> C + Send + Sync + 'static,
+    ) -> Self {
+        self.required.register(constructor);
+        self
+    }
+
     /// Returns a value indicating the storage strategy for the current component.
     #[inline]
     pub fn storage_type(&self) -> StorageType {
@@ -436,25 +653,34 @@ pub struct Components {
     components: Vec<ComponentInfo>,
     indices: TypeIdMap<usize>,
     resource_indices: TypeIdMap<usize>,
+    has_hooks: bool,
 }
 
 impl Components {
     /// Initializes a component of type `T` with this instance.
     /// If a component of this type has already been initialized, this will return
     /// the ID of the pre-existing component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component requires itself, directly or through other required components.
     #[inline]
     pub fn init_component<T: Component>(&mut self, storages: &mut Storages) -> ComponentId {
         let type_id = TypeId::of::<T>();
+        if let Some(index) = self.indices.get(&type_id) {
+            return ComponentId(*index);
+        }
 
-        let Components {
-            indices,
-            components,
-            ..
-        } = self;
-        let index = indices.entry(type_id).or_insert_with(|| {
-            Components::init_component_inner(components, storages, ComponentDescriptor::new::<T>())
-        });
-        ComponentId(*index)
+        let index = Components::init_component_inner(
+            &mut self.components,
+            storages,
+            ComponentDescriptor::new::<T>(),
+        );
+        // the index is registered before the required components, so that a cycle of
+        // requirements terminates and can be reported
+        self.indices.insert(type_id, index);
+        self.init_required_components(ComponentId(index), storages);
+        ComponentId(index)
     }
 
     /// Initializes a component described by `descriptor`.
@@ -463,15 +689,111 @@ impl Components {
     ///
     /// If this method is called multiple times with identical descriptors, a distinct `ComponentId`
     /// will be created for each one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component requires itself through other required components.
     pub fn init_component_with_descriptor(
         &mut self,
         storages: &mut Storages,
         descriptor: ComponentDescriptor,
     ) -> ComponentId {
         let index = Components::init_component_inner(&mut self.components, storages, descriptor);
+        self.init_required_components(ComponentId(index), storages);
         ComponentId(index)
     }
 
+    /// Initializes the components required by the descriptor of `id`, and checks that they don't
+    /// form a cycle.
+    fn init_required_components(&mut self, id: ComponentId, storages: &mut Storages) {
+        let required = std::mem::take(&mut self.components[id.0].descriptor.required);
+        if required.is_empty() {
+            return;
+        }
+        let required_components = required
+            .components
+            .iter()
+            .map(|required| {
+                let required_id = (required.init)(self, storages);
+                (required_id, required.constructor.clone())
+            })
+            .collect();
+        self.components[id.0].required_components = required_components;
+
+        if let Some(cycle) = self.find_required_cycle(id) {
+            let names = cycle
+                .iter()
+                .map(|&id| self.components[id.0].name())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            panic!("Required components form a cycle: {names}");
+        }
+    }
+
+    /// Returns the path from `id` back to itself through required components, if there is one.
+    fn find_required_cycle(&self, id: ComponentId) -> Option<Vec<ComponentId>> {
+        let mut path = vec![id];
+        let mut visited = HashSet::new();
+        self.find_required_cycle_recursive(id, &mut path, &mut visited)
+            .then_some(path)
+    }
+
+    fn find_required_cycle_recursive(
+        &self,
+        target: ComponentId,
+        path: &mut Vec<ComponentId>,
+        visited: &mut HashSet<ComponentId>,
+    ) -> bool {
+        let current = *path.last().unwrap();
+        for (required, _) in &self.components[current.0].required_components {
+            path.push(*required);
+            if *required == target
+                || (visited.insert(*required)
+                    && self.find_required_cycle_recursive(target, path, visited))
+            {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    /// Returns the components required by `id`, directly or through other required components.
+    ///
+    /// Returns an empty list if `id` is not a valid [`ComponentId`].
+    pub fn required_components(&self, id: ComponentId) -> Vec<ComponentId> {
+        self.collect_required_components(&[id])
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Returns the components required by `ids`, directly or transitively, that are not part of
+    /// `ids` themselves, along with the constructors of their values.
+    ///
+    /// Requirements are visited in breadth-first order, so the constructor of the closest
+    /// requirement is used when a component is required more than once.
+    pub(crate) fn collect_required_components(
+        &self,
+        ids: &[ComponentId],
+    ) -> Vec<(ComponentId, RequiredComponentConstructor)> {
+        let mut required_components = Vec::new();
+        let mut visited: HashSet<ComponentId> = ids.iter().copied().collect();
+        let mut queue: VecDeque<ComponentId> = ids.iter().copied().collect();
+        while let Some(id) = queue.pop_front() {
+            let Some(info) = self.get_info(id) else {
+                continue;
+            };
+            for (required, constructor) in &info.required_components {
+                if visited.insert(*required) {
+                    required_components.push((*required, constructor.clone()));
+                    queue.push_back(*required);
+                }
+            }
+        }
+        required_components
+    }
+
     #[inline]
     fn init_component_inner(
         components: &mut Vec<ComponentInfo>,
@@ -525,6 +847,33 @@ impl Components {
         self.components.get_unchecked(id.0)
     }
 
+    /// Gets mutable access to the lifecycle hooks of the given component.
+    ///
+    /// Returns `None` if `id` is not a valid [`ComponentId`].
+    #[inline]
+    pub fn get_hooks_mut(&mut self, id: ComponentId) -> Option<&mut ComponentHooks> {
+        let info = self.components.get_mut(id.0)?;
+        self.has_hooks = true;
+        Some(&mut info.hooks)
+    }
+
+    /// Returns `true` if hooks may have been registered for any component.
+    ///
+    /// This is used to skip looking up hooks entirely in worlds that don't use them.
+    #[inline]
+    pub fn has_hooks(&self) -> bool {
+        self.has_hooks
+    }
+
+    /// Returns `true` if any of the given components has a lifecycle hook.
+    pub(crate) fn any_hooks(&self, ids: &[ComponentId]) -> bool {
+        self.has_hooks
+            && ids.iter().any(|&id| {
+                self.get_info(id)
+                    .is_some_and(|info| !info.hooks().is_empty())
+            })
+    }
+
     /// Type-erased equivalent of [`Components::component_id`].
     #[inline]
     pub fn get_id(&self, type_id: TypeId) -> Option<ComponentId> {
@@ -847,3 +1196,141 @@ impl<T: Component> FromWorld for InitComponentId<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::component::{ComponentDescriptor, StorageType};
+    use crate::prelude::*;
+    use crate::system::CommandQueue;
+    use std::alloc::Layout;
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    struct Position(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct Health(u32);
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    #[component(require(Position))]
+    struct Body;
+
+    #[derive(Component)]
+    #[component(require(Body, Health = || Health(10)))]
+    struct Player;
+
+    #[test]
+    fn required_components_are_inserted() {
+        let mut world = World::new();
+        let player = world.spawn(Player).id();
+        assert_eq!(world.get::<Body>(player), Some(&Body));
+        assert_eq!(world.get::<Position>(player), Some(&Position(0)));
+        assert_eq!(world.get::<Health>(player), Some(&Health(10)));
+
+        let entity = world.spawn(Position(1)).id();
+        world.entity_mut(entity).insert(Player);
+        assert_eq!(world.get::<Position>(entity), Some(&Position(1)));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+
+        let mut queue = CommandQueue::default();
+        let entity = Commands::new(&mut queue, &world).spawn(Body).id();
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Position>(entity), Some(&Position(0)));
+
+        let batch: Vec<_> = world.spawn_batch([Player, Player]).collect();
+        for entity in batch {
+            assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+        }
+    }
+
+    #[test]
+    fn required_components_are_not_overwritten() {
+        let mut world = World::new();
+        let player = world.spawn((Player, Position(2), Health(5))).id();
+        assert_eq!(world.get::<Position>(player), Some(&Position(2)));
+        assert_eq!(world.get::<Health>(player), Some(&Health(5)));
+
+        world.entity_mut(player).insert(Health(3));
+        world.entity_mut(player).insert(Player);
+        assert_eq!(world.get::<Health>(player), Some(&Health(3)));
+
+        // removed required components are inserted again along with the component
+        world.entity_mut(player).remove::<Position>();
+        world.entity_mut(player).insert(Body);
+        assert_eq!(world.get::<Position>(player), Some(&Position(0)));
+    }
+
+    #[test]
+    fn required_components_of_dynamic_components() {
+        let mut world = World::new();
+        // SAFETY: the component has no data and doesn't need to be dropped
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                "Dynamic",
+                StorageType::Table,
+                Layout::new::<()>(),
+                None,
+            )
+        }
+        .with_required(|| Health(7));
+        let dynamic = world.init_component_with_descriptor(descriptor);
+        let health = world.init_component::<Health>();
+        assert_eq!(world.components().required_components(dynamic), [health]);
+
+        let mut entity = world.spawn_empty();
+        // SAFETY: the pointer matches the component's layout
+        unsafe {
+            bevy_ptr::OwningPtr::make((), |ptr| {
+                entity.insert_by_id(dynamic, ptr);
+            });
+        }
+        assert_eq!(entity.get::<Health>(), Some(&Health(7)));
+    }
+
+    #[test]
+    fn required_component_hooks() {
+        #[derive(Resource, Default)]
+        struct Added(u32);
+
+        let mut world = World::new();
+        world.init_resource::<Added>();
+        world
+            .register_component_hooks::<Position>()
+            .on_add(|world, _, _| world.resource_mut::<Added>().0 += 1);
+
+        let entity = world.spawn(Body).id();
+        assert_eq!(world.resource::<Added>().0, 1);
+        world.entity_mut(entity).insert(Body);
+        assert_eq!(world.resource::<Added>().0, 1);
+    }
+
+    #[test]
+    fn transitive_required_components() {
+        let mut world = World::new();
+        let player = world.init_component::<Player>();
+        let info = world.components().get_info(player).unwrap();
+        assert_eq!(info.required_components().count(), 2);
+
+        let ids = [
+            world.init_component::<Body>(),
+            world.init_component::<Health>(),
+            world.init_component::<Position>(),
+        ];
+        assert_eq!(world.components().required_components(player), ids);
+    }
+
+    #[derive(Component, Default)]
+    #[component(require(CycleB))]
+    struct CycleA;
+
+    #[derive(Component, Default)]
+    #[component(require(CycleA))]
+    struct CycleB;
+
+    #[test]
+    #[should_panic = "Required components form a cycle"]
+    fn required_components_cycle() {
+        World::new().init_component::<CycleA>();
+    }
+}
@@ -0,0 +1,35 @@
+use crate as bevy_ecs;
+use crate::component::Component;
+
+/// Marks an entity as disabled, turning it off without despawning it or removing its other
+/// components.
+///
+/// Disabled entities are excluded from queries, unless the query mentions [`Disabled`] itself,
+/// like `With<Disabled>`, `Option<&Disabled>`, `Has<Disabled>` or the
+/// [`IncludeDisabled`](crate::query::IncludeDisabled) filter. They are also skipped by
+/// [`World::iter_entities`](crate::world::World::iter_entities).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::{entity::Disabled, query::IncludeDisabled};
+///
+/// #[derive(Component)]
+/// struct Bullet;
+///
+/// let mut world = World::new();
+/// let bullet = world.spawn((Bullet, Disabled)).id();
+///
+/// let mut active = world.query_filtered::<Entity, With<Bullet>>();
+/// assert_eq!(active.iter(&world).count(), 0);
+///
+/// let mut pooled = world.query_filtered::<Entity, (With<Bullet>, With<Disabled>)>();
+/// assert_eq!(pooled.single(&world), bullet);
+///
+/// let mut all = world.query_filtered::<Entity, (With<Bullet>, IncludeDisabled)>();
+/// assert_eq!(all.iter(&world).count(), 1);
+///
+/// let mut flagged = world.query_filtered::<Has<Disabled>, With<Bullet>>();
+/// assert_eq!(flagged.single(&world), true);
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Disabled;
@@ -127,6 +127,22 @@ impl EntityMap {
         mapper.finish(world);
         result
     }
+
+    /// Like [`Self::world_scope()`], but for mapping entities of `world` to other entities of the
+    /// same world, for example to their clones. Entities that are not in the map are left
+    /// unchanged by [`EntityMapper::get_or_reserve`], instead of being mapped to dead entities.
+    pub fn world_scope_preserving_unmapped<R>(
+        &mut self,
+        world: &mut World,
+        f: impl FnOnce(&mut World, &mut EntityMapper) -> R,
+    ) -> R {
+        let mut mapper = EntityMapper {
+            map: self,
+            dead_start: None,
+            generations: 0,
+        };
+        f(world, &mut mapper)
+    }
 }
 
 /// A wrapper for [`EntityMap`], augmenting it with the ability to allocate new [`Entity`] references in a destination
@@ -138,23 +154,30 @@ impl EntityMap {
 pub struct EntityMapper<'m> {
     /// The wrapped [`EntityMap`].
     map: &'m mut EntityMap,
-    /// A base [`Entity`] used to allocate new references.
-    dead_start: Entity,
+    /// A base [`Entity`] used to allocate new references, or `None` if unmapped entities are
+    /// left unchanged.
+    dead_start: Option<Entity>,
     /// The number of generations this mapper has allocated thus far.
     generations: u32,
 }
 
 impl<'m> EntityMapper<'m> {
     /// Returns the corresponding mapped entity or reserves a new dead entity ID if it is absent.
+    ///
+    /// If the mapper was created by [`EntityMap::world_scope_preserving_unmapped`], absent
+    /// entities are returned unchanged instead.
     pub fn get_or_reserve(&mut self, entity: Entity) -> Entity {
         if let Some(mapped) = self.map.get(entity) {
             return mapped;
         }
+        let Some(dead_start) = self.dead_start else {
+            return entity;
+        };
 
         // this new entity reference is specifically designed to never represent any living entity
         let new = Entity {
-            generation: self.dead_start.generation + self.generations,
-            index: self.dead_start.index,
+            generation: dead_start.generation + self.generations,
+            index: dead_start.index,
         };
         self.generations += 1;
 
@@ -178,7 +201,7 @@ impl<'m> EntityMapper<'m> {
         Self {
             map,
             // SAFETY: Entities data is kept in a valid state via `EntityMap::world_scope`
-            dead_start: unsafe { world.entities_mut().alloc() },
+            dead_start: Some(unsafe { world.entities_mut().alloc() }),
             generations: 0,
         }
     }
@@ -188,10 +211,13 @@ impl<'m> EntityMapper<'m> {
     /// renders the [`EntityMapper`] unable to safely allocate any more references, this method takes ownership of
     /// `self` in order to render it unusable.
     fn finish(self, world: &mut World) {
+        let Some(dead_start) = self.dead_start else {
+            return;
+        };
         // SAFETY: Entities data is kept in a valid state via `EntityMap::world_scope`
         let entities = unsafe { world.entities_mut() };
-        assert!(entities.free(self.dead_start).is_some());
-        assert!(entities.reserve_generations(self.dead_start.index, self.generations));
+        assert!(entities.free(dead_start).is_some());
+        assert!(entities.reserve_generations(dead_start.index, self.generations));
     }
 }
 
@@ -244,4 +270,20 @@ mod tests {
         assert_eq!(entity.index(), dead_ref.index());
         assert!(entity.generation() > dead_ref.generation());
     }
+
+    #[test]
+    fn world_scope_preserving_unmapped() {
+        let mut map = EntityMap::default();
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+        map.insert(a, b);
+
+        let mapped = map.world_scope_preserving_unmapped(&mut world, |_, mapper| {
+            [mapper.get_or_reserve(a), mapper.get_or_reserve(c)]
+        });
+        assert_eq!(mapped, [b, c]);
+        assert_eq!(world.entities().len(), 3);
+    }
 }
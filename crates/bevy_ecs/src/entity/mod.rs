@@ -35,8 +35,10 @@
 //! [`World::despawn`]: crate::world::World::despawn
 //! [`EntityMut::insert`]: crate::world::EntityMut::insert
 //! [`EntityMut::remove`]: crate::world::EntityMut::remove
+mod disabled;
 mod map_entities;
 
+pub use disabled::Disabled;
 pub use map_entities::*;
 
 use crate::{
@@ -543,6 +545,45 @@ impl Entities {
         result
     }
 
+    /// Captures the generations of all entity ids and the list of free ids, which determine the
+    /// ids handed out by the next allocations.
+    pub(crate) fn allocator_state(&mut self) -> EntityAllocatorState {
+        self.verify_flushed();
+        EntityAllocatorState {
+            generations: self.meta.iter().map(|meta| meta.generation).collect(),
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// Restores the state captured by [`allocator_state`](Entities::allocator_state), so that the
+    /// next allocations hand out the same ids as they would have then.
+    ///
+    /// The entities alive must be the ones that were alive when the state was captured.
+    pub(crate) fn restore_allocator_state(&mut self, state: &EntityAllocatorState) {
+        self.verify_flushed();
+        let len = state.generations.len();
+        if self.meta.len() > len {
+            assert!(
+                self.meta[len..]
+                    .iter()
+                    .all(|meta| meta.location.archetype_id == ArchetypeId::INVALID),
+                "entities allocated since the state was captured are still alive"
+            );
+            self.meta.truncate(len);
+        } else {
+            self.meta.resize(len, EntityMeta::EMPTY);
+        }
+        for (meta, &generation) in self.meta.iter_mut().zip(&state.generations) {
+            if meta.location.archetype_id == ArchetypeId::INVALID {
+                meta.generation = generation;
+            } else {
+                debug_assert_eq!(meta.generation, generation);
+            }
+        }
+        self.pending.clone_from(&state.pending);
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+    }
+
     /// Destroy an entity, allowing it to be reused.
     ///
     /// Must not be called while reserved entities are awaiting `flush()`.
@@ -768,6 +809,13 @@ impl Entities {
     }
 }
 
+/// The state of the id allocator of [`Entities`], see [`Entities::allocator_state`].
+#[derive(Clone, Debug)]
+pub(crate) struct EntityAllocatorState {
+    generations: Vec<u32>,
+    pending: Vec<u32>,
+}
+
 // This type is repr(C) to ensure that the layout and values within it can be safe to fully fill
 // with u8::MAX, as required by [`Entities::flush_and_reserve_invalid_assuming_no_entities`].
 // Safety:
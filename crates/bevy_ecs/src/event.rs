@@ -1,7 +1,9 @@
 //! Event handling types.
 
 use crate as bevy_ecs;
+use crate::change_detection::DetectChangesMut;
 use crate::system::{Local, Res, ResMut, Resource, SystemParam};
+use crate::world::{FromWorld, World};
 pub use bevy_ecs_macros::Event;
 use bevy_utils::detailed_trace;
 use std::ops::{Deref, DerefMut};
@@ -12,6 +14,10 @@ use std::{
     iter::Chain,
     marker::PhantomData,
     slice::Iter,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc, Weak,
+    },
 };
 /// A type that can be stored in an [`Events<E>`] resource
 /// You can conveniently access events using the [`EventReader`] and [`EventWriter`] system parameter.
@@ -88,6 +94,35 @@ struct EventInstance<E: Event> {
     pub event: E,
 }
 
+/// How long the events of an [`Events`] collection are kept before being dropped.
+///
+/// The retention of an event type is usually chosen when adding it to an app with
+/// `App::add_event_with_retention`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventRetention {
+    /// Events are dropped on the second [`Events::update`] after they were sent.
+    ///
+    /// Readers that don't read at least once every other update, such as systems that don't run
+    /// every frame, may miss events.
+    #[default]
+    DoubleBuffered,
+    /// Events are kept until every registered reader has read them, and at least until the second
+    /// [`Events::update`] after they were sent.
+    ///
+    /// Every [`EventReader`] is registered with the events when its system is initialized, and
+    /// [`ManualEventReader`]s can be registered with [`Events::get_registered_reader`]. When no
+    /// reader is registered, events are double buffered.
+    ///
+    /// Events accumulate while a registered reader doesn't read them, for example while its system
+    /// doesn't run.
+    UntilConsumed,
+    /// Only the given number of most recent events are kept, regardless of [`Events::update`].
+    ///
+    /// When an event is sent while the buffer is full, the oldest event is dropped. Readers can
+    /// tell how many events they missed with [`ManualEventReader::missed_events`].
+    RingBuffer(usize),
+}
+
 /// An event collection that represents the events that occurred within the last two
 /// [`Events::update`] calls.
 /// Events can be written to using an [`EventWriter`]
@@ -151,6 +186,9 @@ struct EventInstance<E: Event> {
 ///
 /// The buffers in [`Events`] will grow indefinitely if [`update`](Events::update) is never called.
 ///
+/// Other [`EventRetention`] policies can be used to keep events for longer than two updates,
+/// see [`Events::with_retention`].
+///
 /// An alternative call pattern would be to call [`update`](Events::update)
 /// manually across frames to control when events are cleared.
 /// This complicates consumption and risks ever-expanding memory usage if not cleaned up,
@@ -168,6 +206,9 @@ pub struct Events<E: Event> {
     /// Holds the newer events.
     events_b: EventSequence<E>,
     event_count: usize,
+    retention: EventRetention,
+    /// The positions of the registered readers, see [`EventRetention::UntilConsumed`].
+    readers: Vec<Weak<AtomicUsize>>,
 }
 
 // Derived Default impl would incorrectly require E: Default
@@ -177,6 +218,8 @@ impl<E: Event> Default for Events<E> {
             events_a: Default::default(),
             events_b: Default::default(),
             event_count: Default::default(),
+            retention: Default::default(),
+            readers: Default::default(),
         }
     }
 }
@@ -223,10 +266,38 @@ impl<E: Event> DerefMut for EventSequence<E> {
 /// Reads events of type `T` in order and tracks which events have already been read.
 #[derive(SystemParam, Debug)]
 pub struct EventReader<'w, 's, E: Event> {
-    reader: Local<'s, ManualEventReader<E>>,
+    reader: Local<'s, SystemEventReader<E>>,
     events: Res<'w, Events<E>>,
 }
 
+/// The [`ManualEventReader`] of an [`EventReader`], registered with the [`Events`] resource if it
+/// exists when the system is initialized.
+#[derive(Debug)]
+struct SystemEventReader<E: Event>(ManualEventReader<E>);
+
+impl<E: Event> FromWorld for SystemEventReader<E> {
+    fn from_world(world: &mut World) -> Self {
+        Self(match world.get_resource_mut::<Events<E>>() {
+            Some(mut events) => events.bypass_change_detection().get_registered_reader(),
+            None => ManualEventReader::default(),
+        })
+    }
+}
+
+impl<E: Event> Deref for SystemEventReader<E> {
+    type Target = ManualEventReader<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E: Event> DerefMut for SystemEventReader<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl<'w, 's, E: Event> EventReader<'w, 's, E> {
     /// Iterates over the events this [`EventReader`] has not seen yet. This updates the
     /// [`EventReader`]'s event counter, which means subsequent event reads will not include events
@@ -367,6 +438,8 @@ impl<'w, E: Event> EventWriter<'w, E> {
 #[derive(Debug)]
 pub struct ManualEventReader<E: Event> {
     last_event_count: usize,
+    /// The position shared with the [`Events`] this reader is registered with, if any.
+    cursor: Option<Arc<AtomicUsize>>,
     _marker: PhantomData<E>,
 }
 
@@ -374,6 +447,7 @@ impl<E: Event> Default for ManualEventReader<E> {
     fn default() -> Self {
         ManualEventReader {
             last_event_count: 0,
+            cursor: None,
             _marker: Default::default(),
         }
     }
@@ -421,6 +495,14 @@ impl<E: Event> ManualEventReader<E> {
     /// See [`EventReader::clear()`]
     pub fn clear(&mut self, events: &Events<E>) {
         self.last_event_count = events.event_count;
+        self.sync_cursor();
+    }
+
+    /// Shares the position of this reader with the [`Events`] it is registered with.
+    fn sync_cursor(&self) {
+        if let Some(cursor) = &self.cursor {
+            cursor.store(self.last_event_count, atomic::Ordering::Relaxed);
+        }
     }
 }
 
@@ -482,6 +564,7 @@ impl<'a, E: Event> ManualEventIteratorWithId<'a, E> {
         // Ensure `len` is implemented correctly
         debug_assert_eq!(unread_count, reader.len(events));
         reader.last_event_count = events.event_count - unread_count;
+        reader.sync_cursor();
         // Iterate the oldest first, then the newer events
         let chain = a.iter().chain(b.iter());
 
@@ -509,6 +592,7 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
             Some(item) => {
                 detailed_trace!("EventReader::iter() -> {}", item.1);
                 self.reader.last_event_count += 1;
+                self.reader.sync_cursor();
                 self.unread -= 1;
                 Some(item)
             }
@@ -519,10 +603,12 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
         if let Some(EventInstance { event_id, event }) = self.chain.nth(n) {
             self.reader.last_event_count += n + 1;
+            self.reader.sync_cursor();
             self.unread -= n + 1;
             Some((event, *event_id))
         } else {
             self.reader.last_event_count += self.unread;
+            self.reader.sync_cursor();
             self.unread = 0;
             None
         }
@@ -534,11 +620,13 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
     {
         let EventInstance { event_id, event } = self.chain.last()?;
         self.reader.last_event_count += self.unread;
+        self.reader.sync_cursor();
         Some((event, *event_id))
     }
 
     fn count(self) -> usize {
         self.reader.last_event_count += self.unread;
+        self.reader.sync_cursor();
         self.unread
     }
 
@@ -567,6 +655,7 @@ impl<E: Event> Events<E> {
 
         self.events_b.push(event_instance);
         self.event_count += 1;
+        self.enforce_capacity();
     }
 
     /// Sends the default value of the event. Useful when the event is an empty struct.
@@ -582,6 +671,20 @@ impl<E: Event> Events<E> {
         ManualEventReader::default()
     }
 
+    /// Gets a new [`ManualEventReader`] registered with these events. This will include all events
+    /// already in the event buffers.
+    ///
+    /// With [`EventRetention::UntilConsumed`], events are kept until every registered reader has
+    /// read them. A reader stops being registered when it is dropped.
+    pub fn get_registered_reader(&mut self) -> ManualEventReader<E> {
+        let cursor = Arc::new(AtomicUsize::new(0));
+        self.readers.push(Arc::downgrade(&cursor));
+        ManualEventReader {
+            cursor: Some(cursor),
+            ..Default::default()
+        }
+    }
+
     /// Gets a new [`ManualEventReader`]. This will ignore all events already in the event buffers.
     /// It will read all future events.
     pub fn get_reader_current(&self) -> ManualEventReader<E> {
@@ -591,18 +694,95 @@ impl<E: Event> Events<E> {
         }
     }
 
+    /// Creates an empty event collection with the given [`EventRetention`].
+    pub fn with_retention(retention: EventRetention) -> Self {
+        Self {
+            retention,
+            ..Default::default()
+        }
+    }
+
+    /// Returns how long the events are kept before being dropped.
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// Sets how long the events are kept before being dropped.
+    ///
+    /// Events exceeding the capacity of an [`EventRetention::RingBuffer`] are dropped immediately.
+    pub fn set_retention(&mut self, retention: EventRetention) {
+        self.retention = retention;
+        self.enforce_capacity();
+    }
+
     /// Swaps the event buffers and clears the oldest event buffer. In general, this should be
     /// called once per frame/update.
+    ///
+    /// Depending on the [`EventRetention`] of the events, the oldest events may be kept instead.
     pub fn update(&mut self) {
-        std::mem::swap(&mut self.events_a, &mut self.events_b);
-        self.events_b.clear();
-        self.events_b.start_event_count = self.event_count;
+        match self.retention {
+            EventRetention::DoubleBuffered => {
+                std::mem::swap(&mut self.events_a, &mut self.events_b);
+                self.events_b.clear();
+                self.events_b.start_event_count = self.event_count;
+            }
+            EventRetention::UntilConsumed => {
+                let double_buffered_start = self.events_b.start_event_count;
+                let retained_start = self
+                    .oldest_registered_reader()
+                    .map_or(double_buffered_start, |oldest| {
+                        oldest.min(double_buffered_start)
+                    });
+                let dropped = retained_start
+                    .saturating_sub(self.events_a.start_event_count)
+                    .min(self.events_a.len());
+                self.events_a.drain(..dropped);
+                self.events_a.start_event_count += dropped;
+                self.merge_buffers();
+            }
+            EventRetention::RingBuffer(_) => self.merge_buffers(),
+        }
         debug_assert_eq!(
             self.events_a.start_event_count + self.events_a.len(),
             self.events_b.start_event_count
         );
     }
 
+    /// Moves the newer events to the buffer of the oldest ones.
+    fn merge_buffers(&mut self) {
+        let mut newer_events = std::mem::take(&mut self.events_b.events);
+        self.events_a.append(&mut newer_events);
+        self.events_b.start_event_count = self.event_count;
+    }
+
+    /// Returns the number of events read by the registered reader that has read the fewest, and
+    /// forgets the readers that were dropped.
+    fn oldest_registered_reader(&mut self) -> Option<usize> {
+        self.readers.retain(|reader| reader.strong_count() > 0);
+        self.readers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|cursor| cursor.load(atomic::Ordering::Relaxed))
+            .min()
+    }
+
+    /// Drops the oldest events exceeding the capacity of an [`EventRetention::RingBuffer`].
+    fn enforce_capacity(&mut self) {
+        let EventRetention::RingBuffer(capacity) = self.retention else {
+            return;
+        };
+        let excess = self.len().saturating_sub(capacity);
+        if excess == 0 {
+            return;
+        }
+        let dropped_a = excess.min(self.events_a.len());
+        self.events_a.drain(..dropped_a);
+        let dropped_b = excess - dropped_a;
+        self.events_b.drain(..dropped_b);
+        self.events_b.start_event_count += dropped_b;
+        self.events_a.start_event_count = self.events_b.start_event_count - self.events_a.len();
+    }
+
     /// A system that calls [`Events::update`] once per frame.
     pub fn update_system(mut events: ResMut<Self>) {
         events.update();
@@ -701,6 +881,7 @@ impl<E: Event> std::iter::Extend<E> for Events<E> {
         });
 
         self.events_b.extend(events);
+        self.enforce_capacity();
 
         if old_count != event_count {
             detailed_trace!(
@@ -1033,6 +1214,87 @@ mod tests {
     #[derive(Event, Clone, PartialEq, Debug, Default)]
     struct EmptyTestEvent;
 
+    #[test]
+    fn test_events_until_consumed() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::UntilConsumed);
+        let mut reader_a = events.get_registered_reader();
+        let mut reader_b = events.get_registered_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.update();
+        events.update();
+        assert_eq!(get_events(&events, &mut reader_a), vec![TestEvent { i: 0 }]);
+
+        events.send(TestEvent { i: 1 });
+        events.update();
+        events.update();
+        events.update();
+        assert_eq!(
+            get_events(&events, &mut reader_b),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+        events.update();
+        assert_eq!(events.len(), 1, "events read by both readers are dropped");
+
+        drop(reader_a);
+        events.update();
+        assert!(
+            events.is_empty(),
+            "dropped readers aren't waited for: {events:?}"
+        );
+    }
+
+    #[test]
+    fn test_event_reader_until_consumed() {
+        let mut world = World::new();
+        world.insert_resource(Events::<TestEvent>::with_retention(
+            EventRetention::UntilConsumed,
+        ));
+        let mut reader = SystemState::<EventReader<TestEvent>>::new(&mut world);
+
+        world.send_event(TestEvent { i: 0 });
+        for _ in 0..4 {
+            world.resource_mut::<Events<TestEvent>>().update();
+        }
+        let events = reader.get(&world).iter().copied().collect::<Vec<_>>();
+        assert_eq!(events, vec![TestEvent { i: 0 }]);
+
+        world.resource_mut::<Events<TestEvent>>().update();
+        assert!(world.resource::<Events<TestEvent>>().is_empty());
+    }
+
+    #[test]
+    fn test_events_ring_buffer() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::RingBuffer(2));
+        let mut reader = events.get_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.send(TestEvent { i: 1 });
+        events.update();
+        events.update();
+        events.send(TestEvent { i: 2 });
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(reader.missed_events(&events), 1);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 1 }, TestEvent { i: 2 }]
+        );
+
+        events.extend([TestEvent { i: 3 }, TestEvent { i: 4 }, TestEvent { i: 5 }]);
+        assert_eq!(reader.missed_events(&events), 1);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 4 }, TestEvent { i: 5 }]
+        );
+
+        events.set_retention(EventRetention::RingBuffer(1));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events.oldest_id(), 5);
+    }
+
     #[test]
     fn test_firing_empty_event() {
         let mut events = Events::<EmptyTestEvent>::default();
@@ -0,0 +1,393 @@
+//! Indexes of entities by a key derived from one of their components.
+//!
+//! An index is declared by implementing [`ComponentIndex`], which projects a component to a
+//! hashable key, and is read from systems with the [`EntityIndex`] system parameter:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::index::{refresh_index, ComponentIndex, EntityIndex};
+//!
+//! #[derive(Component)]
+//! struct Cell {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! struct ByCell;
+//!
+//! impl ComponentIndex for ByCell {
+//!     type Component = Cell;
+//!     type Key = (i32, i32);
+//!
+//!     fn key(cell: &Cell) -> (i32, i32) {
+//!         (cell.x, cell.y)
+//!     }
+//! }
+//!
+//! fn occupied(cells: EntityIndex<ByCell>) {
+//!     for entity in cells.get(&(0, 0)) {
+//!         println!("{entity:?} is at the origin");
+//!     }
+//! }
+//!
+//! let mut schedule = Schedule::new();
+//! schedule.add_systems((refresh_index::<ByCell>, occupied).chain());
+//! # bevy_ecs::system::assert_is_read_only_system(occupied);
+//! ```
+//!
+//! Indexes are kept up to date by `on_insert` and `on_remove`
+//! [hooks](crate::component::ComponentHooks) on the indexed component. Changes made in place
+//! through [`Mut`] are picked up through change detection by the [`refresh_index`] system, which
+//! should run before the systems reading the index. `App::add_index` in `bevy_app` initializes an
+//! index and runs this system in the `First` schedule.
+//!
+//! [`Mut`]: crate::change_detection::Mut
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId, Tick},
+    entity::Entity,
+    query::Changed,
+    system::{Query, ReadOnlySystemParam, Res, ResMut, Resource, SystemMeta, SystemParam},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+use bevy_utils::HashMap;
+use std::{borrow::Borrow, hash::Hash, marker::PhantomData};
+
+/// Declares an index of the entities with [`ComponentIndex::Component`], by the key returned by
+/// [`ComponentIndex::key`].
+///
+/// Several indexes can be declared for the same component.
+pub trait ComponentIndex: Send + Sync + 'static {
+    /// The indexed component.
+    type Component: Component;
+    /// The key entities are indexed by.
+    type Key: Eq + Hash + Clone + Send + Sync + 'static;
+
+    /// Computes the key of a component.
+    fn key(component: &Self::Component) -> Self::Key;
+}
+
+/// The entities of an index, stored in the world as a resource.
+///
+/// It is usually accessed through [`EntityIndex`].
+#[derive(Resource)]
+pub struct IndexStorage<I: ComponentIndex> {
+    entities: HashMap<I::Key, Vec<Entity>>,
+    keys: HashMap<Entity, I::Key>,
+}
+
+impl<I: ComponentIndex> Default for IndexStorage<I> {
+    fn default() -> Self {
+        Self {
+            entities: Default::default(),
+            keys: Default::default(),
+        }
+    }
+}
+
+impl<I: ComponentIndex> IndexStorage<I> {
+    /// Returns the entities indexed with the given key, in no particular order.
+    pub fn get<Q>(&self, key: &Q) -> &[Entity]
+    where
+        I::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entities.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the key the given entity is indexed with, if it is indexed.
+    pub fn key(&self, entity: Entity) -> Option<&I::Key> {
+        self.keys.get(&entity)
+    }
+
+    /// Returns an iterator over the keys and their entities.
+    pub fn iter(&self) -> impl Iterator<Item = (&I::Key, &[Entity])> {
+        self.entities
+            .iter()
+            .map(|(key, entities)| (key, entities.as_slice()))
+    }
+
+    /// Indexes `entity` with `key`, or removes it from the index if `key` is `None`.
+    fn update(&mut self, entity: Entity, key: Option<I::Key>) {
+        let previous = match key {
+            Some(key) => {
+                if self.keys.get(&entity) == Some(&key) {
+                    return;
+                }
+                self.entities.entry(key.clone()).or_default().push(entity);
+                self.keys.insert(entity, key)
+            }
+            None => self.keys.remove(&entity),
+        };
+        if let Some(previous) = previous {
+            let entities = self.entities.get_mut(&previous).unwrap();
+            entities.retain(|&e| e != entity);
+            if entities.is_empty() {
+                self.entities.remove(&previous);
+            }
+        }
+    }
+}
+
+/// Remembers that the hooks of the index `I` were registered. Only its resource id is initialized,
+/// so that it outlives the [`IndexStorage<I>`] resource.
+#[derive(Resource)]
+struct IndexHooks<I: ComponentIndex>(PhantomData<fn() -> I>);
+
+fn on_insert_indexed<I: ComponentIndex>(world: &mut World, entity: Entity, _: ComponentId) {
+    let key = world.get::<I::Component>(entity).map(I::key);
+    // the index may have been removed, for example by `World::clear_resources`
+    if let Some(mut storage) = world.get_resource_mut::<IndexStorage<I>>() {
+        storage.update(entity, key);
+    }
+}
+
+fn on_remove_indexed<I: ComponentIndex>(world: &mut World, entity: Entity, _: ComponentId) {
+    if let Some(mut storage) = world.get_resource_mut::<IndexStorage<I>>() {
+        storage.update(entity, None);
+    }
+}
+
+impl World {
+    /// Initializes the index `I`, indexing the entities that already have its component.
+    ///
+    /// This is done automatically when a system using [`EntityIndex<I>`] is initialized. If the
+    /// [`IndexStorage<I>`] resource was removed, it is rebuilt.
+    pub fn init_index<I: ComponentIndex>(&mut self) {
+        if self.contains_resource::<IndexStorage<I>>() {
+            return;
+        }
+        if self.components.resource_id::<IndexHooks<I>>().is_none() {
+            self.components.init_resource::<IndexHooks<I>>();
+            self.register_component_hooks::<I::Component>()
+                .on_insert(on_insert_indexed::<I>)
+                .on_remove(on_remove_indexed::<I>);
+        }
+
+        let mut storage = IndexStorage::<I>::default();
+        for (entity, component) in self.query::<(Entity, &I::Component)>().iter(self) {
+            storage.update(entity, Some(I::key(component)));
+        }
+        self.insert_resource(storage);
+    }
+}
+
+/// Re-indexes the entities whose [`ComponentIndex::Component`] was changed in place since this
+/// system last ran.
+///
+/// Insertions and removals are indexed right away, but changes made through
+/// [`Mut`](crate::change_detection::Mut) are only picked up by this system. It should run before
+/// the systems using [`EntityIndex<I>`], which only read the index so that they can run in
+/// parallel. `App::add_index` runs it in the `First` schedule.
+///
+/// Does nothing if the index was not initialized.
+pub fn refresh_index<I: ComponentIndex>(
+    storage: Option<ResMut<IndexStorage<I>>>,
+    changed: Query<(Entity, &I::Component), Changed<I::Component>>,
+) {
+    let Some(mut storage) = storage else {
+        return;
+    };
+    for (entity, component) in &changed {
+        storage.update(entity, Some(I::key(component)));
+    }
+}
+
+/// A read-only [`SystemParam`] to look up entities by the key of the index `I`.
+///
+/// The index is initialized when the system is. Changes made to the indexed component in place
+/// are only visible once [`refresh_index`] has run.
+///
+/// See the [module level documentation](self) for an example.
+pub struct EntityIndex<'w, I: ComponentIndex> {
+    storage: &'w IndexStorage<I>,
+}
+
+impl<'w, I: ComponentIndex> EntityIndex<'w, I> {
+    /// Returns the entities indexed with the given key, in no particular order.
+    pub fn get<Q>(&self, key: &Q) -> &'w [Entity]
+    where
+        I::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.storage.get(key)
+    }
+
+    /// Returns one of the entities indexed with the given key, if any.
+    ///
+    /// This is meant for keys that are unique, like network ids.
+    pub fn get_single<Q>(&self, key: &Q) -> Option<Entity>
+    where
+        I::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).first().copied()
+    }
+
+    /// Returns the key the given entity is indexed with, if it is indexed.
+    pub fn key(&self, entity: Entity) -> Option<&'w I::Key> {
+        self.storage.key(entity)
+    }
+
+    /// Returns an iterator over the keys and their entities.
+    pub fn iter(&self) -> impl Iterator<Item = (&'w I::Key, &'w [Entity])> {
+        self.storage.iter()
+    }
+}
+
+// SAFETY: this impl defers to `Res`, which initializes and validates the access.
+unsafe impl<'a, I: ComponentIndex> SystemParam for EntityIndex<'a, I> {
+    type State = <Res<'static, IndexStorage<I>> as SystemParam>::State;
+    type Item<'w, 's> = EntityIndex<'w, I>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        world.init_index::<I>();
+        <Res<IndexStorage<I>> as SystemParam>::init_state(world, system_meta)
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: access is validated by the delegated `init_state`.
+        let storage = unsafe {
+            <Res<IndexStorage<I>> as SystemParam>::get_param(state, system_meta, world, change_tick)
+        };
+        EntityIndex {
+            storage: storage.into_inner(),
+        }
+    }
+}
+
+// SAFETY: `EntityIndex` only reads the `IndexStorage<I>` resource.
+unsafe impl<'a, I: ComponentIndex> ReadOnlySystemParam for EntityIndex<'a, I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{refresh_index, ComponentIndex, EntityIndex};
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::schedule::{LogLevel, ScheduleBuildSettings};
+    use crate::system::SystemState;
+
+    #[derive(Component)]
+    struct Name(&'static str);
+
+    struct ByName;
+
+    impl ComponentIndex for ByName {
+        type Component = Name;
+        type Key = &'static str;
+
+        fn key(name: &Name) -> &'static str {
+            name.0
+        }
+    }
+
+    struct ByLength;
+
+    impl ComponentIndex for ByLength {
+        type Component = Name;
+        type Key = usize;
+
+        fn key(name: &Name) -> usize {
+            name.0.len()
+        }
+    }
+
+    fn sorted(entities: &[Entity]) -> Vec<Entity> {
+        let mut entities = entities.to_vec();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn index_follows_components() {
+        let mut world = World::new();
+        let alice = world.spawn(Name("alice")).id();
+        let mut state = SystemState::<EntityIndex<ByName>>::new(&mut world);
+        let bob = world.spawn(Name("bob")).id();
+        let other_bob = world.spawn(Name("bob")).id();
+
+        let mut refresh = IntoSystem::into_system(refresh_index::<ByName>);
+        refresh.initialize(&mut world);
+
+        let index = state.get(&world);
+        assert_eq!(index.get("alice"), [alice]);
+        assert_eq!(sorted(index.get("bob")), [bob, other_bob]);
+        assert_eq!(index.key(alice), Some(&"alice"));
+
+        world.get_mut::<Name>(other_bob).unwrap().0 = "carol";
+        world.entity_mut(alice).remove::<Name>();
+        world.despawn(bob);
+
+        // removals are indexed right away, changes in place once the index is refreshed
+        let index = state.get(&world);
+        assert!(index.get("alice").is_empty());
+        assert_eq!(index.get("bob"), [other_bob]);
+
+        refresh.run((), &mut world);
+        let index = state.get(&world);
+        assert!(index.get("alice").is_empty());
+        assert!(index.get("bob").is_empty());
+        assert_eq!(index.get_single("carol"), Some(other_bob));
+        assert_eq!(index.iter().count(), 1);
+    }
+
+    #[test]
+    fn several_indexes() {
+        let mut world = World::new();
+        let a = world.spawn(Name("abc")).id();
+        let b = world.spawn(Name("xyz")).id();
+
+        let mut state =
+            SystemState::<(EntityIndex<ByName>, EntityIndex<ByLength>)>::new(&mut world);
+        let (names, lengths) = state.get(&world);
+        assert_eq!(names.get("xyz"), [b]);
+        assert_eq!(sorted(lengths.get(&3)), [a, b]);
+
+        world.entity_mut(a).insert(Name("abcd"));
+        let (names, lengths) = state.get(&world);
+        assert_eq!(names.get("abcd"), [a]);
+        assert_eq!(lengths.get(&4), [a]);
+        assert_eq!(lengths.get(&3), [b]);
+    }
+
+    #[test]
+    fn removed_index_is_rebuilt() {
+        let mut world = World::new();
+        let a = world.spawn(Name("a")).id();
+        world.init_index::<ByName>();
+
+        world.clear_resources();
+        world.entity_mut(a).insert(Name("b"));
+        world.despawn(a);
+        let mut refresh = IntoSystem::into_system(refresh_index::<ByName>);
+        refresh.initialize(&mut world);
+        refresh.run((), &mut world);
+
+        let mut state = SystemState::<EntityIndex<ByName>>::new(&mut world);
+        let c = world.spawn(Name("c")).id();
+        let index = state.get(&world);
+        assert_eq!(index.get("c"), [c]);
+        assert_eq!(index.iter().count(), 1);
+    }
+
+    #[test]
+    fn readers_do_not_conflict() {
+        fn reader(_names: EntityIndex<ByName>) {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..Default::default()
+        });
+        schedule.add_systems((refresh_index::<ByName>, (reader, reader)).chain());
+        schedule.initialize(&mut world).unwrap();
+    }
+}
@@ -12,9 +12,12 @@ pub mod change_detection;
 pub mod component;
 pub mod entity;
 pub mod event;
+pub mod index;
+pub mod observer;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
+pub mod relation;
 pub mod removal_detection;
 pub mod schedule;
 pub mod storage;
@@ -35,14 +38,16 @@ pub mod prelude {
         bundle::Bundle,
         change_detection::{DetectChanges, DetectChangesMut, Mut, Ref},
         component::Component,
-        entity::Entity,
+        entity::{Disabled, Entity},
         event::{Event, EventReader, EventWriter, Events},
-        query::{Added, AnyOf, Changed, Has, Or, QueryState, With, Without},
+        observer::Trigger,
+        query::{Added, AnyOf, Changed, Has, IncludeDisabled, Or, QueryState, With, Without},
         removal_detection::RemovedComponents,
         schedule::{
             apply_deferred, apply_state_transition, common_conditions::*, Condition,
             IntoSystemConfigs, IntoSystemSet, IntoSystemSetConfig, IntoSystemSetConfigs, NextState,
-            OnEnter, OnExit, OnTransition, Schedule, Schedules, State, States, SystemSet,
+            OnEnter, OnExit, OnPause, OnResume, OnTransition, Schedule, Schedules, State,
+            StateStack, States, SystemSet,
         },
         system::{
             adapter as system_adapter,
@@ -67,7 +72,7 @@ mod tests {
         bundle::Bundle,
         change_detection::Ref,
         component::{Component, ComponentId},
-        entity::Entity,
+        entity::{Disabled, Entity},
         query::{Added, Changed, FilteredAccess, ReadOnlyWorldQuery, With, Without},
         system::Resource,
         world::{EntityRef, Mut, World},
@@ -1377,6 +1382,9 @@ mod tests {
         let b_id = world.components.get_id(TypeId::of::<B>()).unwrap();
         expected.add_write(a_id);
         expected.add_read(b_id);
+        // queries that don't mention `Disabled` exclude disabled entities
+        let disabled_id = world.components.get_id(TypeId::of::<Disabled>()).unwrap();
+        expected.and_without(disabled_id);
         assert!(
             query.component_access.eq(&expected),
             "ComponentId access from query fetch and query filter should be combined"
@@ -0,0 +1,425 @@
+//! Observers: systems that run immediately when an [`Event`] is triggered.
+//!
+//! Unlike [`Events`](crate::event::Events), which are buffered and read by systems during the
+//! next schedule run, a triggered event is handed directly to every matching observer before
+//! [`World::trigger`] returns. Observers are systems that take an [`In<Trigger<E>>`](Trigger)
+//! as their input.
+//!
+//! Observers are either *global*, in which case they run for every trigger of their event, or
+//! *entity-targeted*, in which case they only run when the event is triggered on the entity they
+//! observe. Targeted observers are despawned along with the entity they observe.
+//!
+//! Events triggered with [`World::trigger_propagating`] bubble up a [`Relation`], for example
+//! `Parent` in `bevy_hierarchy`, running the observers of every entity along the way until an
+//! observer calls [`Trigger::stop_propagation`].
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! #[derive(Event)]
+//! struct Damage(u32);
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! let player = world.spawn(Health(10)).id();
+//! world
+//!     .entity_mut(player)
+//!     .observe(|trigger: In<Trigger<Damage>>, mut query: Query<&mut Health>| {
+//!         let mut health = query.get_mut(trigger.entity()).unwrap();
+//!         health.0 = health.0.saturating_sub(trigger.event().0);
+//!     });
+//!
+//! world.trigger_entity(Damage(3), player);
+//! assert_eq!(world.get::<Health>(player).unwrap().0, 7);
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId},
+    entity::Entity,
+    event::Event,
+    relation::{Relation, RelationCleanup, Sources},
+    system::{BoxedSystem, In, IntoSystem},
+    world::{EntityMut, World},
+};
+use bevy_utils::HashMap;
+use std::{
+    any::TypeId,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// The input of an observer system, giving access to the triggered [`Event`].
+///
+/// Observers receive it wrapped in [`In`]:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Event)]
+/// # struct Explode;
+/// fn on_explode(trigger: In<Trigger<Explode>>, mut commands: Commands) {
+///     commands.entity(trigger.entity()).despawn();
+/// }
+/// # let mut world = World::new();
+/// # world.observe(on_explode);
+/// ```
+pub struct Trigger<E: Event> {
+    entity: Entity,
+    state: Arc<TriggerState<E>>,
+}
+
+struct TriggerState<E: Event> {
+    event: E,
+    target: Entity,
+    propagate: AtomicBool,
+}
+
+impl<E: Event> Trigger<E> {
+    /// Returns the triggered event.
+    #[inline]
+    pub fn event(&self) -> &E {
+        &self.state.event
+    }
+
+    /// Returns the entity the observers currently running are attached to.
+    ///
+    /// This differs from [`Trigger::target`] while the event is propagating. For global
+    /// observers, this is the entity the event is currently being delivered to, or
+    /// [`Entity::PLACEHOLDER`] if the event was triggered with [`World::trigger`].
+    #[inline]
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns the entity the event was originally triggered on, or [`Entity::PLACEHOLDER`] if
+    /// the event was triggered with [`World::trigger`].
+    #[inline]
+    pub fn target(&self) -> Entity {
+        self.state.target
+    }
+
+    /// Prevents a propagating event from reaching the next entity.
+    ///
+    /// The remaining observers of the current entity still run.
+    #[inline]
+    pub fn stop_propagation(&self) {
+        self.state.propagate.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<E: Event> Deref for Trigger<E> {
+    type Target = E;
+
+    #[inline]
+    fn deref(&self) -> &E {
+        self.event()
+    }
+}
+
+impl<E: Event> Deref for In<Trigger<E>> {
+    type Target = Trigger<E>;
+
+    #[inline]
+    fn deref(&self) -> &Trigger<E> {
+        &self.0
+    }
+}
+
+/// Holds an observer system of the entity it is attached to.
+#[derive(Component)]
+struct Observer<E: Event> {
+    initialized: bool,
+    /// `None` while the observer is running.
+    system: Option<BoxedSystem<Trigger<E>>>,
+}
+
+/// Links an entity-targeted observer to the entity it observes.
+///
+/// Observers are despawned along with the entity they observe.
+#[derive(Component)]
+struct Observing(Entity);
+
+impl Relation for Observing {
+    type Sources = Sources<Self>;
+
+    const CLEANUP: RelationCleanup = RelationCleanup::DespawnSources;
+
+    fn target(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Tracks the global observers of a [`World`], by the [`ComponentId`] of their `Observer<E>`
+/// component.
+#[derive(Debug, Default)]
+pub(crate) struct Observers {
+    global: HashMap<ComponentId, Vec<Entity>>,
+}
+
+/// The `on_remove` hook of `Observer<E>`: forgets global observers when they are despawned.
+fn on_remove_observer(world: &mut World, entity: Entity, id: ComponentId) {
+    if let Some(global) = world.observers.global.get_mut(&id) {
+        global.retain(|&observer| observer != entity);
+    }
+}
+
+/// Turns `entity` into an observer of `E`, observing `target` or every trigger if `None`.
+pub(crate) fn insert_observer<E: Event>(
+    world: &mut World,
+    entity: Entity,
+    target: Option<Entity>,
+    system: BoxedSystem<Trigger<E>>,
+) {
+    if world.component_id::<Observer<E>>().is_none() {
+        world
+            .register_component_hooks::<Observer<E>>()
+            .on_remove(on_remove_observer);
+    }
+    let id = world.init_component::<Observer<E>>();
+
+    let mut entity_mut = world.entity_mut(entity);
+    assert!(
+        !entity_mut.contains::<Observer<E>>(),
+        "Entity {entity:?} is already an observer of `{}`.",
+        std::any::type_name::<E>()
+    );
+    entity_mut.insert(Observer {
+        initialized: false,
+        system: Some(system),
+    });
+    match target {
+        Some(target) => {
+            world.insert_relation(entity, Observing(target));
+        }
+        None => world.observers.global.entry(id).or_default().push(entity),
+    }
+}
+
+/// Runs the observers of `E` attached to `entity`, followed by the global observers of `E`.
+fn run_observers<E: Event>(
+    world: &mut World,
+    id: ComponentId,
+    entity: Entity,
+    state: &Arc<TriggerState<E>>,
+) {
+    let mut observers = world
+        .get::<Sources<Observing>>(entity)
+        .map(|sources| sources.sources().to_vec())
+        .unwrap_or_default();
+    observers.extend(world.observers.global.get(&id).into_iter().flatten());
+
+    for observer in observers {
+        let Some(mut observer_state) = world.get_mut::<Observer<E>>(observer) else {
+            continue;
+        };
+        // An observer that triggers its own event does not run recursively.
+        let Some(mut system) = observer_state.system.take() else {
+            continue;
+        };
+        let initialized = std::mem::replace(&mut observer_state.initialized, true);
+        if !initialized {
+            system.initialize(world);
+        }
+        system.run(
+            Trigger {
+                entity,
+                state: Arc::clone(state),
+            },
+            world,
+        );
+        system.apply_deferred(world);
+
+        // return ownership of the system (if the observer still exists)
+        if let Some(mut observer_state) = world.get_mut::<Observer<E>>(observer) {
+            observer_state.system = Some(system);
+        }
+    }
+}
+
+impl World {
+    /// Spawns a global observer of the event `E`, which runs every time `E` is triggered.
+    ///
+    /// Returns the entity of the observer. Despawning it removes the observer.
+    pub fn observe<E: Event, M>(&mut self, observer: impl IntoSystem<Trigger<E>, (), M>) -> Entity {
+        let entity = self.spawn_empty().id();
+        insert_observer(
+            self,
+            entity,
+            None,
+            Box::new(IntoSystem::into_system(observer)),
+        );
+        entity
+    }
+
+    /// Triggers `event`, running every global observer of `E`.
+    pub fn trigger<E: Event>(&mut self, event: E) {
+        self.trigger_inner::<E, Observing>(event, Entity::PLACEHOLDER, false);
+    }
+
+    /// Triggers `event` on `entity`, running the observers of `E` attached to `entity`, then the
+    /// global observers of `E`.
+    pub fn trigger_entity<E: Event>(&mut self, event: E, entity: Entity) {
+        self.trigger_inner::<E, Observing>(event, entity, false);
+    }
+
+    /// Triggers `event` on `entity`, then propagates it up the relation `R`: after the observers
+    /// of an entity have run, the event is triggered on its [`Relation::target`], until an entity
+    /// without `R` is reached or an observer calls [`Trigger::stop_propagation`].
+    ///
+    /// Global observers run once for every entity the event reaches.
+    pub fn trigger_propagating<E: Event, R: Relation>(&mut self, event: E, entity: Entity) {
+        self.trigger_inner::<E, R>(event, entity, true);
+    }
+
+    fn trigger_inner<E: Event, R: Relation>(&mut self, event: E, target: Entity, propagate: bool) {
+        let Some(id) = self.components.get_id(TypeId::of::<Observer<E>>()) else {
+            return;
+        };
+        let state = Arc::new(TriggerState {
+            event,
+            target,
+            propagate: AtomicBool::new(propagate),
+        });
+
+        let mut entity = target;
+        let mut visited = Vec::new();
+        loop {
+            run_observers(self, id, entity, &state);
+            if !state.propagate.load(Ordering::Relaxed) {
+                break;
+            }
+            visited.push(entity);
+            match self.get::<R>(entity).map(R::target) {
+                Some(next) if !visited.contains(&next) => entity = next,
+                _ => break,
+            }
+        }
+    }
+}
+
+impl<'w> EntityMut<'w> {
+    /// Spawns an observer of the event `E` that runs when `E` is triggered on this entity.
+    ///
+    /// The observer is despawned along with this entity.
+    pub fn observe<E: Event, M>(
+        &mut self,
+        observer: impl IntoSystem<Trigger<E>, (), M>,
+    ) -> &mut Self {
+        let target = self.id();
+        let system: BoxedSystem<Trigger<E>> = Box::new(IntoSystem::into_system(observer));
+        self.world_scope(|world| {
+            let entity = world.spawn_empty().id();
+            insert_observer(world, entity, Some(target), system);
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trigger;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::relation::{Relation, Sources};
+
+    #[derive(Event)]
+    struct Ping(&'static str);
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<String>);
+
+    #[derive(Component)]
+    struct ChildOf(Entity);
+
+    impl Relation for ChildOf {
+        type Sources = Sources<Self>;
+
+        fn target(&self) -> Entity {
+            self.0
+        }
+    }
+
+    fn log(label: &'static str) -> impl Fn(In<Trigger<Ping>>, ResMut<Log>) {
+        move |trigger, mut log| log.0.push(format!("{label}:{}", trigger.event().0))
+    }
+
+    #[test]
+    fn global_and_targeted_observers() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let a = world.spawn_empty().observe(log("a")).id();
+        let b = world.spawn_empty().observe(log("b")).id();
+        world.observe(log("global"));
+
+        world.trigger(Ping("none"));
+        world.trigger_entity(Ping("a"), a);
+        world.trigger_entity(Ping("b"), b);
+
+        assert_eq!(
+            world.resource::<Log>().0,
+            ["global:none", "a:a", "global:a", "b:b", "global:b"]
+        );
+    }
+
+    #[test]
+    fn propagation() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let root = world.spawn_empty().observe(log("root")).id();
+        let parent = world.spawn_empty().observe(log("parent")).id();
+        world.insert_relation(parent, ChildOf(root));
+        let child = world.spawn_empty().observe(log("child")).id();
+        world.insert_relation(child, ChildOf(parent));
+
+        world.trigger_propagating::<_, ChildOf>(Ping("x"), child);
+        assert_eq!(world.resource::<Log>().0, ["child:x", "parent:x", "root:x"]);
+
+        world.resource_mut::<Log>().0.clear();
+        world
+            .entity_mut(parent)
+            .observe(|trigger: In<Trigger<Ping>>| trigger.stop_propagation());
+        world.trigger_propagating::<_, ChildOf>(Ping("y"), child);
+        assert_eq!(world.resource::<Log>().0, ["child:y", "parent:y"]);
+
+        // Without propagation, only the target's observers run.
+        world.resource_mut::<Log>().0.clear();
+        world.trigger_entity(Ping("z"), child);
+        assert_eq!(world.resource::<Log>().0, ["child:z"]);
+    }
+
+    #[test]
+    fn observers_are_despawned_with_their_target() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let target = world.spawn_empty().observe(log("target")).id();
+        let global = world.observe(log("global"));
+        assert_eq!(world.entities().len(), 3);
+
+        world.despawn(target);
+        assert_eq!(world.entities().len(), 1);
+
+        world.despawn(global);
+        world.trigger(Ping("nobody"));
+        assert!(world.resource::<Log>().0.is_empty());
+    }
+
+    #[test]
+    fn observers_from_commands() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let target = world.spawn_empty().id();
+
+        world.observe(|trigger: In<Trigger<Ping>>, mut commands: Commands| {
+            if trigger.event().0 == "spawn" {
+                commands.entity(trigger.entity()).observe(log("target"));
+                commands.trigger_entity(Ping("again"), trigger.entity());
+            }
+        });
+        world.trigger_entity(Ping("spawn"), target);
+        assert_eq!(world.resource::<Log>().0, ["target:again"]);
+    }
+}
@@ -168,6 +168,20 @@ impl<T: SparseSetIndex> Access<T> {
             && other.writes.is_disjoint(&self.reads_and_writes)
     }
 
+    /// Returns `true` if everything the access reads or writes is also read or written by `other`.
+    pub fn is_subset(&self, other: &Access<T>) -> bool {
+        if self.reads_all {
+            return other.reads_all && self.writes.is_subset(&other.writes);
+        }
+
+        if other.reads_all {
+            return self.writes.is_subset(&other.writes);
+        }
+
+        self.reads_and_writes.is_subset(&other.reads_and_writes)
+            && self.writes.is_subset(&other.writes)
+    }
+
     /// Returns a vector of elements that the access and `other` cannot access at the same time.
     pub fn get_conflicts(&self, other: &Access<T>) -> Vec<T> {
         let mut conflicts = FixedBitSet::default();
@@ -230,6 +244,9 @@ pub struct FilteredAccess<T: SparseSetIndex> {
     // An array of filter sets to express `With` or `Without` clauses in disjunctive normal form, for example: `Or<(With<A>, With<B>)>`.
     // Filters like `(With<A>, Or<(With<B>, Without<C>)>` are expanded into `Or<((With<A>, With<B>), (With<A>, Without<C>))>`.
     filter_sets: Vec<AccessFilters<T>>,
+    // Elements whose presence is observed without being read, like `Has<T>`. These never cause
+    // conflicts, but still count as referenced by the query.
+    archetypal: FixedBitSet,
 }
 
 impl<T: SparseSetIndex> Default for FilteredAccess<T> {
@@ -237,6 +254,7 @@ impl<T: SparseSetIndex> Default for FilteredAccess<T> {
         Self {
             access: Access::default(),
             filter_sets: vec![AccessFilters::default()],
+            archetypal: FixedBitSet::default(),
         }
     }
 }
@@ -274,6 +292,15 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.and_with(index);
     }
 
+    /// Marks the element given by `index` as observed without being accessed, like
+    /// [`Has`](super::Has) does.
+    ///
+    /// This does not affect compatibility, but makes [`references`](Self::references) return `true`.
+    pub fn add_archetypal(&mut self, index: T) {
+        self.archetypal.grow(index.sparse_set_index() + 1);
+        self.archetypal.insert(index.sparse_set_index());
+    }
+
     /// Adds a `With` filter: corresponds to a conjunction (AND) operation.
     ///
     /// Suppose we begin with `Or<(With<A>, With<B>)>`, which is represented by an array of two `AccessFilter` instances.
@@ -307,9 +334,39 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.filter_sets.append(&mut other.filter_sets.clone());
     }
 
+    /// Returns `true` if the element given by `index` is accessed, used in a `With` or
+    /// `Without` filter, or [archetypal](Self::add_archetypal).
+    ///
+    /// Unlike [`Access::has_read`], this ignores [`read_all`](Access::read_all).
+    pub fn references(&self, index: T) -> bool {
+        let index = index.sparse_set_index();
+        self.access.reads_and_writes.contains(index)
+            || self.archetypal.contains(index)
+            || self
+                .filter_sets
+                .iter()
+                .any(|filter| filter.with.contains(index) || filter.without.contains(index))
+    }
+
+    /// Returns `true` if the set of elements given by `set_contains` satisfies the `With` and
+    /// `Without` filters of at least one of the filter sets.
+    pub fn filters_match(&self, set_contains: impl Fn(T) -> bool) -> bool {
+        self.filter_sets.iter().any(|filter| {
+            filter
+                .with
+                .ones()
+                .all(|index| set_contains(T::get_sparse_set_index(index)))
+                && !filter
+                    .without
+                    .ones()
+                    .any(|index| set_contains(T::get_sparse_set_index(index)))
+        })
+    }
+
     /// Adds all of the accesses from `other` to `self`.
     pub fn extend_access(&mut self, other: &FilteredAccess<T>) {
         self.access.extend(&other.access);
+        self.archetypal.union_with(&other.archetypal);
     }
 
     /// Returns `true` if this and `other` can be active at the same time.
@@ -350,6 +407,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     /// `Or<((With<A>, With<C>), (With<A>, Without<D>), (Without<B>, With<C>), (Without<B>, Without<D>))>`.
     pub fn extend(&mut self, other: &FilteredAccess<T>) {
         self.access.extend(&other.access);
+        self.archetypal.union_with(&other.archetypal);
 
         // We can avoid allocating a new array of bitsets if `other` contains just a single set of filters:
         // in this case we can short-circuit by performing an in-place union for each bitset.
@@ -562,6 +620,26 @@ mod tests {
         assert!(access_b.is_compatible(&access_a));
     }
 
+    #[test]
+    fn access_is_subset() {
+        let mut read = Access::<usize>::default();
+        read.add_read(0);
+
+        let mut write = Access::<usize>::default();
+        write.add_write(0);
+        write.add_read(1);
+
+        assert!(read.is_subset(&write));
+        assert!(!write.is_subset(&read));
+
+        let mut read_all = Access::<usize>::default();
+        read_all.read_all();
+
+        assert!(read.is_subset(&read_all));
+        assert!(!write.is_subset(&read_all));
+        assert!(!read_all.is_subset(&write));
+    }
+
     #[test]
     fn access_get_conflicts() {
         let mut access_a = Access::<usize>::default();
@@ -0,0 +1,635 @@
+use crate::{
+    archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
+    change_detection::MutUntyped,
+    component::ComponentId,
+    entity::Entity,
+    query::{Access, FilteredAccess},
+    world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
+};
+use bevy_ptr::Ptr;
+
+/// A term fetched by a [`DynamicQueryState`], in the order it was added to the [`QueryBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DynamicFetch {
+    /// The component to fetch.
+    pub component_id: ComponentId,
+    /// Whether the component is fetched mutably.
+    pub mutable: bool,
+    /// Whether entities without the component still match the query.
+    pub optional: bool,
+}
+
+/// Builds a [`DynamicQueryState`] out of terms specified at runtime by [`ComponentId`].
+///
+/// This is the untyped counterpart of [`QueryState`](super::QueryState), for code that only
+/// knows its components at runtime, like scripting or reflection based tools. Fetched terms are
+/// returned in the order they were added to the builder, as [`Ptr`] for reads and [`MutUntyped`]
+/// for writes.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::QueryBuilder;
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// #[derive(Component)]
+/// struct Velocity(f32);
+///
+/// #[derive(Component)]
+/// struct Frozen;
+///
+/// let mut world = World::new();
+/// world.spawn((Position(0.0), Velocity(1.0)));
+/// world.spawn((Position(0.0), Velocity(1.0), Frozen));
+///
+/// let position = world.init_component::<Position>();
+/// let velocity = world.init_component::<Velocity>();
+/// let frozen = world.init_component::<Frozen>();
+/// let mut query = QueryBuilder::new(&mut world)
+///     .write(position)
+///     .read(velocity)
+///     .without(frozen)
+///     .build();
+///
+/// for mut item in query.iter_mut(&mut world) {
+///     // SAFETY: the terms were built from these component types.
+///     let velocity = unsafe { item.get(1).unwrap().deref::<Velocity>().0 };
+///     let mut position = item.get_mut(0).unwrap();
+///     unsafe { position.as_mut().deref_mut::<Position>().0 += velocity };
+/// }
+/// ```
+pub struct QueryBuilder<'w> {
+    world: &'w mut World,
+    fetches: Vec<DynamicFetch>,
+    with: Vec<ComponentId>,
+    without: Vec<ComponentId>,
+    component_access: FilteredAccess<ComponentId>,
+    include_disabled: bool,
+}
+
+impl<'w> QueryBuilder<'w> {
+    /// Creates a builder for a query on `world` that matches every entity.
+    pub fn new(world: &'w mut World) -> Self {
+        Self {
+            world,
+            fetches: Vec::new(),
+            with: Vec::new(),
+            without: Vec::new(),
+            component_access: FilteredAccess::default(),
+            include_disabled: false,
+        }
+    }
+
+    /// Fetches the component `component_id` immutably, only matching entities that have it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component does not exist in the world, or if it is already fetched mutably.
+    pub fn read(self, component_id: ComponentId) -> Self {
+        self.fetch(component_id, false, false)
+    }
+
+    /// Fetches the component `component_id` mutably, only matching entities that have it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component does not exist in the world, or if it is already fetched.
+    pub fn write(self, component_id: ComponentId) -> Self {
+        self.fetch(component_id, true, false)
+    }
+
+    /// Fetches the component `component_id` immutably if the entity has it, without affecting
+    /// which entities match the query.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component does not exist in the world, or if it is already fetched mutably.
+    pub fn optional_read(self, component_id: ComponentId) -> Self {
+        self.fetch(component_id, false, true)
+    }
+
+    /// Fetches the component `component_id` mutably if the entity has it, without affecting
+    /// which entities match the query.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component does not exist in the world, or if it is already fetched.
+    pub fn optional_write(self, component_id: ComponentId) -> Self {
+        self.fetch(component_id, true, true)
+    }
+
+    /// Only matches entities that have the component `component_id`, without fetching it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component does not exist in the world.
+    pub fn with(mut self, component_id: ComponentId) -> Self {
+        self.assert_exists(component_id);
+        self.component_access.and_with(component_id);
+        self.with.push(component_id);
+        self
+    }
+
+    /// Only matches entities that do not have the component `component_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component does not exist in the world.
+    pub fn without(mut self, component_id: ComponentId) -> Self {
+        self.assert_exists(component_id);
+        self.component_access.and_without(component_id);
+        self.without.push(component_id);
+        self
+    }
+
+    /// Also matches [`Disabled`](crate::entity::Disabled) entities, which are excluded unless a term mentions
+    /// `Disabled`, like [`IncludeDisabled`](super::IncludeDisabled) does for typed queries.
+    pub fn include_disabled(mut self) -> Self {
+        self.include_disabled = true;
+        self
+    }
+
+    /// Returns the access of the terms added so far.
+    pub fn component_access(&self) -> &FilteredAccess<ComponentId> {
+        &self.component_access
+    }
+
+    /// Creates the [`DynamicQueryState`], matching the archetypes currently in the world.
+    pub fn build(mut self) -> DynamicQueryState {
+        let disabled = self.world.disabled_component;
+        if self.include_disabled {
+            let mut with_disabled = self.component_access.clone();
+            with_disabled.and_with(disabled);
+            self.component_access.and_without(disabled);
+            self.component_access.append_or(&with_disabled);
+        } else if !self.component_access.references(disabled) {
+            self.component_access.and_without(disabled);
+            self.without.push(disabled);
+        }
+
+        let mut state = DynamicQueryState {
+            world_id: self.world.id(),
+            archetype_generation: ArchetypeGeneration::initial(),
+            matched_archetype_ids: Vec::new(),
+            archetype_component_access: Default::default(),
+            component_access: self.component_access,
+            fetches: self.fetches,
+            with: self.with,
+            without: self.without,
+        };
+        state.update_archetypes(self.world);
+        state
+    }
+
+    fn fetch(mut self, component_id: ComponentId, mutable: bool, optional: bool) -> Self {
+        self.assert_exists(component_id);
+        let name = self
+            .world
+            .components()
+            .get_info(component_id)
+            .unwrap()
+            .name();
+        let access = self.component_access.access();
+        if mutable {
+            assert!(
+                !access.has_read(component_id),
+                "&mut {name} conflicts with a previous access in this query. Mutable component access must be unique.",
+            );
+        } else {
+            assert!(
+                !access.has_write(component_id),
+                "&{name} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+            );
+        }
+
+        if optional {
+            // Like `Option<T>`, optional terms add access without restricting the filters.
+            let mut intermediate = self.component_access.clone();
+            add_access(&mut intermediate, component_id, mutable);
+            self.component_access.extend_access(&intermediate);
+        } else {
+            add_access(&mut self.component_access, component_id, mutable);
+        }
+        self.fetches.push(DynamicFetch {
+            component_id,
+            mutable,
+            optional,
+        });
+        self
+    }
+
+    fn assert_exists(&self, component_id: ComponentId) {
+        assert!(
+            self.world.components().get_info(component_id).is_some(),
+            "Cannot query {component_id:?}, which does not exist in this World.",
+        );
+    }
+}
+
+fn add_access(access: &mut FilteredAccess<ComponentId>, component_id: ComponentId, mutable: bool) {
+    if mutable {
+        access.add_write(component_id);
+    } else {
+        access.add_read(component_id);
+    }
+}
+
+/// A query over terms specified at runtime, created by a [`QueryBuilder`].
+///
+/// Like [`QueryState`](super::QueryState), this caches the archetypes matched by the query, and
+/// can only be used with the [`World`] it was created from.
+pub struct DynamicQueryState {
+    world_id: WorldId,
+    archetype_generation: ArchetypeGeneration,
+    matched_archetype_ids: Vec<ArchetypeId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+    component_access: FilteredAccess<ComponentId>,
+    fetches: Vec<DynamicFetch>,
+    with: Vec<ComponentId>,
+    without: Vec<ComponentId>,
+}
+
+impl DynamicQueryState {
+    /// Returns the fetched terms, in the order they are returned by the query items.
+    pub fn fetches(&self) -> &[DynamicFetch] {
+        &self.fetches
+    }
+
+    /// Returns the components accessed by this query, and the filters restricting them.
+    ///
+    /// This can be checked against the access of other queries with
+    /// [`FilteredAccess::is_compatible`].
+    pub fn component_access(&self) -> &FilteredAccess<ComponentId> {
+        &self.component_access
+    }
+
+    /// Returns the archetype components accessed by this query.
+    pub fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    /// Returns `true` if the given [`Entity`] matches the query.
+    pub fn contains(&mut self, entity: Entity, world: &World) -> bool {
+        self.update_archetypes(world);
+        world
+            .entities()
+            .get(entity)
+            .is_some_and(|location| self.matches(&world.archetypes()[location.archetype_id]))
+    }
+
+    /// Updates the cached matched archetypes with the ones created since the last update.
+    ///
+    /// This is called automatically by the other methods of the state.
+    ///
+    /// # Panics
+    ///
+    /// If `world` is not the [`World`] this state was created from.
+    pub fn update_archetypes(&mut self, world: &World) {
+        self.validate_world(world.id());
+        let archetypes = world.archetypes();
+        let new_generation = archetypes.generation();
+        let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
+
+        for archetype_index in old_generation.value()..new_generation.value() {
+            let archetype = &archetypes[ArchetypeId::new(archetype_index)];
+            if !self.matches(archetype) {
+                continue;
+            }
+            for fetch in &self.fetches {
+                if let Some(id) = archetype.get_archetype_component_id(fetch.component_id) {
+                    if fetch.mutable {
+                        self.archetype_component_access.add_write(id);
+                    } else {
+                        self.archetype_component_access.add_read(id);
+                    }
+                }
+            }
+            self.matched_archetype_ids.push(archetype.id());
+        }
+    }
+
+    /// Returns an iterator over the query items, with shared access to every fetched term.
+    pub fn iter<'w>(&'w mut self, world: &'w World) -> impl Iterator<Item = DynamicQueryItem<'w>> {
+        self.update_archetypes(world);
+        let state: &'w Self = self;
+        let world = world.as_unsafe_world_cell_readonly();
+        state.entities(world).map(move |entity| {
+            // SAFETY: the world is borrowed immutably and every term is fetched immutably.
+            unsafe { state.fetch(world, entity, true) }
+        })
+    }
+
+    /// Returns an iterator over the query items, with exclusive access to the terms that were
+    /// added with [`QueryBuilder::write`] or [`QueryBuilder::optional_write`].
+    pub fn iter_mut<'w>(
+        &'w mut self,
+        world: &'w mut World,
+    ) -> impl Iterator<Item = DynamicQueryItem<'w>> {
+        self.update_archetypes(world);
+        let state: &'w Self = self;
+        let world = world.as_unsafe_world_cell();
+        state.entities(world).map(move |entity| {
+            // SAFETY: the world is borrowed mutably, the builder ensures that every term is
+            // accessed only once, and every item belongs to a different entity.
+            unsafe { state.fetch(world, entity, false) }
+        })
+    }
+
+    /// Returns the query item for the given [`Entity`], with shared access to every fetched term.
+    ///
+    /// Returns `None` if the entity does not exist or does not match the query.
+    pub fn get<'w>(&mut self, world: &'w World, entity: Entity) -> Option<DynamicQueryItem<'w>> {
+        if !self.contains(entity, world) {
+            return None;
+        }
+        // SAFETY: the world is borrowed immutably and every term is fetched immutably.
+        Some(unsafe { self.fetch(world.as_unsafe_world_cell_readonly(), entity, true) })
+    }
+
+    /// Returns the query item for the given [`Entity`], with exclusive access to the terms that
+    /// were added with [`QueryBuilder::write`] or [`QueryBuilder::optional_write`].
+    ///
+    /// Returns `None` if the entity does not exist or does not match the query.
+    pub fn get_mut<'w>(
+        &mut self,
+        world: &'w mut World,
+        entity: Entity,
+    ) -> Option<DynamicQueryItem<'w>> {
+        if !self.contains(entity, world) {
+            return None;
+        }
+        // SAFETY: the world is borrowed mutably and the builder ensures that every term is
+        // accessed only once.
+        Some(unsafe { self.fetch(world.as_unsafe_world_cell(), entity, false) })
+    }
+
+    fn validate_world(&self, world_id: WorldId) {
+        assert!(
+            world_id == self.world_id,
+            "Attempted to use DynamicQueryState with a mismatched World. QueryStates can only be used with the World they were created from.",
+        );
+    }
+
+    fn matches(&self, archetype: &Archetype) -> bool {
+        self.fetches
+            .iter()
+            .all(|fetch| fetch.optional || archetype.contains(fetch.component_id))
+            && self.with.iter().all(|&id| archetype.contains(id))
+            && !self.without.iter().any(|&id| archetype.contains(id))
+    }
+
+    fn entities<'w>(&'w self, world: UnsafeWorldCell<'w>) -> impl Iterator<Item = Entity> + 'w {
+        self.matched_archetype_ids.iter().flat_map(move |&id| {
+            world.archetypes()[id]
+                .entities()
+                .iter()
+                .map(|entity| entity.entity())
+        })
+    }
+
+    /// # Safety
+    ///
+    /// - `entity` must exist in `world` and match the query.
+    /// - If `read_only` is `false`, the caller must have exclusive access to the mutable terms of
+    ///   `entity`, and shared access to the others. Otherwise, shared access to all of them.
+    unsafe fn fetch<'w>(
+        &self,
+        world: UnsafeWorldCell<'w>,
+        entity: Entity,
+        read_only: bool,
+    ) -> DynamicQueryItem<'w> {
+        let cell = world.get_entity(entity).unwrap();
+        let fetched = self
+            .fetches
+            .iter()
+            .map(|fetch| {
+                // SAFETY: access is ensured by the caller.
+                unsafe {
+                    if fetch.mutable && !read_only {
+                        cell.get_mut_by_id(fetch.component_id)
+                            .map(FetchedComponent::Mut)
+                    } else {
+                        cell.get_by_id(fetch.component_id)
+                            .map(FetchedComponent::Ref)
+                    }
+                }
+            })
+            .collect();
+        DynamicQueryItem { entity, fetched }
+    }
+}
+
+enum FetchedComponent<'w> {
+    Ref(Ptr<'w>),
+    Mut(MutUntyped<'w>),
+}
+
+/// An item returned by a [`DynamicQueryState`], holding the fetched terms of an entity.
+pub struct DynamicQueryItem<'w> {
+    entity: Entity,
+    fetched: Vec<Option<FetchedComponent<'w>>>,
+}
+
+impl<'w> DynamicQueryItem<'w> {
+    /// Returns the matched entity.
+    #[inline]
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns the number of fetched terms.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fetched.len()
+    }
+
+    /// Returns `true` if the query does not fetch any term.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fetched.is_empty()
+    }
+
+    /// Returns the term at `index`, or `None` if it is an optional term the entity does not have.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Ptr<'_>> {
+        self.fetched[index].as_ref().map(|fetched| match fetched {
+            FetchedComponent::Ref(ptr) => *ptr,
+            FetchedComponent::Mut(mut_untyped) => mut_untyped.as_ref(),
+        })
+    }
+
+    /// Returns the mutable term at `index`, or `None` if it is an optional term the entity does
+    /// not have, or if the item was not fetched with mutable access.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<MutUntyped<'_>> {
+        match self.fetched[index].as_mut()? {
+            FetchedComponent::Ref(_) => None,
+            FetchedComponent::Mut(mut_untyped) => Some(mut_untyped.reborrow()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryBuilder;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::query::FilteredAccess;
+
+    #[derive(Component, PartialEq, Debug)]
+    struct A(usize);
+
+    #[derive(Component, PartialEq, Debug)]
+    #[component(storage = "SparseSet")]
+    struct B(usize);
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn filters() {
+        let mut world = World::new();
+        let ab = world.spawn((A(0), B(1))).id();
+        let a = world.spawn(A(2)).id();
+        let ac = world.spawn((A(3), C)).id();
+        let [a_id, b_id, c_id] = [
+            world.init_component::<A>(),
+            world.init_component::<B>(),
+            world.init_component::<C>(),
+        ];
+
+        let mut query = QueryBuilder::new(&mut world)
+            .read(a_id)
+            .optional_read(b_id)
+            .without(c_id)
+            .build();
+        let mut items: Vec<_> = query
+            .iter(&world)
+            .map(|item| {
+                // SAFETY: the terms match the component types.
+                unsafe {
+                    (
+                        item.entity(),
+                        item.get(0).unwrap().deref::<A>().0,
+                        item.get(1).map(|b| b.deref::<B>().0),
+                    )
+                }
+            })
+            .collect();
+        items.sort();
+        assert_eq!(items, vec![(ab, 0, Some(1)), (a, 2, None)]);
+
+        let mut query = QueryBuilder::new(&mut world).with(c_id).build();
+        let entities: Vec<_> = query.iter(&world).map(|item| item.entity()).collect();
+        assert_eq!(entities, vec![ac]);
+        assert!(query.get(&world, ab).is_none());
+    }
+
+    #[test]
+    fn writes_and_new_archetypes() {
+        let mut world = World::new();
+        let a_id = world.init_component::<A>();
+        let b_id = world.init_component::<B>();
+        let mut query = QueryBuilder::new(&mut world)
+            .write(a_id)
+            .optional_write(b_id)
+            .build();
+
+        // archetypes created after the query is built are matched too
+        let e1 = world.spawn(A(1)).id();
+        let e2 = world.spawn((A(2), B(2))).id();
+        for mut item in query.iter_mut(&mut world) {
+            // SAFETY: the terms match the component types.
+            unsafe {
+                item.get_mut(0).unwrap().as_mut().deref_mut::<A>().0 *= 10;
+                if let Some(mut b) = item.get_mut(1) {
+                    b.as_mut().deref_mut::<B>().0 *= 10;
+                }
+            }
+        }
+        assert_eq!(world.get::<A>(e1), Some(&A(10)));
+        assert_eq!(world.get::<A>(e2), Some(&A(20)));
+        assert_eq!(world.get::<B>(e2), Some(&B(20)));
+
+        let mut item = query.get_mut(&mut world, e1).unwrap();
+        assert!(item.get_mut(1).is_none());
+        assert!(query.get(&world, e2).unwrap().get_mut(0).is_none());
+    }
+
+    #[test]
+    fn disabled_entities() {
+        let mut world = World::new();
+        world.spawn(A(1));
+        world.spawn((A(2), Disabled));
+        let a_id = world.init_component::<A>();
+
+        let mut query = QueryBuilder::new(&mut world).read(a_id).build();
+        assert_eq!(query.iter(&world).count(), 1);
+
+        let mut query = QueryBuilder::new(&mut world)
+            .read(a_id)
+            .include_disabled()
+            .build();
+        assert_eq!(query.iter(&world).count(), 2);
+
+        let disabled_id = world.init_component::<Disabled>();
+        let mut query = QueryBuilder::new(&mut world)
+            .read(a_id)
+            .with(disabled_id)
+            .build();
+        assert_eq!(query.iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn change_detection() {
+        let mut world = World::new();
+        let a_id = world.init_component::<A>();
+        let entity = world.spawn(A(0)).id();
+        let mut query = QueryBuilder::new(&mut world).write(a_id).build();
+        world.clear_trackers();
+
+        // read-only iteration does not trigger change detection
+        assert_eq!(query.iter(&world).count(), 1);
+        let mut changed = world.query_filtered::<Entity, Changed<A>>();
+        assert_eq!(changed.iter(&world).count(), 0);
+
+        let mut item = query.get_mut(&mut world, entity).unwrap();
+        item.get_mut(0).unwrap().set_changed();
+        assert_eq!(changed.iter(&world).collect::<Vec<_>>(), vec![entity]);
+    }
+
+    #[test]
+    fn access_matches_static_queries() {
+        let mut world = World::new();
+        let a_id = world.init_component::<A>();
+        let b_id = world.init_component::<B>();
+        let c_id = world.init_component::<C>();
+
+        let dynamic = QueryBuilder::new(&mut world)
+            .write(a_id)
+            .optional_read(b_id)
+            .without(c_id)
+            .build();
+        let typed = world.query_filtered::<(&mut A, Option<&B>), Without<C>>();
+        assert_eq!(dynamic.component_access(), &typed.component_access);
+
+        let mut with_c = FilteredAccess::default();
+        with_c.add_write(a_id);
+        with_c.and_with(c_id);
+        assert!(dynamic.component_access().is_compatible(&with_c));
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_terms() {
+        let mut world = World::new();
+        let a_id = world.init_component::<A>();
+        QueryBuilder::new(&mut world).write(a_id).read(a_id);
+    }
+}
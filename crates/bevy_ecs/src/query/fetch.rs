@@ -1,7 +1,7 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
     change_detection::{Ticks, TicksMut},
-    component::{Component, ComponentId, ComponentStorage, StorageType, Tick},
+    component::{Component, ComponentId, ComponentStorage, Components, StorageType, Tick},
     entity::Entity,
     query::{Access, DebugCheckedUnwrap, FilteredAccess},
     storage::{ComponentSparseSet, Table, TableRow},
@@ -437,6 +437,18 @@ pub unsafe trait WorldQuery {
     /// Creates and initializes a [`State`](WorldQuery::State) for this [`WorldQuery`] type.
     fn init_state(world: &mut World) -> Self::State;
 
+    /// Attempts to create a [`State`](WorldQuery::State) for this [`WorldQuery`] type without
+    /// mutating the [`World`].
+    ///
+    /// Returns `None` if a component used by this query hasn't been initialized in `components`.
+    ///
+    /// This is used by [`QueryState::transmute`](crate::query::QueryState::transmute) and
+    /// [`QueryState::join`](crate::query::QueryState::join). The default implementation returns
+    /// `None`, so a query that doesn't override it cannot be the target of a transmute or join.
+    fn get_state(_components: &Components) -> Option<Self::State> {
+        None
+    }
+
     /// Returns `true` if this query matches a set of components. Otherwise, returns `false`.
     fn matches_component_set(
         state: &Self::State,
@@ -512,6 +524,10 @@ unsafe impl WorldQuery for Entity {
 
     fn init_state(_world: &mut World) {}
 
+    fn get_state(_components: &Components) -> Option<()> {
+        Some(())
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -591,6 +607,10 @@ unsafe impl<'a> WorldQuery for EntityRef<'a> {
 
     fn init_state(_world: &mut World) {}
 
+    fn get_state(_components: &Components) -> Option<()> {
+        Some(())
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -734,6 +754,10 @@ unsafe impl<T: Component> WorldQuery for &T {
         world.init_component::<T>()
     }
 
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -895,6 +919,10 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
         world.init_component::<T>()
     }
 
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1056,6 +1084,10 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
         world.init_component::<T>()
     }
 
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1163,6 +1195,10 @@ unsafe impl<T: WorldQuery> WorldQuery for Option<T> {
         T::init_state(world)
     }
 
+    fn get_state(components: &Components) -> Option<T::State> {
+        T::get_state(components)
+    }
+
     fn matches_component_set(
         _state: &T::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1283,8 +1319,10 @@ unsafe impl<T: Component> WorldQuery for Has<T> {
         *fetch
     }
 
-    fn update_component_access(_state: &Self::State, _access: &mut FilteredAccess<ComponentId>) {
-        // Do nothing as presence of `Has<T>` never affects whether two queries are disjoint
+    fn update_component_access(&state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        // Presence of `Has<T>` never affects whether two queries are disjoint, but it still
+        // mentions `T`, which matters for `Disabled`
+        access.add_archetypal(state);
     }
 
     fn update_archetype_component_access(
@@ -1298,6 +1336,10 @@ unsafe impl<T: Component> WorldQuery for Has<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<T>()
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1394,6 +1436,10 @@ macro_rules! impl_tuple_fetch {
                 ($($name::init_state(_world),)*)
             }
 
+            fn get_state(_components: &Components) -> Option<Self::State> {
+                Some(($($name::get_state(_components)?,)*))
+            }
+
             fn matches_component_set(state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($name,)*) = state;
                 true $(&& $name::matches_component_set($name, _set_contains_id))*
@@ -1517,6 +1563,10 @@ macro_rules! impl_anytuple_fetch {
                 ($($name::init_state(_world),)*)
             }
 
+            fn get_state(_components: &Components) -> Option<Self::State> {
+                Some(($($name::get_state(_components)?,)*))
+            }
+
             fn matches_component_set(_state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($name,)*) = _state;
                 false $(|| $name::matches_component_set($name, _set_contains_id))*
@@ -1591,6 +1641,10 @@ unsafe impl<Q: WorldQuery> WorldQuery for NopWorldQuery<Q> {
         Q::init_state(world)
     }
 
+    fn get_state(components: &Components) -> Option<Self::State> {
+        Q::get_state(components)
+    }
+
     fn matches_component_set(
         state: &Self::State,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1654,6 +1708,10 @@ unsafe impl<T: ?Sized> WorldQuery for PhantomData<T> {
 
     fn init_state(_world: &mut World) -> Self::State {}
 
+    fn get_state(_components: &Components) -> Option<Self::State> {
+        Some(())
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
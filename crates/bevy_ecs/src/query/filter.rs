@@ -1,7 +1,7 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
-    component::{Component, ComponentId, ComponentStorage, StorageType, Tick},
-    entity::Entity,
+    component::{Component, ComponentId, ComponentStorage, Components, StorageType, Tick},
+    entity::{Disabled, Entity},
     query::{Access, DebugCheckedUnwrap, FilteredAccess, WorldQuery},
     storage::{Column, ComponentSparseSet, Table, TableRow},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
@@ -105,6 +105,10 @@ unsafe impl<T: Component> WorldQuery for With<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<T>()
+    }
+
     fn matches_component_set(
         &id: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -206,6 +210,10 @@ unsafe impl<T: Component> WorldQuery for Without<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<T>()
+    }
+
     fn matches_component_set(
         &id: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -217,6 +225,110 @@ unsafe impl<T: Component> WorldQuery for Without<T> {
 // SAFETY: no component access or archetype component access
 unsafe impl<T: Component> ReadOnlyWorldQuery for Without<T> {}
 
+/// Filter that includes [`Disabled`] entities in a query, which excludes them by default.
+///
+/// Unlike `With<Disabled>`, this matches the entities that are not disabled as well.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// # use bevy_ecs::entity::Disabled;
+/// # use bevy_ecs::query::{Has, IncludeDisabled};
+/// # use bevy_ecs::system::IntoSystem;
+/// # use bevy_ecs::system::Query;
+/// #
+/// # #[derive(Component)]
+/// # struct Name { name: &'static str };
+/// #
+/// fn all_names_system(query: Query<(&Name, Has<Disabled>), IncludeDisabled>) {
+///     for (name, disabled) in &query {
+///         println!("{} is disabled: {disabled}", name.name);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(all_names_system);
+/// ```
+pub struct IncludeDisabled;
+
+// SAFETY: `Self::ReadOnly` is the same as `Self`
+unsafe impl WorldQuery for IncludeDisabled {
+    type Fetch<'w> = ();
+    type Item<'w> = ();
+    type ReadOnly = Self;
+    type State = ComponentId;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(_: Self::Item<'wlong>) -> Self::Item<'wshort> {}
+
+    #[inline]
+    unsafe fn init_fetch(
+        _world: UnsafeWorldCell,
+        _state: &ComponentId,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
+    }
+
+    const IS_DENSE: bool = true;
+
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline]
+    unsafe fn set_table(_fetch: &mut (), _state: &ComponentId, _table: &Table) {}
+
+    #[inline]
+    unsafe fn set_archetype(
+        _fetch: &mut (),
+        _state: &ComponentId,
+        _archetype: &Archetype,
+        _table: &Table,
+    ) {
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+    }
+
+    #[inline]
+    fn update_component_access(&id: &ComponentId, access: &mut FilteredAccess<ComponentId>) {
+        // `Or<(With<Disabled>, Without<Disabled>)>` matches every entity, while still
+        // mentioning `Disabled` so that `QueryState` does not exclude disabled entities
+        let mut with_disabled = access.clone();
+        with_disabled.and_with(id);
+        access.and_without(id);
+        access.append_or(&with_disabled);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        _state: &ComponentId,
+        _archetype: &Archetype,
+        _access: &mut Access<ArchetypeComponentId>,
+    ) {
+    }
+
+    fn init_state(world: &mut World) -> ComponentId {
+        world.init_component::<Disabled>()
+    }
+
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<Disabled>()
+    }
+
+    fn matches_component_set(
+        _state: &ComponentId,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+// SAFETY: no component access or archetype component access
+unsafe impl ReadOnlyWorldQuery for IncludeDisabled {}
+
 /// A filter that tests if any of the given filters apply.
 ///
 /// This is useful for example if a system with multiple components in a query only wants to run
@@ -370,6 +482,10 @@ macro_rules! impl_query_filter_tuple {
                 ($($filter::init_state(world),)*)
             }
 
+            fn get_state(components: &Components) -> Option<Self::State> {
+                Some(($($filter::get_state(components)?,)*))
+            }
+
             fn matches_component_set(_state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($filter,)*) = _state;
                 false $(|| $filter::matches_component_set($filter, _set_contains_id))*
@@ -534,6 +650,10 @@ macro_rules! impl_tick_filter {
                 world.init_component::<T>()
             }
 
+            fn get_state(components: &Components) -> Option<ComponentId> {
+                components.component_id::<T>()
+            }
+
             fn matches_component_set(&id: &ComponentId, set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 set_contains_id(id)
             }
@@ -631,6 +751,7 @@ pub trait ArchetypeFilter {}
 
 impl<T> ArchetypeFilter for With<T> {}
 impl<T> ArchetypeFilter for Without<T> {}
+impl ArchetypeFilter for IncludeDisabled {}
 
 macro_rules! impl_archetype_filter_tuple {
     ($($filter: ident),*) => {
@@ -6,7 +6,7 @@ use crate::{
     storage::{TableId, TableRow, Tables},
     world::unsafe_world_cell::UnsafeWorldCell,
 };
-use std::{borrow::Borrow, iter::FusedIterator, mem::MaybeUninit};
+use std::{borrow::Borrow, cmp::Ordering, iter::FusedIterator, mem::MaybeUninit};
 
 use super::ReadOnlyWorldQuery;
 
@@ -39,6 +39,77 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryIter<'w, 's, Q, F> {
             cursor: QueryIterationCursor::init(world, query_state, last_run, this_run),
         }
     }
+
+    /// Collects the remaining query items and returns an iterator over them, sorted by the key
+    /// extracted from each item by `f`.
+    ///
+    /// The sort is stable: items with equal keys keep the order in which the query returned them.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Name(&'static str);
+    /// #
+    /// #[derive(Component)]
+    /// struct Initiative(u32);
+    ///
+    /// fn take_turns(query: Query<(&Name, &Initiative)>) {
+    ///     for (name, _) in query.iter().sort_by_key(|(_, initiative)| initiative.0) {
+    ///         println!("{}'s turn", name.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(take_turns);
+    /// ```
+    pub fn sort_by_key<K: Ord>(
+        self,
+        f: impl FnMut(&Q::Item<'w>) -> K,
+    ) -> std::vec::IntoIter<Q::Item<'w>> {
+        let mut items: Vec<_> = self.collect();
+        items.sort_by_key(f);
+        items.into_iter()
+    }
+
+    /// Collects the remaining query items and returns an iterator over them, sorted with the
+    /// `compare` function.
+    ///
+    /// The sort is stable: items that compare equal keep the order in which the query returned them.
+    pub fn sort_by(
+        self,
+        compare: impl FnMut(&Q::Item<'w>, &Q::Item<'w>) -> Ordering,
+    ) -> std::vec::IntoIter<Q::Item<'w>> {
+        let mut items: Vec<_> = self.collect();
+        items.sort_by(compare);
+        items.into_iter()
+    }
+
+    /// Collects the remaining query items and returns an iterator over them, sorted by the key
+    /// extracted from each item by `f`.
+    ///
+    /// This sort is unstable, which is faster than [`sort_by_key`](Self::sort_by_key) but may
+    /// reorder items with equal keys.
+    pub fn sort_unstable_by_key<K: Ord>(
+        self,
+        f: impl FnMut(&Q::Item<'w>) -> K,
+    ) -> std::vec::IntoIter<Q::Item<'w>> {
+        let mut items: Vec<_> = self.collect();
+        items.sort_unstable_by_key(f);
+        items.into_iter()
+    }
+
+    /// Collects the remaining query items and returns an iterator over them, sorted with the
+    /// `compare` function.
+    ///
+    /// This sort is unstable, which is faster than [`sort_by`](Self::sort_by) but may reorder
+    /// items that compare equal.
+    pub fn sort_unstable_by(
+        self,
+        compare: impl FnMut(&Q::Item<'w>, &Q::Item<'w>) -> Ordering,
+    ) -> std::vec::IntoIter<Q::Item<'w>> {
+        let mut items: Vec<_> = self.collect();
+        items.sort_unstable_by(compare);
+        items.into_iter()
+    }
 }
 
 impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Iterator for QueryIter<'w, 's, Q, F> {
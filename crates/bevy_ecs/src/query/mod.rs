@@ -1,6 +1,7 @@
 //! Contains APIs for retrieving component data from the world.
 
 mod access;
+mod builder;
 mod fetch;
 mod filter;
 mod iter;
@@ -8,6 +9,7 @@ mod par_iter;
 mod state;
 
 pub use access::*;
+pub use builder::*;
 pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
@@ -63,7 +65,9 @@ impl<T> DebugCheckedUnwrap for Option<T> {
 #[cfg(test)]
 mod tests {
     use super::{ReadOnlyWorldQuery, WorldQuery};
-    use crate::prelude::{AnyOf, Changed, Entity, Or, QueryState, With, Without};
+    use crate::prelude::{
+        AnyOf, Changed, Disabled, Entity, IncludeDisabled, Or, QueryState, With, Without,
+    };
     use crate::query::{ArchetypeFilter, Has, QueryCombinationIter};
     use crate::schedule::{IntoSystemConfigs, Schedule};
     use crate::system::{IntoSystem, Query, System, SystemState};
@@ -99,6 +103,44 @@ mod tests {
         assert_eq!(values, vec![&B(3)]);
     }
 
+    #[test]
+    fn query_sorted() {
+        let mut world = World::new();
+        world.spawn((A(3), B(0)));
+        world.spawn((A(1), B(1)));
+        world.spawn(A(2));
+        world.spawn((A(1), B(3), C(3)));
+
+        let mut query = world.query::<(&A, Option<&B>)>();
+        let values = query
+            .iter(&world)
+            .sort_by_key(|(a, _)| a.0)
+            .map(|(a, b)| (a.0, b.map(|b| b.0)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![(1, Some(1)), (1, Some(3)), (2, None), (3, Some(0))]
+        );
+
+        let values = query
+            .iter(&world)
+            .sort_unstable_by(|(a1, _), (a2, _)| a2.0.cmp(&a1.0))
+            .map(|(a, _)| a.0)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![3, 2, 1, 1]);
+
+        let mut system_state = SystemState::<Query<(&mut A, &B)>>::new(&mut world);
+        let mut query = system_state.get_mut(&mut world);
+        for (i, (mut a, _)) in query.iter_mut_sorted_by_key(|(_, b)| b.0).enumerate() {
+            a.0 = i;
+        }
+        let values = query
+            .iter_sorted_by(|(a1, _), (a2, _)| a1.0.cmp(&a2.0))
+            .map(|(a, b)| (a.0, b.0))
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![(0, 0), (1, 1), (2, 3)]);
+    }
+
     #[test]
     fn query_filtered_exactsizeiterator_len() {
         fn choose(n: usize, k: usize) -> usize {
@@ -799,4 +841,47 @@ mod tests {
         let values = world.query::<&B>().iter(&world).collect::<Vec<&B>>();
         assert_eq!(values, vec![&B(2)]);
     }
+
+    #[test]
+    fn disabled_entities_are_excluded_by_default() {
+        let mut world = World::new();
+        let enabled = world.spawn(A(1)).id();
+        let disabled = world.spawn((A(2), Disabled)).id();
+
+        let mut query = world.query::<Entity>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![enabled]);
+        let mut query = world.query::<crate::world::EntityRef>();
+        assert_eq!(query.iter(&world).count(), 1);
+        assert!(world.query::<&A>().get(&world, disabled).is_err());
+
+        let mut query = world.query_filtered::<Entity, With<Disabled>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![disabled]);
+
+        let mut query = world.query_filtered::<&A, IncludeDisabled>();
+        let mut values = query.iter(&world).map(|a| a.0).collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![1, 2]);
+
+        let entities = world.iter_entities().map(|e| e.id()).collect::<Vec<_>>();
+        assert_eq!(entities, vec![enabled]);
+        assert_eq!(world.iter_entities_including_disabled().count(), 2);
+
+        // re-enabling an entity makes it visible to existing queries again
+        world.entity_mut(disabled).remove::<Disabled>();
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn has_disabled_includes_disabled_entities() {
+        let mut world = World::new();
+        let enabled = world.spawn(A(1)).id();
+        let disabled = world.spawn((A(2), Disabled)).id();
+
+        let mut query = world.query::<(Entity, Has<Disabled>)>();
+        let mut results = query.iter(&world).collect::<Vec<_>>();
+        results.sort_unstable();
+        assert_eq!(results, vec![(enabled, false), (disabled, true)]);
+        assert_eq!(query.get(&world, disabled), Ok((disabled, true)));
+    }
 }
@@ -35,6 +35,11 @@ pub struct QueryState<Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    /// The id of [`Disabled`](crate::entity::Disabled), if the query excludes disabled entities.
+    pub(crate) disabled_id: Option<ComponentId>,
+    /// The combined access of the states this one was transmuted or joined from, whose filters
+    /// new archetypes must also match.
+    pub(crate) origin_access: Option<FilteredAccess<ComponentId>>,
 }
 
 impl<Q: WorldQuery, F: ReadOnlyWorldQuery> std::fmt::Debug for QueryState<Q, F> {
@@ -111,6 +116,13 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        // Disabled entities are excluded, unless the query explicitly mentions them.
+        let disabled = world.disabled_component;
+        let disabled_id = (!component_access.references(disabled)).then(|| {
+            component_access.and_without(disabled);
+            disabled
+        });
+
         let mut state = Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -118,6 +130,8 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
             matched_archetype_ids: Vec::new(),
             fetch_state,
             filter_state,
+            disabled_id,
+            origin_access: None,
             component_access,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
@@ -127,6 +141,189 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         state
     }
 
+    /// Creates a state matching the archetypes given by `archetype_ids` that `Q` and `F` match,
+    /// without initializing any component.
+    ///
+    /// # Panics
+    ///
+    /// If a component used by `Q` or `F` hasn't been initialized in `world`.
+    fn new_within_archetypes(
+        world: &World,
+        archetype_ids: impl Iterator<Item = ArchetypeId>,
+        archetype_generation: ArchetypeGeneration,
+        disabled_id: Option<ComponentId>,
+        origin_access: FilteredAccess<ComponentId>,
+    ) -> Self {
+        let components = world.components();
+        let (Some(fetch_state), Some(filter_state)) =
+            (Q::get_state(components), F::get_state(components))
+        else {
+            panic!(
+                "Could not create the state of {}: every component it uses must be initialized in the World, and every query it is made of must implement `WorldQuery::get_state`.",
+                std::any::type_name::<Self>(),
+            );
+        };
+
+        let mut component_access = FilteredAccess::default();
+        Q::update_component_access(&fetch_state, &mut component_access);
+        let mut filter_component_access = FilteredAccess::default();
+        F::update_component_access(&filter_state, &mut filter_component_access);
+        component_access.extend(&filter_component_access);
+        if let Some(disabled_id) = disabled_id {
+            component_access.and_without(disabled_id);
+        }
+
+        let origin_access = Some(origin_access);
+        let mut state = Self {
+            world_id: world.id(),
+            archetype_generation,
+            matched_table_ids: Vec::new(),
+            matched_archetype_ids: Vec::new(),
+            fetch_state,
+            filter_state,
+            disabled_id,
+            origin_access,
+            component_access,
+            matched_tables: Default::default(),
+            matched_archetypes: Default::default(),
+            archetype_component_access: Default::default(),
+        };
+        let archetypes = world.archetypes();
+        for archetype_id in archetype_ids {
+            state.new_archetype(&archetypes[archetype_id]);
+        }
+        state
+    }
+
+    /// Returns the access of this state, combined with the access of the states it was
+    /// transmuted or joined from.
+    fn combined_origin_access(&self) -> FilteredAccess<ComponentId> {
+        let mut access = self.component_access.clone();
+        if let Some(origin_access) = &self.origin_access {
+            access.extend(origin_access);
+        }
+        access
+    }
+
+    /// Creates a [`QueryState`] fetching `NewQ` from the entities matched by this state.
+    ///
+    /// This is how a [`QueryLens`](crate::system::QueryLens) narrows a [`Query`](crate::system::Query),
+    /// see [`Query::transmute_lens`](crate::system::Query::transmute_lens).
+    ///
+    /// The new state matches the archetypes that this state matched when it was last updated.
+    /// Archetypes created afterwards are only matched by the new state if they also pass the
+    /// `With`/`Without` filters of this state. Filters of this state that aren't archetypal, like
+    /// [`Changed`](crate::query::Changed), are not applied to the new state.
+    ///
+    /// # Panics
+    ///
+    /// - If `NewQ` accesses a component that this state doesn't access, or writes a component
+    ///   that this state only reads.
+    /// - If a component used by `NewQ` hasn't been initialized in `world`.
+    /// - If `world` does not match the one used to call `QueryState::new` for this instance.
+    pub fn transmute<NewQ: WorldQuery>(&self, world: &World) -> QueryState<NewQ> {
+        self.transmute_filtered::<NewQ, ()>(world)
+    }
+
+    /// Creates a [`QueryState`] fetching `NewQ` from the entities matched by both this state and
+    /// the `NewF` filter.
+    ///
+    /// See [`transmute`](Self::transmute) for more details.
+    pub fn transmute_filtered<NewQ: WorldQuery, NewF: ReadOnlyWorldQuery>(
+        &self,
+        world: &World,
+    ) -> QueryState<NewQ, NewF> {
+        self.validate_world(world.id());
+        let state = QueryState::<NewQ, NewF>::new_within_archetypes(
+            world,
+            self.matched_archetype_ids.iter().copied(),
+            self.archetype_generation,
+            self.disabled_id,
+            self.combined_origin_access(),
+        );
+        assert!(
+            state
+                .component_access
+                .access()
+                .is_subset(self.component_access.access()),
+            "Transmuted state {} attempts to access components that are not accessed by the original state {}.",
+            std::any::type_name::<QueryState<NewQ, NewF>>(),
+            std::any::type_name::<Self>(),
+        );
+        state
+    }
+
+    /// Creates a [`QueryState`] fetching `NewQ` from the entities matched by both this state and
+    /// `other`.
+    ///
+    /// This is how two [`Query`](crate::system::Query)s are joined on their entities, see
+    /// [`Query::join`](crate::system::Query::join).
+    ///
+    /// The new state matches the archetypes that both states matched when they were last updated.
+    /// Archetypes created afterwards are only matched by the new state if they also pass the
+    /// `With`/`Without` filters of both states. Filters of either state that aren't archetypal,
+    /// like [`Changed`](crate::query::Changed), are not applied to the new state.
+    ///
+    /// # Panics
+    ///
+    /// - If `NewQ` accesses a component that neither state accesses, or writes a component that
+    ///   both states only read.
+    /// - If a component used by `NewQ` hasn't been initialized in `world`.
+    /// - If `world` does not match the one used to create either state.
+    pub fn join<OtherQ: WorldQuery, OtherF: ReadOnlyWorldQuery, NewQ: WorldQuery>(
+        &self,
+        world: &World,
+        other: &QueryState<OtherQ, OtherF>,
+    ) -> QueryState<NewQ> {
+        self.join_filtered::<OtherQ, OtherF, NewQ, ()>(world, other)
+    }
+
+    /// Creates a [`QueryState`] fetching `NewQ` from the entities matched by this state, `other`
+    /// and the `NewF` filter.
+    ///
+    /// See [`join`](Self::join) for more details.
+    pub fn join_filtered<
+        OtherQ: WorldQuery,
+        OtherF: ReadOnlyWorldQuery,
+        NewQ: WorldQuery,
+        NewF: ReadOnlyWorldQuery,
+    >(
+        &self,
+        world: &World,
+        other: &QueryState<OtherQ, OtherF>,
+    ) -> QueryState<NewQ, NewF> {
+        self.validate_world(world.id());
+        other.validate_world(world.id());
+
+        // Disabled entities are only matched by both states if neither excludes them.
+        let disabled_id = self.disabled_id.or(other.disabled_id);
+        let state = QueryState::<NewQ, NewF>::new_within_archetypes(
+            world,
+            self.matched_archetype_ids
+                .iter()
+                .copied()
+                .filter(|id| other.matched_archetypes.contains(id.index())),
+            self.archetype_generation.min(other.archetype_generation),
+            disabled_id,
+            {
+                let mut origin_access = self.combined_origin_access();
+                origin_access.extend(&other.combined_origin_access());
+                origin_access
+            },
+        );
+
+        let mut joined_access = self.component_access.access().clone();
+        joined_access.extend(other.component_access.access());
+        assert!(
+            state.component_access.access().is_subset(&joined_access),
+            "Joined state {} attempts to access components that are not accessed by {} or {}.",
+            std::any::type_name::<QueryState<NewQ, NewF>>(),
+            std::any::type_name::<Self>(),
+            std::any::type_name::<QueryState<OtherQ, OtherF>>(),
+        );
+        state
+    }
+
     /// Checks if the query is empty for the given [`World`], where the last change and current tick are given.
     #[inline]
     pub fn is_empty(&self, world: &World, last_run: Tick, this_run: Tick) -> bool {
@@ -202,6 +399,10 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     pub fn new_archetype(&mut self, archetype: &Archetype) {
         if Q::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
+            && !self.disabled_id.is_some_and(|id| archetype.contains(id))
+            && self.origin_access.as_ref().map_or(true, |access| {
+                access.filters_match(|id| archetype.contains(id))
+            })
         {
             Q::update_archetype_component_access(
                 &self.fetch_state,
@@ -1314,7 +1515,7 @@ impl fmt::Display for QueryEntityError {
 
 #[cfg(test)]
 mod tests {
-    use crate::{prelude::*, query::QueryEntityError};
+    use crate::{self as bevy_ecs, prelude::*, query::QueryEntityError, system::SystemState};
 
     #[test]
     fn get_many_unchecked_manual_uniqueness() {
@@ -1419,6 +1620,139 @@ mod tests {
         let mut query_state = world_1.query::<Entity>();
         let _panics = query_state.get_many_mut(&mut world_2, []);
     }
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(usize);
+    #[derive(Component, Debug, PartialEq)]
+    struct B(usize);
+    #[derive(Component, Debug, PartialEq)]
+    struct C(usize);
+
+    #[test]
+    fn transmute() {
+        let mut world = World::new();
+        world.spawn((A(0), B(0)));
+        world.spawn((A(1), B(1), C(1)));
+        world.spawn(A(2));
+
+        let query_state = world.query_filtered::<(&A, &B), Without<C>>();
+        let mut transmuted = query_state.transmute::<&A>(&world);
+        assert_eq!(transmuted.iter(&world).collect::<Vec<_>>(), vec![&A(0)]);
+
+        let query_state = world.query::<(Entity, &mut A)>();
+        let mut transmuted = query_state.transmute_filtered::<&mut A, With<B>>(&world);
+        for mut a in transmuted.iter_mut(&mut world) {
+            a.0 += 10;
+        }
+        let mut values = world
+            .query::<&A>()
+            .iter(&world)
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![2, 10, 11]);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempts to access components that are not accessed")]
+    fn transmute_to_unaccessed_component() {
+        let mut world = World::new();
+        world.init_component::<B>();
+        let query_state = world.query::<&A>();
+        let _panics = query_state.transmute::<&B>(&world);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempts to access components that are not accessed")]
+    fn transmute_read_to_write() {
+        let mut world = World::new();
+        let query_state = world.query::<&A>();
+        let _panics = query_state.transmute::<&mut A>(&world);
+    }
+
+    #[test]
+    fn join() {
+        let mut world = World::new();
+        let e0 = world.spawn((A(0), B(0))).id();
+        world.spawn(A(1));
+        world.spawn(B(2));
+        let e3 = world.spawn((A(3), B(3), C(3))).id();
+
+        let query_a = world.query::<&A>();
+        let query_b = world.query::<(Entity, &B)>();
+        let mut joined = query_a.join::<_, _, (Entity, &A, &B)>(&world, &query_b);
+        let mut items = joined.iter(&world).collect::<Vec<_>>();
+        items.sort_by_key(|(entity, ..)| *entity);
+        assert_eq!(items, vec![(e0, &A(0), &B(0)), (e3, &A(3), &B(3))]);
+
+        let mut joined = query_a.join_filtered::<_, _, (Entity, &A), With<C>>(&world, &query_b);
+        assert_eq!(joined.iter(&world).collect::<Vec<_>>(), vec![(e3, &A(3))]);
+    }
+
+    #[test]
+    fn transmuted_and_joined_states_keep_original_filters() {
+        let mut world = World::new();
+        world.spawn((A(0), B(0)));
+
+        let query_state = world.query_filtered::<&A, (With<B>, Without<C>)>();
+        let mut transmuted = query_state.transmute::<&A>(&world);
+        let query_b = world.query::<&B>();
+        let mut joined = query_state.join::<_, _, &A>(&world, &query_b);
+
+        // archetypes created afterwards must still match the filters of the original states
+        world.spawn(A(1));
+        world.spawn((A(2), B(2), C(2)));
+        world.spawn((A(3), B(3), C(3))).remove::<C>();
+        let mut values = transmuted.iter(&world).map(|a| a.0).collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 3]);
+        let mut values = joined.iter(&world).map(|a| a.0).collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempts to access components that are not accessed")]
+    fn join_to_unaccessed_component() {
+        let mut world = World::new();
+        world.init_component::<C>();
+        let query_a = world.query::<&A>();
+        let query_b = world.query::<&B>();
+        let _panics = query_a.join::<_, _, (&A, &C)>(&world, &query_b);
+    }
+
+    #[test]
+    fn query_lenses() {
+        fn sum(query: Query<&A>) -> usize {
+            query.iter().map(|a| a.0).sum()
+        }
+
+        let mut world = World::new();
+        world.spawn((A(1), B(1)));
+        world.spawn((A(2), B(2), C(2)));
+        world.spawn(C(3));
+
+        let mut system_state =
+            SystemState::<(Query<(&mut A, &B)>, Query<(Entity, &C)>)>::new(&mut world);
+        let (mut query_ab, mut query_c) = system_state.get_mut(&mut world);
+        assert_eq!(sum(query_ab.transmute_lens::<&A>().query()), 3);
+
+        let mut lens = query_ab.join::<_, _, (&mut A, &C)>(&mut query_c);
+        for (mut a, c) in &mut lens.query() {
+            a.0 += c.0;
+        }
+        assert_eq!(sum(query_ab.transmute_lens::<&A>().query()), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempts to write a component that is only read")]
+    fn read_only_query_lens_write() {
+        let mut world = World::new();
+        let mut system_state = SystemState::<Query<&mut A>>::new(&mut world);
+        let query = system_state.get_mut(&mut world);
+        let mut read_only = query.to_readonly();
+        let _panics = read_only.transmute_lens::<&mut A>();
+    }
 }
 
 /// An error that occurs when evaluating a [`Query`](crate::system::Query) or [`QueryState`] as a single expected result via
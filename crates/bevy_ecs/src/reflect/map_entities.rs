@@ -41,6 +41,21 @@ impl ReflectMapEntities {
             (self.map_entities)(world, mapper, entities);
         });
     }
+
+    /// Like [`map_entities`](Self::map_entities), but for mapping entities to other entities of
+    /// the same world: entity references that are not in the [`EntityMap`] are left unchanged.
+    ///
+    /// See [`EntityMap::world_scope_preserving_unmapped`] for more details.
+    pub fn map_entities_preserving_unmapped(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        entities: &[Entity],
+    ) {
+        entity_map.world_scope_preserving_unmapped(world, |world, mapper| {
+            (self.map_entities)(world, mapper, entities);
+        });
+    }
 }
 
 impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
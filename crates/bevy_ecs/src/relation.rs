@@ -0,0 +1,622 @@
+//! Typed relationships between entities.
+//!
+//! A relation links a *source* entity to a single *target* entity. The source stores the link as
+//! a [`Relation`] component, while the target keeps track of every entity pointing at it through
+//! the relation's [`RelationSources`] component. Both components are regular components, so a
+//! relation can be queried from either side:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::relation::{Relation, RelationCleanup, Sources};
+//!
+//! /// An item equipped by a character.
+//! #[derive(Component)]
+//! struct EquippedBy(Entity);
+//!
+//! impl Relation for EquippedBy {
+//!     type Sources = Sources<EquippedBy>;
+//!     const CLEANUP: RelationCleanup = RelationCleanup::DespawnSources;
+//!
+//!     fn target(&self) -> Entity {
+//!         self.0
+//!     }
+//! }
+//!
+//! let mut world = World::new();
+//! let player = world.spawn_empty().id();
+//! let sword = world.spawn_empty().insert_relation(EquippedBy(player)).id();
+//!
+//! // Query from the source side...
+//! assert_eq!(world.get::<EquippedBy>(sword).unwrap().target(), player);
+//! // ...or from the target side.
+//! assert_eq!(world.get::<Sources<EquippedBy>>(player).unwrap().sources(), &[sword]);
+//!
+//! // The sword is despawned with its owner.
+//! world.despawn(player);
+//! assert!(world.get_entity(sword).is_none());
+//! ```
+//!
+//! Once a relation is [registered](World::init_relation), which [`World::insert_relation`] does
+//! automatically, its components are kept in sync by [hooks](World::register_component_hooks):
+//! inserting, overwriting or removing the [`Relation`] component updates the sources of its
+//! targets, and removing the [`Relation::Sources`] component or despawning either entity cleans
+//! up the other side. Sources are cleaned up according to [`Relation::CLEANUP`] when their target
+//! goes away.
+//!
+//! Changing the target of a relation component in place, through a mutable reference, bypasses
+//! these hooks. Entity mapping does this, so it should be wrapped in
+//! [`World::remap_relations`].
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId},
+    entity::{Entity, EntityMapper, MapEntities},
+    world::{EntityMut, World},
+};
+use std::marker::PhantomData;
+
+/// What happens to the sources of a relation when its target is despawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelationCleanup {
+    /// The relation component is removed from every source, leaving the sources otherwise intact.
+    RemoveEdge,
+    /// Every source is despawned as well, recursively cleaning up their own relations.
+    DespawnSources,
+}
+
+/// A component that links the entity it is on (the source) to another entity (the target).
+///
+/// Each source can have at most one target per relation kind, while a target can be pointed at
+/// by any number of sources, which are tracked in its [`Relation::Sources`] component.
+///
+/// See the [module level documentation](crate::relation) for an example.
+pub trait Relation: Component + Sized {
+    /// The component stored on the target entity that lists every source of this relation.
+    type Sources: RelationSources;
+
+    /// What happens to the sources of this relation when the target is despawned.
+    const CLEANUP: RelationCleanup = RelationCleanup::RemoveEdge;
+
+    /// Returns the target entity of this relation.
+    fn target(&self) -> Entity;
+}
+
+/// A component that lists the sources of a [`Relation`], stored on the target entity.
+///
+/// The order of sources is preserved, new sources are appended to the end.
+pub trait RelationSources: Component + Sized {
+    /// Creates the component with a single source.
+    fn from_source(source: Entity) -> Self;
+
+    /// Returns the sources of the relation, in order.
+    fn sources(&self) -> &[Entity];
+
+    /// Adds `source` to the end of the list.
+    fn add_source(&mut self, source: Entity);
+
+    /// Removes `source` from the list, if it is present.
+    fn remove_source(&mut self, source: Entity);
+}
+
+/// A general purpose [`RelationSources`] component, for relations that do not need a
+/// dedicated type on the target side.
+#[derive(Component, Debug)]
+pub struct Sources<R> {
+    sources: Vec<Entity>,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R: Relation> Sources<R> {
+    /// Returns the sources of the relation, in order.
+    pub fn sources(&self) -> &[Entity] {
+        &self.sources
+    }
+
+    /// Returns an iterator over the sources of the relation.
+    pub fn iter(&self) -> std::slice::Iter<'_, Entity> {
+        self.sources.iter()
+    }
+}
+
+impl<R: Relation> RelationSources for Sources<R> {
+    fn from_source(source: Entity) -> Self {
+        Self {
+            sources: vec![source],
+            marker: PhantomData,
+        }
+    }
+
+    fn sources(&self) -> &[Entity] {
+        &self.sources
+    }
+
+    fn add_source(&mut self, source: Entity) {
+        self.sources.push(source);
+    }
+
+    fn remove_source(&mut self, source: Entity) {
+        self.sources.retain(|e| *e != source);
+    }
+}
+
+impl<R: Relation> MapEntities for Sources<R> {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for entity in &mut self.sources {
+            *entity = entity_mapper.get_or_reserve(*entity);
+        }
+    }
+}
+
+impl<'a, R: Relation> IntoIterator for &'a Sources<R> {
+    type Item = &'a Entity;
+    type IntoIter = std::slice::Iter<'a, Entity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.sources.iter()
+    }
+}
+
+/// Stores the relation kinds registered with a [`World`] through [`World::init_relation`].
+#[derive(Debug, Default)]
+pub struct Relations {
+    relations: Vec<RelationInfo>,
+}
+
+#[derive(Debug)]
+pub(crate) struct RelationInfo {
+    pub(crate) relation: ComponentId,
+    pub(crate) sources: ComponentId,
+    /// Returns the target of the given source, if it has the relation.
+    target: fn(&World, Entity) -> Option<Entity>,
+    /// Moves the given source from the sources of its previous target, if any, to the sources of
+    /// its current target.
+    relink: fn(&mut World, Entity, Option<Entity>),
+}
+
+impl Relations {
+    /// Returns `true` if no relation kinds have been registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.relations.is_empty()
+    }
+
+    /// Returns `true` if the relation with the given component id has been registered.
+    pub fn contains(&self, relation: ComponentId) -> bool {
+        self.relations.iter().any(|info| info.relation == relation)
+    }
+
+    /// Returns `true` if the given component id is the [`Relation::Sources`] of a registered
+    /// relation.
+    pub fn contains_sources(&self, sources: ComponentId) -> bool {
+        self.relations.iter().any(|info| info.sources == sources)
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &RelationInfo> {
+        self.relations.iter()
+    }
+}
+
+fn relation_target<R: Relation>(world: &World, source: Entity) -> Option<Entity> {
+    world.get::<R>(source).map(R::target)
+}
+
+fn relink_source<R: Relation>(world: &mut World, source: Entity, previous: Option<Entity>) {
+    let target = relation_target::<R>(world, source);
+    if target == previous {
+        return;
+    }
+    if let Some(previous) = previous {
+        remove_source::<R>(world, previous, source);
+    }
+    if let Some(target) = target {
+        add_source::<R>(world, target, source);
+    }
+}
+
+/// The `on_insert` hook of a [`Relation`]: adds the source to the sources of its target.
+fn on_insert_relation<R: Relation>(world: &mut World, source: Entity, _: ComponentId) {
+    if let Some(target) = relation_target::<R>(world, source) {
+        add_source::<R>(world, target, source);
+    }
+}
+
+/// The `on_replace` hook of a [`Relation`]: removes the source from the sources of its target,
+/// before the relation is overwritten or removed.
+fn on_replace_relation<R: Relation>(world: &mut World, source: Entity, _: ComponentId) {
+    if let Some(target) = relation_target::<R>(world, source) {
+        remove_source::<R>(world, target, source);
+    }
+}
+
+/// The `on_remove` hook of [`Relation::Sources`]: cleans up every source according to
+/// [`Relation::CLEANUP`].
+fn on_remove_sources<R: Relation>(world: &mut World, target: Entity, _: ComponentId) {
+    let Some(sources) = world
+        .get::<R::Sources>(target)
+        .map(|sources| sources.sources().to_vec())
+    else {
+        return;
+    };
+    for source in sources {
+        // Forget the source first, so that the `on_remove` hook of `R` leaves the sources
+        // component being removed alone.
+        if let Some(mut sources) = world.get_mut::<R::Sources>(target) {
+            sources.remove_source(source);
+        }
+        match R::CLEANUP {
+            RelationCleanup::RemoveEdge => {
+                if let Some(mut source) = world.get_entity_mut(source) {
+                    source.remove::<R>();
+                }
+            }
+            RelationCleanup::DespawnSources => {
+                if world.get_entity(source).is_some() {
+                    world.despawn(source);
+                }
+            }
+        }
+    }
+}
+
+impl World {
+    /// Registers the relation `R` with this world.
+    ///
+    /// This installs [hooks](World::register_component_hooks) on `R` and [`Relation::Sources`],
+    /// which keep both sides of the relation in sync when either component is inserted, replaced
+    /// or removed, and apply [`Relation::CLEANUP`] when a target is despawned. These hooks are
+    /// added to any other hooks of these components.
+    ///
+    /// This is done automatically by [`World::insert_relation`]. Calling it manually is needed
+    /// for relation components inserted in some other way, for example with [`EntityMut::insert`]
+    /// or by a scene, to be linked to their target. A relation component whose target does not
+    /// exist, or is its own entity, is not linked until its target is changed.
+    pub fn init_relation<R: Relation>(&mut self) {
+        if self
+            .component_id::<R>()
+            .is_some_and(|relation| self.relations.contains(relation))
+        {
+            return;
+        }
+        self.register_component_hooks::<R>()
+            .on_insert(on_insert_relation::<R>)
+            .on_replace(on_replace_relation::<R>);
+        self.register_component_hooks::<R::Sources>()
+            .on_remove(on_remove_sources::<R>);
+        let relation = self.init_component::<R>();
+        let sources = self.init_component::<R::Sources>();
+        self.relations.relations.push(RelationInfo {
+            relation,
+            sources,
+            target: relation_target::<R>,
+            relink: relink_source::<R>,
+        });
+    }
+
+    /// Returns the relation kinds registered with this world.
+    #[inline]
+    pub fn relations(&self) -> &Relations {
+        &self.relations
+    }
+
+    /// Inserts `relation` on `source`, linking it to [`Relation::target`].
+    ///
+    /// `source` is appended to the target's [`Relation::Sources`]. If `source` was previously
+    /// related to a different target, it is removed from the sources of that target, whose
+    /// [`Relation::Sources`] component is removed if it ends up empty.
+    ///
+    /// Returns the previous target of `source`, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or the target do not exist, or if they are the same entity.
+    pub fn insert_relation<R: Relation>(&mut self, source: Entity, relation: R) -> Option<Entity> {
+        self.init_relation::<R>();
+        let target = relation.target();
+        assert_ne!(
+            source,
+            target,
+            "Entity {source:?} cannot be related to itself by `{}`.",
+            std::any::type_name::<R>()
+        );
+        assert!(
+            self.get_entity(target).is_some(),
+            "Could not relate {source:?} to {target:?} by `{}` because the target doesn't exist in this World.",
+            std::any::type_name::<R>()
+        );
+
+        let mut source_mut = self.entity_mut(source);
+        let previous = source_mut.get::<R>().map(R::target);
+        if previous == Some(target) {
+            // Keep the position of `source` in the sources of its target.
+            *source_mut.get_mut::<R>().unwrap() = relation;
+        } else {
+            source_mut.insert(relation);
+        }
+        previous
+    }
+
+    /// Removes the relation `R` from `source`, and removes `source` from the sources of its
+    /// target. The target's [`Relation::Sources`] component is removed if it ends up empty.
+    ///
+    /// Returns the removed relation, if `source` exists and had one.
+    pub fn remove_relation<R: Relation>(&mut self, source: Entity) -> Option<R> {
+        self.get_entity_mut(source)?.take::<R>()
+    }
+
+    /// Runs `f`, which may change the targets of the registered relations of `entities` in place,
+    /// and then moves each of `entities` from the sources of its previous targets to the sources
+    /// of its new ones.
+    ///
+    /// Changing a relation component through a mutable reference bypasses the hooks that keep
+    /// the sources of its target in sync. This is what happens when entities are mapped, for
+    /// example after spawning a scene or cloning entities, so such mappings should run in `f`.
+    pub fn remap_relations(&mut self, entities: &[Entity], f: impl FnOnce(&mut World)) {
+        if self.relations.is_empty() {
+            f(self);
+            return;
+        }
+        let relinks: Vec<_> = self
+            .relations
+            .iter()
+            .flat_map(|info| {
+                entities
+                    .iter()
+                    .map(|&entity| (info.relink, entity, (info.target)(self, entity)))
+            })
+            .collect();
+        f(self);
+        for (relink, entity, previous) in relinks {
+            if self.get_entity(entity).is_some() {
+                relink(self, entity, previous);
+            }
+        }
+    }
+}
+
+/// Adds `source` to the end of the [`Relation::Sources`] of `target`, unless it is already one of
+/// them, or `target` does not exist or is `source` itself.
+fn add_source<R: Relation>(world: &mut World, target: Entity, source: Entity) {
+    if target == source {
+        return;
+    }
+    let Some(mut target) = world.get_entity_mut(target) else {
+        return;
+    };
+    if let Some(mut sources) = target.get_mut::<R::Sources>() {
+        if !sources.sources().contains(&source) {
+            sources.add_source(source);
+        }
+    } else {
+        target.insert(R::Sources::from_source(source));
+    }
+}
+
+/// Removes `source` from the [`Relation::Sources`] of `target`, removing the component if it
+/// ends up empty.
+fn remove_source<R: Relation>(world: &mut World, target: Entity, source: Entity) {
+    let Some(mut target) = world.get_entity_mut(target) else {
+        return;
+    };
+    let Some(mut sources) = target.get_mut::<R::Sources>() else {
+        return;
+    };
+    if !sources.sources().contains(&source) {
+        return;
+    }
+    sources.remove_source(source);
+    if sources.sources().is_empty() {
+        target.remove::<R::Sources>();
+    }
+}
+
+impl<'w> EntityMut<'w> {
+    /// Inserts `relation` on this entity, linking it to [`Relation::target`].
+    ///
+    /// See [`World::insert_relation`] for more details.
+    pub fn insert_relation<R: Relation>(&mut self, relation: R) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| {
+            world.insert_relation(source, relation);
+        });
+        self
+    }
+
+    /// Removes the relation `R` from this entity, if it has one.
+    ///
+    /// See [`World::remove_relation`] for more details.
+    pub fn remove_relation<R: Relation>(&mut self) -> Option<R> {
+        let source = self.id();
+        self.world_scope(|world| world.remove_relation::<R>(source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Relation, RelationCleanup, Sources};
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct Likes(Entity);
+
+    impl Relation for Likes {
+        type Sources = Sources<Likes>;
+
+        fn target(&self) -> Entity {
+            self.0
+        }
+    }
+
+    #[derive(Component)]
+    struct OwnedBy(Entity);
+
+    impl Relation for OwnedBy {
+        type Sources = Sources<OwnedBy>;
+        const CLEANUP: RelationCleanup = RelationCleanup::DespawnSources;
+
+        fn target(&self) -> Entity {
+            self.0
+        }
+    }
+
+    fn sources<R: Relation<Sources = Sources<R>>>(world: &World, target: Entity) -> Vec<Entity> {
+        world
+            .get::<Sources<R>>(target)
+            .map(|sources| sources.sources().to_vec())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn insert_and_move_relation() {
+        let mut world = World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        assert_eq!(world.insert_relation(a, Likes(b)), None);
+        assert_eq!(world.insert_relation(c, Likes(b)), None);
+        assert_eq!(sources::<Likes>(&world, b), vec![a, c]);
+
+        // Moving `a` to a new target updates both old and new targets.
+        assert_eq!(world.insert_relation(a, Likes(c)), Some(b));
+        assert_eq!(sources::<Likes>(&world, b), vec![c]);
+        assert_eq!(sources::<Likes>(&world, c), vec![a]);
+
+        assert_eq!(world.remove_relation::<Likes>(c).map(|l| l.0), Some(b));
+        assert!(world.get::<Sources<Likes>>(b).is_none());
+        assert!(world.get::<Likes>(c).is_none());
+    }
+
+    #[test]
+    fn query_both_sides() {
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        let source = world.spawn_empty().insert_relation(Likes(target)).id();
+
+        let mut sources = world.query::<(Entity, &Sources<Likes>)>();
+        let (entity, likers) = sources.single(&world);
+        assert_eq!(entity, target);
+        assert_eq!(likers.sources(), &[source]);
+
+        let mut targets = world.query::<&Likes>();
+        assert_eq!(targets.single(&world).target(), target);
+    }
+
+    #[test]
+    fn removing_components_keeps_sides_in_sync() {
+        let mut world = World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.insert_relation(a, Likes(c));
+        world.insert_relation(b, Likes(c));
+
+        world.entity_mut(a).remove::<Likes>();
+        assert_eq!(sources::<Likes>(&world, c), vec![b]);
+
+        world.entity_mut(c).remove::<Sources<Likes>>();
+        assert!(world.get::<Likes>(b).is_none());
+    }
+
+    #[test]
+    fn despawn_removes_edges() {
+        let mut world = World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.insert_relation(a, Likes(b));
+        world.insert_relation(b, Likes(c));
+
+        // Despawning a source removes it from its target.
+        world.despawn(a);
+        assert!(world.get::<Sources<Likes>>(b).is_none());
+
+        // Despawning a target removes the edge from its sources.
+        world.despawn(c);
+        assert!(world.get_entity(b).is_some());
+        assert!(world.get::<Likes>(b).is_none());
+    }
+
+    #[test]
+    fn despawn_sources_recursively() {
+        let mut world = World::new();
+        let [root, a, b, other] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.insert_relation(a, OwnedBy(root));
+        world.insert_relation(b, OwnedBy(a));
+        world.insert_relation(other, Likes(b));
+
+        world.despawn(root);
+        assert!(world.get_entity(a).is_none());
+        assert!(world.get_entity(b).is_none());
+        assert!(world.get::<Likes>(other).is_none());
+    }
+
+    #[test]
+    fn despawn_cycle_terminates() {
+        let mut world = World::new();
+        let [a, b] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.insert_relation(a, OwnedBy(b));
+        world.insert_relation(b, OwnedBy(a));
+
+        world.despawn(a);
+        assert!(world.get_entity(a).is_none());
+        assert!(world.get_entity(b).is_none());
+    }
+
+    #[test]
+    fn inserted_components_are_linked() {
+        let mut world = World::new();
+        world.init_relation::<Likes>();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world.entity_mut(a).insert(Likes(b));
+        world.spawn(Likes(b));
+        assert_eq!(sources::<Likes>(&world, b).len(), 2);
+
+        // Overwriting the relation moves the source to the new target.
+        world.entity_mut(a).insert(Likes(c));
+        assert_eq!(sources::<Likes>(&world, b).len(), 1);
+        assert_eq!(sources::<Likes>(&world, c), vec![a]);
+
+        // Relations to missing entities or to their own entity are left unlinked.
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+        world.entity_mut(a).insert(Likes(missing));
+        world.entity_mut(b).insert(Likes(b));
+        assert!(world.get::<Sources<Likes>>(c).is_none());
+        assert_eq!(sources::<Likes>(&world, b).len(), 1);
+    }
+
+    #[test]
+    fn remap_relations() {
+        let mut world = World::new();
+        world.init_relation::<Likes>();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.insert_relation(a, Likes(b));
+
+        world.remap_relations(&[a], |world| world.get_mut::<Likes>(a).unwrap().0 = c);
+        assert!(world.get::<Sources<Likes>>(b).is_none());
+        assert_eq!(sources::<Likes>(&world, c), vec![a]);
+    }
+
+    #[test]
+    fn relation_hooks_compose_with_other_hooks() {
+        #[derive(Resource, Default)]
+        struct Removed(usize);
+
+        let mut world = World::new();
+        world.init_resource::<Removed>();
+        world
+            .register_component_hooks::<Likes>()
+            .on_remove(|world, _, _| world.resource_mut::<Removed>().0 += 1);
+        world.init_relation::<Likes>();
+        let [a, b] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.insert_relation(a, Likes(b));
+
+        world.entity_mut(a).remove::<Likes>();
+        assert!(world.get::<Sources<Likes>>(b).is_none());
+        assert_eq!(world.resource::<Removed>().0, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn self_relation_panics() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        world.insert_relation(a, Likes(a));
+    }
+}
@@ -4,7 +4,7 @@ use std::ops::Not;
 
 use crate::component::{self, ComponentId};
 use crate::query::Access;
-use crate::system::{CombinatorSystem, Combine, IntoSystem, ReadOnlySystem, System};
+use crate::system::{CombinatorSystem, Combine, ErrorHandler, IntoSystem, ReadOnlySystem, System};
 use crate::world::unsafe_world_cell::UnsafeWorldCell;
 use crate::world::World;
 
@@ -189,7 +189,7 @@ pub mod common_conditions {
         event::{Event, EventReader},
         prelude::{Component, Query, With},
         removal_detection::RemovedComponents,
-        schedule::{State, States},
+        schedule::{State, StateStack, States},
         system::{IntoSystem, Res, Resource, System},
     };
 
@@ -688,11 +688,14 @@ pub mod common_conditions {
     }
 
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
-    /// if the state machine is currently in `state`.
+    /// if the state machine is currently in `state`, or if `state` is paused in its
+    /// [`StateStack`] when that resource exists.
     ///
-    /// # Panics
+    /// A state paused by [`StateStack::push`] is still considered active, so systems keep running
+    /// while another state is pushed on top of theirs, like a menu opened over a game.
     ///
-    /// The condition will panic if the resource does not exist.
+    /// The condition will return `false` if the state does not exist, like a
+    /// [`ComputedStates`](crate::schedule::ComputedStates) whose sources don't match.
     ///
     /// # Example
     ///
@@ -737,8 +740,13 @@ pub mod common_conditions {
     /// app.run(&mut world);
     /// assert_eq!(world.resource::<Counter>().0, 0);
     /// ```
-    pub fn in_state<S: States>(state: S) -> impl FnMut(Res<State<S>>) -> bool + Clone {
-        move |current_state: Res<State<S>>| *current_state == state
+    pub fn in_state<S: States>(
+        state: S,
+    ) -> impl FnMut(Option<Res<State<S>>>, Option<Res<StateStack<S>>>) -> bool + Clone {
+        move |current_state: Option<Res<State<S>>>, stack: Option<Res<StateStack<S>>>| {
+            current_state.is_some_and(|current_state| *current_state == state)
+                || stack.is_some_and(|stack| stack.paused().contains(&state))
+        }
     }
 
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
@@ -1036,6 +1044,10 @@ where
         self.condition.is_exclusive()
     }
 
+    fn has_deferred(&self) -> bool {
+        self.condition.has_deferred()
+    }
+
     unsafe fn run_unsafe(&mut self, input: Self::In, world: UnsafeWorldCell) -> Self::Out {
         // SAFETY: The inner condition system asserts its own safety.
         !self.condition.run_unsafe(input, world)
@@ -1068,6 +1080,10 @@ where
     fn set_last_run(&mut self, last_run: component::Tick) {
         self.condition.set_last_run(last_run);
     }
+
+    fn set_error_handler(&mut self, handler: &ErrorHandler) {
+        self.condition.set_error_handler(handler);
+    }
 }
 
 // SAFETY: This trait is only implemented when the inner system is read-only.
@@ -1133,7 +1149,9 @@ mod tests {
     use crate as bevy_ecs;
     use crate::component::Component;
     use crate::schedule::IntoSystemConfigs;
-    use crate::schedule::{common_conditions::not, State, States};
+    use crate::schedule::{
+        apply_state_transition, common_conditions::not, NextState, State, StateStack, States,
+    };
     use crate::system::Local;
     use crate::{change_detection::ResMut, schedule::Schedule, world::World};
     use bevy_ecs_macros::Event;
@@ -1249,6 +1267,50 @@ mod tests {
     fn test_system() {}
 
     // Ensure distributive_run_if compiles with the common conditions.
+    #[test]
+    fn in_state_without_state() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(increment_counter.run_if(in_state(TestState::A)));
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 0);
+
+        world.init_resource::<State<TestState>>();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn in_state_considers_paused_states() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.init_resource::<State<TestState>>();
+        world.init_resource::<NextState<TestState>>();
+        world.init_resource::<StateStack<TestState>>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(increment_counter.run_if(in_state(TestState::A)));
+
+        world
+            .resource_mut::<StateStack<TestState>>()
+            .push(TestState::B);
+        apply_state_transition::<TestState>(&mut world);
+        assert_eq!(world.resource::<State<TestState>>().get(), &TestState::B);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        // replacing the state doesn't keep it paused
+        world.resource_mut::<StateStack<TestState>>().pop();
+        apply_state_transition::<TestState>(&mut world);
+        world
+            .resource_mut::<NextState<TestState>>()
+            .set(TestState::B);
+        apply_state_transition::<TestState>(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
     #[test]
     fn distributive_run_if_compiles() {
         Schedule::default().add_systems(
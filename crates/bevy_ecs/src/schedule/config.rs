@@ -6,7 +6,7 @@ use crate::{
         graph_utils::{Ambiguity, Dependency, DependencyKind, GraphInfo},
         set::{BoxedSystemSet, IntoSystemSet, SystemSet},
     },
-    system::{BoxedSystem, IntoSystem, System},
+    system::{BoxedSystem, FallibleSystem, IntoSystem, System, SystemError},
 };
 
 fn new_condition<M>(condition: impl Condition<M>) -> BoxedCondition {
@@ -32,7 +32,10 @@ fn ambiguous_with(graph_info: &mut GraphInfo, set: BoxedSystemSet) {
     }
 }
 
-impl<Marker, F> IntoSystemConfigs<Marker> for F
+#[doc(hidden)]
+pub struct InfallibleSystemMarker;
+
+impl<Marker, F> IntoSystemConfigs<(InfallibleSystemMarker, Marker)> for F
 where
     F: IntoSystem<(), (), Marker>,
 {
@@ -41,6 +44,19 @@ where
     }
 }
 
+#[doc(hidden)]
+pub struct FallibleSystemMarker;
+
+impl<Marker, F, E> IntoSystemConfigs<(FallibleSystemMarker, E, Marker)> for F
+where
+    F: IntoSystem<(), Result<(), E>, Marker>,
+    E: Into<SystemError> + 'static,
+{
+    fn into_configs(self) -> SystemConfigs {
+        SystemConfigs::new_system(Box::new(FallibleSystem::new(IntoSystem::into_system(self))))
+    }
+}
+
 impl IntoSystemConfigs<()> for BoxedSystem<(), ()> {
     fn into_configs(self) -> SystemConfigs {
         SystemConfigs::new_system(self)
@@ -96,33 +112,17 @@ impl SystemConfigs {
         }
     }
 
-    fn before_inner(&mut self, set: BoxedSystemSet) {
-        match self {
-            SystemConfigs::SystemConfig(config) => {
-                config
-                    .graph_info
-                    .dependencies
-                    .push(Dependency::new(DependencyKind::Before, set));
-            }
-            SystemConfigs::Configs { configs, .. } => {
-                for config in configs {
-                    config.before_inner(set.dyn_clone());
-                }
-            }
-        }
-    }
-
-    fn after_inner(&mut self, set: BoxedSystemSet) {
+    fn dependency_inner(&mut self, kind: DependencyKind, set: BoxedSystemSet) {
         match self {
             SystemConfigs::SystemConfig(config) => {
                 config
                     .graph_info
                     .dependencies
-                    .push(Dependency::new(DependencyKind::After, set));
+                    .push(Dependency::new(kind, set));
             }
             SystemConfigs::Configs { configs, .. } => {
                 for config in configs {
-                    config.after_inner(set.dyn_clone());
+                    config.dependency_inner(kind, set.dyn_clone());
                 }
             }
         }
@@ -207,6 +207,23 @@ where
         self.into_configs().after(set)
     }
 
+    /// Run before all systems in `set`, without an automatic sync point in between.
+    ///
+    /// By default, an [`apply_deferred`](crate::schedule::apply_deferred) sync point is inserted
+    /// between systems with [`Deferred`](crate::system::Deferred) parameters, like
+    /// [`Commands`](crate::system::Commands), and the systems ordered after them. See
+    /// [`ScheduleBuildSettings::auto_insert_apply_deferred`](crate::schedule::ScheduleBuildSettings::auto_insert_apply_deferred).
+    fn before_ignore_deferred<M>(self, set: impl IntoSystemSet<M>) -> SystemConfigs {
+        self.into_configs().before_ignore_deferred(set)
+    }
+
+    /// Run after all systems in `set`, without an automatic sync point in between.
+    ///
+    /// See [`before_ignore_deferred`](Self::before_ignore_deferred).
+    fn after_ignore_deferred<M>(self, set: impl IntoSystemSet<M>) -> SystemConfigs {
+        self.into_configs().after_ignore_deferred(set)
+    }
+
     /// Add a run condition to each contained system.
     ///
     /// Each system will receive its own clone of the [`Condition`] and will only run
@@ -314,13 +331,25 @@ impl IntoSystemConfigs<()> for SystemConfigs {
 
     fn before<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
         let set = set.into_system_set();
-        self.before_inner(set.dyn_clone());
+        self.dependency_inner(DependencyKind::Before, set.dyn_clone());
         self
     }
 
     fn after<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
         let set = set.into_system_set();
-        self.after_inner(set.dyn_clone());
+        self.dependency_inner(DependencyKind::After, set.dyn_clone());
+        self
+    }
+
+    fn before_ignore_deferred<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        let set = set.into_system_set();
+        self.dependency_inner(DependencyKind::BeforeNoSync, set.dyn_clone());
+        self
+    }
+
+    fn after_ignore_deferred<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        let set = set.into_system_set();
+        self.dependency_inner(DependencyKind::AfterNoSync, set.dyn_clone());
         self
     }
 
@@ -424,6 +453,21 @@ pub trait IntoSystemSetConfig: Sized {
     fn after<M>(self, set: impl IntoSystemSet<M>) -> SystemSetConfig {
         self.into_config().after(set)
     }
+    /// Run before all systems in `set`, without an automatic sync point in between.
+    ///
+    /// By default, an [`apply_deferred`](crate::schedule::apply_deferred) sync point is inserted
+    /// between systems with [`Deferred`](crate::system::Deferred) parameters, like
+    /// [`Commands`](crate::system::Commands), and the systems ordered after them. See
+    /// [`ScheduleBuildSettings::auto_insert_apply_deferred`](crate::schedule::ScheduleBuildSettings::auto_insert_apply_deferred).
+    fn before_ignore_deferred<M>(self, set: impl IntoSystemSet<M>) -> SystemSetConfig {
+        self.into_config().before_ignore_deferred(set)
+    }
+    /// Run after all systems in `set`, without an automatic sync point in between.
+    ///
+    /// See [`before_ignore_deferred`](Self::before_ignore_deferred).
+    fn after_ignore_deferred<M>(self, set: impl IntoSystemSet<M>) -> SystemSetConfig {
+        self.into_config().after_ignore_deferred(set)
+    }
     /// Run the systems in this set only if the [`Condition`] is `true`.
     ///
     /// The `Condition` will be evaluated at most once (per schedule run),
@@ -486,6 +530,22 @@ impl IntoSystemSetConfig for SystemSetConfig {
         self
     }
 
+    fn before_ignore_deferred<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        self.graph_info.dependencies.push(Dependency::new(
+            DependencyKind::BeforeNoSync,
+            Box::new(set.into_system_set()),
+        ));
+        self
+    }
+
+    fn after_ignore_deferred<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        self.graph_info.dependencies.push(Dependency::new(
+            DependencyKind::AfterNoSync,
+            Box::new(set.into_system_set()),
+        ));
+        self
+    }
+
     fn run_if<M>(mut self, condition: impl Condition<M>) -> Self {
         self.conditions.push(new_condition(condition));
         self
@@ -534,6 +594,23 @@ where
         self.into_configs().after(set)
     }
 
+    /// Run before all systems in `set`, without an automatic sync point in between.
+    ///
+    /// By default, an [`apply_deferred`](crate::schedule::apply_deferred) sync point is inserted
+    /// between systems with [`Deferred`](crate::system::Deferred) parameters, like
+    /// [`Commands`](crate::system::Commands), and the systems ordered after them. See
+    /// [`ScheduleBuildSettings::auto_insert_apply_deferred`](crate::schedule::ScheduleBuildSettings::auto_insert_apply_deferred).
+    fn before_ignore_deferred<M>(self, set: impl IntoSystemSet<M>) -> SystemSetConfigs {
+        self.into_configs().before_ignore_deferred(set)
+    }
+
+    /// Run after all systems in `set`, without an automatic sync point in between.
+    ///
+    /// See [`before_ignore_deferred`](Self::before_ignore_deferred).
+    fn after_ignore_deferred<M>(self, set: impl IntoSystemSet<M>) -> SystemSetConfigs {
+        self.into_configs().after_ignore_deferred(set)
+    }
+
     /// Suppress warnings and errors that would result from systems in these sets having ambiguities
     /// (conflicting access but indeterminate order) with systems in `set`.
     fn ambiguous_with<M>(self, set: impl IntoSystemSet<M>) -> SystemSetConfigs {
@@ -596,6 +673,30 @@ impl IntoSystemSetConfigs for SystemSetConfigs {
         self
     }
 
+    fn before_ignore_deferred<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        let set = set.into_system_set();
+        for config in &mut self.sets {
+            config.graph_info.dependencies.push(Dependency::new(
+                DependencyKind::BeforeNoSync,
+                set.dyn_clone(),
+            ));
+        }
+
+        self
+    }
+
+    fn after_ignore_deferred<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        let set = set.into_system_set();
+        for config in &mut self.sets {
+            config.graph_info.dependencies.push(Dependency::new(
+                DependencyKind::AfterNoSync,
+                set.dyn_clone(),
+            ));
+        }
+
+        self
+    }
+
     fn ambiguous_with<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
         let set = set.into_system_set();
         for config in &mut self.sets {
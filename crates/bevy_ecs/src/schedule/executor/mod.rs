@@ -1,10 +1,12 @@
 mod multi_threaded;
 mod simple;
 mod single_threaded;
+mod stats;
 
 pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
+pub use self::stats::{SystemRunStats, SystemStats};
 
 use fixedbitset::FixedBitSet;
 
@@ -18,7 +20,18 @@ use crate::{
 pub(super) trait SystemExecutor: Send + Sync {
     fn kind(&self) -> ExecutorKind;
     fn init(&mut self, schedule: &SystemSchedule);
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World);
+    /// Runs the systems of `schedule`, except those in `skip_systems`.
+    ///
+    /// `skip_systems` is set by [`Stepping`](super::Stepping), and is `None` for schedules that
+    /// are not stepped through.
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    );
+    /// Returns the systems that were skipped by run conditions during the last run.
+    fn skipped_systems(&self) -> &FixedBitSet;
     fn set_apply_final_deferred(&mut self, value: bool);
 }
 
@@ -4,15 +4,16 @@ use std::{
 };
 
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
-use bevy_utils::default;
 use bevy_utils::syncunsafecell::SyncUnsafeCell;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Instrument};
+use bevy_utils::{default, Duration, Instant};
 use std::panic::AssertUnwindSafe;
 
 use async_channel::{Receiver, Sender};
 use fixedbitset::FixedBitSet;
 
+use super::stats::{ExclusiveTimer, StatsRecorder};
 use crate::{
     archetype::ArchetypeComponentId,
     prelude::Resource,
@@ -68,6 +69,8 @@ struct SystemTaskMetadata {
 struct SystemResult {
     system_index: usize,
     success: bool,
+    /// The time the system ran for, if statistics are recorded.
+    time: Option<Duration>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -100,7 +103,7 @@ pub struct MultiThreadedExecutor {
     ready_systems_copy: FixedBitSet,
     /// Systems that are running.
     running_systems: FixedBitSet,
-    /// Systems that got skipped.
+    /// Systems that got skipped by run conditions.
     skipped_systems: FixedBitSet,
     /// Systems whose conditions have been evaluated and were run or skipped.
     completed_systems: FixedBitSet,
@@ -112,6 +115,8 @@ pub struct MultiThreadedExecutor {
     panic_payload: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
     /// When set, stops the executor from running any more systems.
     stop_spawning: bool,
+    /// Records per-system statistics when [`SystemStats`](crate::schedule::SystemStats) exists.
+    stats: StatsRecorder,
 }
 
 impl Default for MultiThreadedExecutor {
@@ -159,24 +164,44 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.num_dependencies_remaining = Vec::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    ) {
         // reset counts
+        self.skipped_systems.clear();
         self.num_systems = schedule.systems.len();
         if self.num_systems == 0 {
             return;
         }
+        self.stats.begin(world, self.num_systems);
         self.num_running_systems = 0;
         self.num_completed_systems = 0;
         self.num_dependencies_remaining.clear();
         self.num_dependencies_remaining
             .extend_from_slice(&schedule.system_dependencies);
 
-        for (system_index, dependencies) in self.num_dependencies_remaining.iter_mut().enumerate() {
-            if *dependencies == 0 {
+        if let Some(skip_systems) = skip_systems {
+            // the skipped systems are treated as completed, without running or being reported as
+            // skipped by their run conditions
+            self.completed_systems.union_with(skip_systems);
+            self.num_completed_systems = self.completed_systems.count_ones(..);
+        }
+
+        for (system_index, dependencies) in self.num_dependencies_remaining.iter().enumerate() {
+            if *dependencies == 0 && !self.completed_systems.contains(system_index) {
                 self.ready_systems.insert(system_index);
             }
         }
 
+        if let Some(skip_systems) = skip_systems {
+            for system_index in skip_systems.ones() {
+                self.signal_dependents(system_index);
+            }
+        }
+
         let thread_executor = world
             .get_resource::<MainThreadExecutor>()
             .map(|e| e.0.clone());
@@ -240,6 +265,8 @@ impl SystemExecutor for MultiThreadedExecutor {
             debug_assert!(self.unapplied_systems.is_clear());
         }
 
+        self.stats.finish(schedule, world);
+
         // check to see if there was a panic
         let mut payload = self.panic_payload.lock().unwrap();
         if let Some(payload) = payload.take() {
@@ -250,9 +277,12 @@ impl SystemExecutor for MultiThreadedExecutor {
         debug_assert!(self.running_systems.is_clear());
         self.active_access.clear();
         self.evaluated_sets.clear();
-        self.skipped_systems.clear();
         self.completed_systems.clear();
     }
+
+    fn skipped_systems(&self) -> &FixedBitSet {
+        &self.skipped_systems
+    }
 }
 
 impl MultiThreadedExecutor {
@@ -282,6 +312,7 @@ impl MultiThreadedExecutor {
             apply_final_deferred: true,
             panic_payload: Arc::new(Mutex::new(None)),
             stop_spawning: false,
+            stats: StatsRecorder::new(),
         }
     }
 
@@ -494,9 +525,11 @@ impl MultiThreadedExecutor {
 
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let timed = self.stats.is_enabled();
         let task = async move {
             #[cfg(feature = "trace")]
             let system_guard = system_span.enter();
+            let start = timed.then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -504,6 +537,7 @@ impl MultiThreadedExecutor {
                 // - `update_archetype_component_access` has been called.
                 unsafe { system.run_unsafe((), world) };
             }));
+            let time = start.map(|start| start.elapsed());
             #[cfg(feature = "trace")]
             drop(system_guard);
             // tell the executor that the system finished
@@ -511,6 +545,7 @@ impl MultiThreadedExecutor {
                 .try_send(SystemResult {
                     system_index,
                     success: res.is_ok(),
+                    time,
                 })
                 .unwrap_or_else(|error| unreachable!("{}", error));
             if let Err(payload) = res {
@@ -557,6 +592,7 @@ impl MultiThreadedExecutor {
 
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let timed = self.stats.is_enabled();
         if is_apply_deferred(system) {
             // TODO: avoid allocation
             let unapplied_systems = self.unapplied_systems.clone();
@@ -564,7 +600,9 @@ impl MultiThreadedExecutor {
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
+                let timer = timed.then(|| ExclusiveTimer::start(world));
                 let res = apply_deferred(&unapplied_systems, systems, world);
+                let time = timer.map(|timer| timer.stop(world));
                 #[cfg(feature = "trace")]
                 drop(system_guard);
                 // tell the executor that the system finished
@@ -572,6 +610,7 @@ impl MultiThreadedExecutor {
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        time,
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -588,9 +627,11 @@ impl MultiThreadedExecutor {
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
+                let timer = timed.then(|| ExclusiveTimer::start(world));
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     system.run((), world);
                 }));
+                let time = timer.map(|timer| timer.stop(world));
                 #[cfg(feature = "trace")]
                 drop(system_guard);
                 // tell the executor that the system finished
@@ -598,6 +639,7 @@ impl MultiThreadedExecutor {
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        time,
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -624,8 +666,13 @@ impl MultiThreadedExecutor {
         let SystemResult {
             system_index,
             success,
+            time,
         } = result;
 
+        if let Some(time) = time {
+            self.stats.ran(system_index, time);
+        }
+
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
         }
@@ -649,6 +696,7 @@ impl MultiThreadedExecutor {
     }
 
     fn skip_system_and_signal_dependents(&mut self, system_index: usize) {
+        self.stats.skipped(system_index);
         self.num_completed_systems += 1;
         self.completed_systems.insert(system_index);
         self.signal_dependents(system_index);
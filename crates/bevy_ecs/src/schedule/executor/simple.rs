@@ -3,6 +3,7 @@ use bevy_utils::tracing::info_span;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
+use super::stats::{ExclusiveTimer, StatsRecorder};
 use crate::{
     schedule::{BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
     world::World,
@@ -16,6 +17,10 @@ pub struct SimpleExecutor {
     evaluated_sets: FixedBitSet,
     /// Systems that have run or been skipped.
     completed_systems: FixedBitSet,
+    /// Systems that were skipped by run conditions.
+    skipped_systems: FixedBitSet,
+    /// Records per-system statistics when [`SystemStats`](crate::schedule::SystemStats) exists.
+    stats: StatsRecorder,
 }
 
 impl SystemExecutor for SimpleExecutor {
@@ -27,15 +32,31 @@ impl SystemExecutor for SimpleExecutor {
         // do nothing. simple executor does not do a final sync
     }
 
+    fn skipped_systems(&self) -> &FixedBitSet {
+        &self.skipped_systems
+    }
+
     fn init(&mut self, schedule: &SystemSchedule) {
         let sys_count = schedule.system_ids.len();
         let set_count = schedule.set_ids.len();
         self.evaluated_sets = FixedBitSet::with_capacity(set_count);
         self.completed_systems = FixedBitSet::with_capacity(sys_count);
+        self.skipped_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    ) {
+        self.stats.begin(world, schedule.systems.len());
+        self.skipped_systems.clear();
         for system_index in 0..schedule.systems.len() {
+            if skip_systems.is_some_and(|skip| skip.contains(system_index)) {
+                continue;
+            }
+
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
             #[cfg(feature = "trace")]
@@ -73,10 +94,16 @@ impl SystemExecutor for SimpleExecutor {
             self.completed_systems.insert(system_index);
 
             if !should_run {
+                self.skipped_systems.insert(system_index);
+                self.stats.skipped(system_index);
                 continue;
             }
 
             let system = &mut schedule.systems[system_index];
+            let timer = self
+                .stats
+                .is_enabled()
+                .then(|| ExclusiveTimer::start(world));
             #[cfg(feature = "trace")]
             let system_span = info_span!("system", name = &*name).entered();
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
@@ -90,8 +117,12 @@ impl SystemExecutor for SimpleExecutor {
             }
 
             system.apply_deferred(world);
+            if let Some(timer) = timer {
+                self.stats.ran(system_index, timer.stop(world));
+            }
         }
 
+        self.stats.finish(schedule, world);
         self.evaluated_sets.clear();
         self.completed_systems.clear();
     }
@@ -104,6 +135,8 @@ impl SimpleExecutor {
         Self {
             evaluated_sets: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
+            skipped_systems: FixedBitSet::new(),
+            stats: StatsRecorder::new(),
         }
     }
 }
@@ -3,6 +3,7 @@ use bevy_utils::tracing::info_span;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
+use super::stats::{ExclusiveTimer, StatsRecorder};
 use crate::{
     schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
     world::World,
@@ -18,6 +19,10 @@ pub struct SingleThreadedExecutor {
     evaluated_sets: FixedBitSet,
     /// Systems that have run or been skipped.
     completed_systems: FixedBitSet,
+    /// Systems that were skipped by run conditions.
+    skipped_systems: FixedBitSet,
+    /// Records per-system statistics when [`SystemStats`](crate::schedule::SystemStats) exists.
+    stats: StatsRecorder,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
     /// Setting when true applies deferred system buffers after all systems have run
@@ -33,17 +38,33 @@ impl SystemExecutor for SingleThreadedExecutor {
         self.apply_final_deferred = apply_final_deferred;
     }
 
+    fn skipped_systems(&self) -> &FixedBitSet {
+        &self.skipped_systems
+    }
+
     fn init(&mut self, schedule: &SystemSchedule) {
         // pre-allocate space
         let sys_count = schedule.system_ids.len();
         let set_count = schedule.set_ids.len();
         self.evaluated_sets = FixedBitSet::with_capacity(set_count);
         self.completed_systems = FixedBitSet::with_capacity(sys_count);
+        self.skipped_systems = FixedBitSet::with_capacity(sys_count);
         self.unapplied_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    ) {
+        self.stats.begin(world, schedule.systems.len());
+        self.skipped_systems.clear();
         for system_index in 0..schedule.systems.len() {
+            if skip_systems.is_some_and(|skip| skip.contains(system_index)) {
+                continue;
+            }
+
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
             #[cfg(feature = "trace")]
@@ -81,9 +102,15 @@ impl SystemExecutor for SingleThreadedExecutor {
             self.completed_systems.insert(system_index);
 
             if !should_run {
+                self.skipped_systems.insert(system_index);
+                self.stats.skipped(system_index);
                 continue;
             }
 
+            let timer = self
+                .stats
+                .is_enabled()
+                .then(|| ExclusiveTimer::start(world));
             let system = &mut schedule.systems[system_index];
             if is_apply_deferred(system) {
                 #[cfg(feature = "trace")]
@@ -105,11 +132,15 @@ impl SystemExecutor for SingleThreadedExecutor {
                 }
                 self.unapplied_systems.insert(system_index);
             }
+            if let Some(timer) = timer {
+                self.stats.ran(system_index, timer.stop(world));
+            }
         }
 
         if self.apply_final_deferred {
             self.apply_deferred(schedule, world);
         }
+        self.stats.finish(schedule, world);
         self.evaluated_sets.clear();
         self.completed_systems.clear();
     }
@@ -123,6 +154,8 @@ impl SingleThreadedExecutor {
         Self {
             evaluated_sets: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
+            skipped_systems: FixedBitSet::new(),
+            stats: StatsRecorder::new(),
             unapplied_systems: FixedBitSet::new(),
             apply_final_deferred: true,
         }
@@ -0,0 +1,244 @@
+use std::borrow::Cow;
+
+use bevy_utils::{Duration, HashMap, Instant};
+
+use crate::{self as bevy_ecs, schedule::SystemSchedule, system::Resource, world::World};
+
+/// Per-system timings and run counts, recorded by the executors of every [`Schedule`] while this
+/// resource exists in the [`World`].
+///
+/// Recording is opt-in: insert this resource to start it, and remove it to stop. Systems are
+/// identified by their [name](crate::system::System::name), so systems with the same name in
+/// different schedules share their statistics.
+///
+/// The time of a system that runs other schedules, like [`World::run_schedule`] in an exclusive
+/// system, excludes the time of the systems of those schedules, which are recorded separately.
+///
+/// [`Schedule`]: crate::schedule::Schedule
+#[derive(Resource, Default, Debug)]
+pub struct SystemStats {
+    systems: HashMap<Cow<'static, str>, SystemRunStats>,
+    /// The total time ever recorded, which is never cleared.
+    recorded: Duration,
+}
+
+/// The statistics of a single system, see [`SystemStats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemRunStats {
+    /// The number of times the system ran.
+    pub runs: u64,
+    /// The number of times the system was skipped because of its run conditions, or the run
+    /// conditions of one of its sets.
+    pub skips: u64,
+    /// The total wall time the system ran for.
+    pub total_time: Duration,
+    /// The longest wall time of a single run of the system.
+    pub max_time: Duration,
+}
+
+impl SystemRunStats {
+    /// Returns the average wall time of a run of the system.
+    pub fn mean_time(&self) -> Duration {
+        if self.runs == 0 {
+            Duration::ZERO
+        } else {
+            self.total_time.div_f64(self.runs as f64)
+        }
+    }
+}
+
+impl SystemStats {
+    /// Returns the statistics of the system with the given name, if it has run or been skipped.
+    pub fn get(&self, name: &str) -> Option<&SystemRunStats> {
+        self.systems.get(name)
+    }
+
+    /// Returns an iterator over the names and statistics of the systems, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SystemRunStats)> {
+        self.systems.iter().map(|(name, stats)| (&**name, stats))
+    }
+
+    /// Returns the `count` systems with the longest total time, slowest first.
+    pub fn slowest(&self, count: usize) -> Vec<(&str, &SystemRunStats)> {
+        let mut systems: Vec<_> = self.iter().collect();
+        systems.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.total_time));
+        systems.truncate(count);
+        systems
+    }
+
+    /// Forgets the statistics of all systems, to start measuring a new interval.
+    pub fn clear(&mut self) {
+        self.systems.clear();
+    }
+}
+
+/// What happened to a system during a single run of a schedule.
+#[derive(Clone, Copy)]
+enum SystemRecord {
+    NotRun,
+    Ran(Duration),
+    Skipped,
+}
+
+/// Records what happened to each system during a run of an executor, to add it to
+/// [`SystemStats`] once the run is complete.
+#[derive(Default)]
+pub(super) struct StatsRecorder {
+    enabled: bool,
+    records: Vec<SystemRecord>,
+}
+
+impl StatsRecorder {
+    pub(super) const fn new() -> Self {
+        Self {
+            enabled: false,
+            records: Vec::new(),
+        }
+    }
+
+    /// Starts recording a run of `system_count` systems, if [`SystemStats`] exists.
+    pub(super) fn begin(&mut self, world: &World, system_count: usize) {
+        self.enabled = world.contains_resource::<SystemStats>();
+        self.records.clear();
+        if self.enabled {
+            self.records.resize(system_count, SystemRecord::NotRun);
+        }
+    }
+
+    pub(super) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(super) fn ran(&mut self, system_index: usize, time: Duration) {
+        if self.enabled {
+            self.records[system_index] = SystemRecord::Ran(time);
+        }
+    }
+
+    pub(super) fn skipped(&mut self, system_index: usize) {
+        if self.enabled {
+            self.records[system_index] = SystemRecord::Skipped;
+        }
+    }
+
+    /// Adds the records of the run to [`SystemStats`].
+    pub(super) fn finish(&mut self, schedule: &SystemSchedule, world: &mut World) {
+        if !self.enabled {
+            return;
+        }
+        self.enabled = false;
+        let Some(mut stats) = world.get_resource_mut::<SystemStats>() else {
+            return;
+        };
+        for (system, record) in schedule.systems.iter().zip(self.records.drain(..)) {
+            let time = match record {
+                SystemRecord::NotRun => continue,
+                SystemRecord::Ran(time) => time,
+                SystemRecord::Skipped => {
+                    stats.systems.entry(system.name()).or_default().skips += 1;
+                    continue;
+                }
+            };
+            stats.recorded += time;
+            let entry = stats.systems.entry(system.name()).or_default();
+            entry.runs += 1;
+            entry.total_time += time;
+            entry.max_time = entry.max_time.max(time);
+        }
+    }
+}
+
+/// Measures the time of a system with exclusive access to the [`World`], excluding the time of
+/// the systems of any schedule it runs.
+pub(super) struct ExclusiveTimer {
+    start: Instant,
+    recorded: Duration,
+}
+
+impl ExclusiveTimer {
+    pub(super) fn start(world: &World) -> Self {
+        Self {
+            start: Instant::now(),
+            recorded: Self::recorded(world),
+        }
+    }
+
+    pub(super) fn stop(self, world: &World) -> Duration {
+        let elapsed = self.start.elapsed();
+        let nested = Self::recorded(world).saturating_sub(self.recorded);
+        elapsed.saturating_sub(nested)
+    }
+
+    fn recorded(world: &World) -> Duration {
+        world
+            .get_resource::<SystemStats>()
+            .map_or(Duration::ZERO, |stats| stats.recorded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SystemStats;
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        schedule::{ExecutorKind, ScheduleLabel},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Inner;
+
+    fn sleepy() {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    fn skipped() {}
+
+    fn run_inner(world: &mut World) {
+        world.run_schedule(Inner);
+    }
+
+    fn record_stats(kind: ExecutorKind) {
+        let mut world = World::new();
+        world.init_resource::<SystemStats>();
+        let mut inner = Schedule::new();
+        inner.set_executor_kind(kind).add_systems(sleepy);
+        world.add_schedule(inner, Inner);
+
+        let mut schedule = Schedule::new();
+        schedule
+            .set_executor_kind(kind)
+            .add_systems((run_inner, skipped.run_if(|| false)));
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let stats = world.resource::<SystemStats>();
+        let sleepy = stats.get(&IntoSystem::into_system(sleepy).name()).unwrap();
+        assert_eq!(sleepy.runs, 2);
+        assert!(sleepy.mean_time() >= std::time::Duration::from_millis(20));
+        let skipped = stats.get(&IntoSystem::into_system(skipped).name()).unwrap();
+        assert_eq!((skipped.runs, skipped.skips), (0, 2));
+        let run_inner = stats
+            .get(&IntoSystem::into_system(run_inner).name())
+            .unwrap();
+        assert_eq!(run_inner.runs, 2);
+        // the time of `sleepy` is not counted in `run_inner`
+        assert!(run_inner.max_time < std::time::Duration::from_millis(20));
+        assert_eq!(stats.slowest(1)[0].1, sleepy);
+    }
+
+    #[test]
+    fn single_threaded_stats() {
+        record_stats(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn simple_stats() {
+        record_stats(ExecutorKind::Simple);
+    }
+
+    #[test]
+    fn multi_threaded_stats() {
+        record_stats(ExecutorKind::MultiThreaded);
+    }
+}
@@ -0,0 +1,470 @@
+use std::fmt::Write;
+
+use bevy_utils::{get_short_name, petgraph::Direction, HashMap, HashSet};
+
+use crate::{
+    component::Components,
+    schedule::{BoxedCondition, Dag, NodeId, Schedule},
+    system::System,
+};
+
+/// A snapshot of the graph of a [`Schedule`], which can be rendered as
+/// [Graphviz DOT](https://graphviz.org/doc/info/lang.html) or as JSON.
+///
+/// Created by [`Schedule::export_graph`]. Nodes and edges are sorted by id, so the output of the
+/// same schedule is stable and can be checked in and diffed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleGraphExport {
+    /// The systems and system sets of the schedule.
+    pub nodes: Vec<ExportedNode>,
+    /// The edges from system sets to their members.
+    pub hierarchy: Vec<ExportedEdge>,
+    /// The edges from systems or sets to the systems or sets that run after them, including the
+    /// edges to and from automatically inserted sync points.
+    pub dependencies: Vec<ExportedEdge>,
+    /// The pairs of systems with conflicting access and no ordering between them.
+    ///
+    /// This is only filled once the schedule has been built.
+    pub ambiguities: Vec<ExportedAmbiguity>,
+}
+
+/// Whether an [`ExportedNode`] is a system or a system set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportedNodeKind {
+    /// A system.
+    System,
+    /// A system set.
+    Set,
+    /// An [`apply_deferred`](crate::schedule::apply_deferred) sync point, inserted automatically.
+    ///
+    /// See [`ScheduleBuildSettings::auto_insert_apply_deferred`](crate::schedule::ScheduleBuildSettings::auto_insert_apply_deferred).
+    SyncPoint,
+}
+
+/// A system or system set of a [`ScheduleGraphExport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedNode {
+    /// The id of the node, unique within its schedule, like `system_3` or `set_0`.
+    pub id: String,
+    /// Whether the node is a system or a system set.
+    pub kind: ExportedNodeKind,
+    /// The full type name of the system or set.
+    pub name: String,
+    /// The names of the run conditions of the system or set.
+    pub conditions: Vec<String>,
+}
+
+/// An edge between the ids of two [`ExportedNode`]s.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExportedEdge {
+    /// The id of the source node.
+    pub from: String,
+    /// The id of the target node.
+    pub to: String,
+}
+
+/// An ambiguity between two systems of a [`ScheduleGraphExport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedAmbiguity {
+    /// The id of the first system.
+    pub a: String,
+    /// The id of the second system.
+    pub b: String,
+    /// The names of the components both systems access, at least one of them mutably.
+    ///
+    /// If empty, the systems conflict on [`World`](crate::world::World) access.
+    pub conflicts: Vec<String>,
+}
+
+fn node_id(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system_{index}"),
+        NodeId::Set(index) => format!("set_{index}"),
+    }
+}
+
+fn condition_names(conditions: &[BoxedCondition]) -> Vec<String> {
+    conditions
+        .iter()
+        .map(|condition| condition.name().to_string())
+        .collect()
+}
+
+impl Schedule {
+    /// Exports the systems, system sets, edges and ambiguities of this schedule.
+    ///
+    /// Ambiguities are only detected when the schedule is built, so call
+    /// [`Schedule::initialize`] first to include them.
+    pub fn export_graph(&self, components: &Components) -> ScheduleGraphExport {
+        let graph = self.graph();
+
+        // built systems and their conditions are moved out of the graph, into the executable
+        let mut systems: HashMap<NodeId, (&dyn System<In = (), Out = ()>, &[BoxedCondition])> =
+            graph
+                .systems()
+                .map(|(id, system, conditions)| (id, (system, conditions)))
+                .collect();
+        for ((&id, system), conditions) in self
+            .executable
+            .system_ids
+            .iter()
+            .zip(self.executable.systems.iter())
+            .zip(self.executable.system_conditions.iter())
+        {
+            systems.insert(id, (&**system, conditions.as_slice()));
+        }
+        let mut set_conditions: HashMap<NodeId, &[BoxedCondition]> = self
+            .executable
+            .set_ids
+            .iter()
+            .copied()
+            .zip(self.executable.set_conditions.iter().map(Vec::as_slice))
+            .collect();
+
+        let sync_points: HashSet<NodeId> = graph.auto_sync_points().collect();
+        let mut nodes = Vec::new();
+        for (&id, &(system, conditions)) in &systems {
+            nodes.push((
+                id,
+                ExportedNode {
+                    id: node_id(id),
+                    kind: if sync_points.contains(&id) {
+                        ExportedNodeKind::SyncPoint
+                    } else {
+                        ExportedNodeKind::System
+                    },
+                    name: system.name().to_string(),
+                    conditions: condition_names(conditions),
+                },
+            ));
+        }
+        for (id, set, conditions) in graph.system_sets() {
+            let conditions = set_conditions.remove(&id).unwrap_or(conditions);
+            let name = if set.is_anonymous() {
+                let members: Vec<String> = graph
+                    .hierarchy()
+                    .graph()
+                    .neighbors_directed(id, Direction::Outgoing)
+                    .map(|member| match member {
+                        NodeId::System(_) => systems
+                            .get(&member)
+                            .map(|(system, _)| system.name().to_string())
+                            .unwrap_or_default(),
+                        NodeId::Set(_) => format!("{:?}", graph.set_at(member)),
+                    })
+                    .collect();
+                format!("({})", members.join(", "))
+            } else {
+                format!("{set:?}")
+            };
+            nodes.push((
+                id,
+                ExportedNode {
+                    id: node_id(id),
+                    kind: ExportedNodeKind::Set,
+                    name,
+                    conditions: condition_names(conditions),
+                },
+            ));
+        }
+        nodes.sort_by_key(|(id, _)| *id);
+
+        let edges = |dag: &Dag, sync_dag: Option<&Dag>| {
+            let mut edges: Vec<(NodeId, NodeId)> =
+                dag.graph().all_edges().map(|(a, b, _)| (a, b)).collect();
+            if let Some(sync_dag) = sync_dag {
+                edges.extend(
+                    sync_dag
+                        .graph()
+                        .all_edges()
+                        .filter(|(a, b, _)| sync_points.contains(a) || sync_points.contains(b))
+                        .map(|(a, b, _)| (a, b)),
+                );
+            }
+            edges.sort();
+            edges
+                .into_iter()
+                .map(|(a, b)| ExportedEdge {
+                    from: node_id(a),
+                    to: node_id(b),
+                })
+                .collect()
+        };
+
+        let ambiguities = graph
+            .conflicting_systems()
+            .iter()
+            .map(|(a, b, conflicts)| ExportedAmbiguity {
+                a: node_id(*a),
+                b: node_id(*b),
+                conflicts: conflicts
+                    .iter()
+                    .map(|&id| components.get_name(id).unwrap_or_default().to_string())
+                    .collect(),
+            })
+            .collect();
+
+        ScheduleGraphExport {
+            nodes: nodes.into_iter().map(|(_, node)| node).collect(),
+            hierarchy: edges(graph.hierarchy(), None),
+            dependencies: edges(graph.dependency(), Some(graph.dependency_flattened())),
+            ambiguities,
+        }
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl ScheduleGraphExport {
+    /// Renders the graph in the [Graphviz DOT](https://graphviz.org/doc/info/lang.html) format.
+    ///
+    /// Systems are drawn as boxes, sets as rounded, dashed boxes and automatic sync points as
+    /// octagons, labelled with their short names and run conditions. Hierarchy edges are dashed,
+    /// dependency edges are solid and ambiguities are red and undirected.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph schedule {\n");
+        dot.push_str("    rankdir=LR;\n    node [shape=box];\n");
+        for node in &self.nodes {
+            let mut label = escape(&get_short_name(&node.name));
+            for condition in &node.conditions {
+                let _ = write!(label, "\\nif {}", escape(&get_short_name(condition)));
+            }
+            let style = match node.kind {
+                ExportedNodeKind::System => "",
+                ExportedNodeKind::Set => ", style=\"rounded,dashed\"",
+                ExportedNodeKind::SyncPoint => ", shape=octagon",
+            };
+            let _ = writeln!(dot, "    {} [label=\"{label}\"{style}];", node.id);
+        }
+        for edge in &self.hierarchy {
+            let _ = writeln!(dot, "    {} -> {} [style=dashed];", edge.from, edge.to);
+        }
+        for edge in &self.dependencies {
+            let _ = writeln!(dot, "    {} -> {};", edge.from, edge.to);
+        }
+        for ambiguity in &self.ambiguities {
+            let label = ambiguity
+                .conflicts
+                .iter()
+                .map(|name| escape(&get_short_name(name)))
+                .collect::<Vec<_>>()
+                .join("\\n");
+            let _ = writeln!(
+                dot,
+                "    {} -> {} [dir=none, color=red, constraint=false, label=\"{label}\"];",
+                ambiguity.a, ambiguity.b
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Serializes the graph to JSON, with one node, edge or ambiguity per line.
+    ///
+    /// The JSON object has the same fields as [`ScheduleGraphExport`], with node kinds written as
+    /// `"system"`, `"set"` or `"sync_point"`.
+    pub fn to_json(&self) -> String {
+        let nodes = self.nodes.iter().map(|node| {
+            let kind = match node.kind {
+                ExportedNodeKind::System => "system",
+                ExportedNodeKind::Set => "set",
+                ExportedNodeKind::SyncPoint => "sync_point",
+            };
+            format!(
+                "{{\"id\": {}, \"kind\": {}, \"name\": {}, \"conditions\": {}}}",
+                json_string(&node.id),
+                json_string(kind),
+                json_string(&node.name),
+                json_strings(&node.conditions)
+            )
+        });
+        let edge = |edge: &ExportedEdge| {
+            format!(
+                "{{\"from\": {}, \"to\": {}}}",
+                json_string(&edge.from),
+                json_string(&edge.to)
+            )
+        };
+        let ambiguities = self.ambiguities.iter().map(|ambiguity| {
+            format!(
+                "{{\"a\": {}, \"b\": {}, \"conflicts\": {}}}",
+                json_string(&ambiguity.a),
+                json_string(&ambiguity.b),
+                json_strings(&ambiguity.conflicts)
+            )
+        });
+
+        let mut json = String::from("{\n");
+        write_json_array(&mut json, "nodes", nodes, false);
+        write_json_array(
+            &mut json,
+            "hierarchy",
+            self.hierarchy.iter().map(edge),
+            false,
+        );
+        write_json_array(
+            &mut json,
+            "dependencies",
+            self.dependencies.iter().map(edge),
+            false,
+        );
+        write_json_array(&mut json, "ambiguities", ambiguities, true);
+        json.push_str("}\n");
+        json
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_strings(values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|value| json_string(value)).collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn write_json_array(json: &mut String, key: &str, items: impl Iterator<Item = String>, last: bool) {
+    let items: Vec<String> = items.collect();
+    let _ = write!(json, "  \"{key}\": [");
+    if !items.is_empty() {
+        let _ = write!(json, "\n    {}\n  ", items.join(",\n    "));
+    }
+    json.push(']');
+    json.push_str(if last { "\n" } else { ",\n" });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExportedNode, ExportedNodeKind, ScheduleGraphExport};
+    use crate::{self as bevy_ecs, prelude::*};
+
+    #[derive(Resource)]
+    struct Counter(u32);
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Logic;
+
+    fn increment(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    fn double(mut counter: ResMut<Counter>) {
+        counter.0 *= 2;
+    }
+
+    fn print(_counter: Res<Counter>) {}
+
+    #[test]
+    fn export_built_schedule() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(Logic.run_if(|| true))
+            .add_systems((increment, double.before(print)).in_set(Logic))
+            .add_systems(print);
+        schedule.initialize(&mut world).unwrap();
+
+        let export = schedule.export_graph(world.components());
+        let systems: Vec<_> = export
+            .nodes
+            .iter()
+            .filter(|node| node.kind == ExportedNodeKind::System)
+            .collect();
+        assert_eq!(systems.len(), 3);
+        let logic = export
+            .nodes
+            .iter()
+            .find(|node| node.name == "Logic")
+            .unwrap();
+        assert_eq!(logic.conditions.len(), 1);
+        assert_eq!(
+            export
+                .hierarchy
+                .iter()
+                .filter(|edge| edge.from == logic.id)
+                .count(),
+            2
+        );
+        assert!(!export.dependencies.is_empty());
+        // `increment` accesses `Counter` without being ordered with `double` or `print`
+        assert_eq!(export.ambiguities.len(), 2);
+        assert!(export.ambiguities[0].conflicts[0].ends_with("Counter"));
+
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph schedule {"));
+        assert!(dot.contains("label=\"Logic\\nif "));
+        assert!(dot.contains("color=red"));
+
+        let json = export.to_json();
+        assert!(json.contains("\"kind\": \"set\""));
+        assert!(json.contains("\"name\": \"Logic\""));
+        assert!(json.contains("\"ambiguities\""));
+    }
+
+    #[test]
+    fn export_sync_points() {
+        fn spawn(mut commands: Commands) {
+            commands.spawn_empty();
+        }
+
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        schedule.add_systems((spawn, print).chain());
+        world.insert_resource(Counter(0));
+        schedule.initialize(&mut world).unwrap();
+
+        let export = schedule.export_graph(world.components());
+        let sync_point = export
+            .nodes
+            .iter()
+            .find(|node| node.kind == ExportedNodeKind::SyncPoint)
+            .unwrap();
+        assert!(export
+            .dependencies
+            .iter()
+            .any(|edge| edge.to == sync_point.id));
+        assert!(export
+            .dependencies
+            .iter()
+            .any(|edge| edge.from == sync_point.id));
+        assert!(export.to_json().contains("\"kind\": \"sync_point\""));
+    }
+
+    #[test]
+    fn export_json_escapes_names() {
+        let export = ScheduleGraphExport {
+            nodes: vec![ExportedNode {
+                id: "system_0".to_string(),
+                kind: ExportedNodeKind::System,
+                name: "quoted \"name\" with \\ and\nnewline".to_string(),
+                conditions: vec!["tab\tand\u{1}control".to_string()],
+            }],
+            hierarchy: Vec::new(),
+            dependencies: Vec::new(),
+            ambiguities: Vec::new(),
+        };
+
+        let json = export.to_json();
+        assert!(json.contains(r#""name": "quoted \"name\" with \\ and\nnewline""#));
+        assert!(json.contains(r#""conditions": ["tab\tand\u0001control"]"#));
+    }
+}
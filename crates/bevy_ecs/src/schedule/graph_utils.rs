@@ -45,6 +45,10 @@ pub(crate) enum DependencyKind {
     Before,
     /// A node that should be succeeded.
     After,
+    /// A node that should be preceded, without an automatic sync point in between.
+    BeforeNoSync,
+    /// A node that should be succeeded, without an automatic sync point in between.
+    AfterNoSync,
 }
 
 /// An edge to be added to the dependency graph.
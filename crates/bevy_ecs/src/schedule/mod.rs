@@ -3,19 +3,23 @@
 mod condition;
 mod config;
 mod executor;
+mod export;
 mod graph_utils;
 #[allow(clippy::module_inception)]
 mod schedule;
 mod set;
 mod state;
+mod stepping;
 
 pub use self::condition::*;
 pub use self::config::*;
 pub use self::executor::*;
+pub use self::export::*;
 use self::graph_utils::*;
 pub use self::schedule::*;
 pub use self::set::*;
 pub use self::state::*;
+pub use self::stepping::*;
 
 pub use self::graph_utils::NodeId;
 
@@ -177,6 +181,27 @@ mod tests {
             assert_eq!(world.resource::<SystemOrder>().0, vec![1, u32::MAX, 0]);
         }
 
+        #[test]
+        fn order_set_last() {
+            let mut world = World::new();
+            let mut schedule = Schedule::new();
+
+            world.init_resource::<SystemOrder>();
+
+            schedule.order_last(TestSet::X);
+            schedule.add_systems((
+                (make_function_system(2), make_exclusive_system(3))
+                    .chain()
+                    .in_set(TestSet::X),
+                make_function_system(0).in_set(TestSet::A),
+            ));
+            // systems added after the set was ordered last still run before it
+            schedule.add_systems(make_exclusive_system(1).after(TestSet::A));
+            schedule.run(&mut world);
+
+            assert_eq!(world.resource::<SystemOrder>().0, vec![0, 1, 2, 3]);
+        }
+
         #[test]
         fn add_systems_correct_order() {
             let mut world = World::new();
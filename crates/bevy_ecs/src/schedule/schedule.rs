@@ -19,7 +19,7 @@ use crate::{
     self as bevy_ecs,
     component::{ComponentId, Components, Tick},
     schedule::*,
-    system::{BoxedSystem, Resource, System},
+    system::{BoxedSystem, ErrorHandler, IntoSystem, Resource, System},
     world::World,
 };
 
@@ -41,8 +41,13 @@ impl Schedules {
     ///
     /// If the map already had an entry for `label`, `schedule` is inserted,
     /// and the old schedule is returned. Otherwise, `None` is returned.
-    pub fn insert(&mut self, label: impl ScheduleLabel, schedule: Schedule) -> Option<Schedule> {
+    pub fn insert(
+        &mut self,
+        label: impl ScheduleLabel,
+        mut schedule: Schedule,
+    ) -> Option<Schedule> {
         let label = label.dyn_clone();
+        schedule.label = Some(label.dyn_clone());
         self.inner.insert(label, schedule)
     }
 
@@ -151,10 +156,13 @@ fn make_executor(kind: ExecutorKind) -> Box<dyn SystemExecutor> {
 /// }
 /// ```
 pub struct Schedule {
+    label: Option<BoxedScheduleLabel>,
     graph: ScheduleGraph,
-    executable: SystemSchedule,
+    pub(super) executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
     executor_initialized: bool,
+    error_handler: Option<ErrorHandler>,
+    error_handler_applied: bool,
 }
 
 impl Default for Schedule {
@@ -167,13 +175,21 @@ impl Schedule {
     /// Constructs an empty `Schedule`.
     pub fn new() -> Self {
         Self {
+            label: None,
             graph: ScheduleGraph::new(),
             executable: SystemSchedule::new(),
             executor: make_executor(ExecutorKind::default()),
             executor_initialized: false,
+            error_handler: None,
+            error_handler_applied: true,
         }
     }
 
+    /// Returns the label of the schedule, if it has been added to [`Schedules`].
+    pub fn label(&self) -> Option<&dyn ScheduleLabel> {
+        self.label.as_deref()
+    }
+
     /// Add a collection of systems to the schedule.
     pub fn add_systems<M>(&mut self, systems: impl IntoSystemConfigs<M>) -> &mut Self {
         self.graph.add_systems_inner(systems.into_configs(), false);
@@ -192,6 +208,16 @@ impl Schedule {
         self
     }
 
+    /// Orders the systems in `set` after every other system in this schedule, including the
+    /// systems added later, adding the set if it does not exist.
+    ///
+    /// The systems of different sets ordered last are not ordered relative to each other.
+    /// Ordering a system after `set` creates a dependency cycle.
+    pub fn order_last(&mut self, set: impl SystemSet) -> &mut Self {
+        self.graph.order_last(set);
+        self
+    }
+
     /// Changes miscellaneous build settings.
     pub fn set_build_settings(&mut self, settings: ScheduleBuildSettings) -> &mut Self {
         self.graph.settings = settings;
@@ -212,6 +238,19 @@ impl Schedule {
         self
     }
 
+    /// Returns the [`ErrorHandler`] of the schedule's fallible systems, if it has its own.
+    pub fn error_handler(&self) -> Option<&ErrorHandler> {
+        self.error_handler.as_ref()
+    }
+
+    /// Sets the [`ErrorHandler`] of the schedule's fallible systems and run conditions,
+    /// overriding the world's [`DefaultErrorHandler`](crate::system::DefaultErrorHandler).
+    pub fn set_error_handler(&mut self, handler: ErrorHandler) -> &mut Self {
+        self.error_handler = Some(handler);
+        self.error_handler_applied = false;
+        self
+    }
+
     /// Set whether the schedule applies deferred system buffers on final time or not. This is a catch-all
     /// in case a system uses commands but was not explicitly ordered before an instance of
     /// [`apply_deferred`](crate::prelude::apply_deferred). By default this
@@ -222,10 +261,24 @@ impl Schedule {
     }
 
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
+    ///
+    /// If the schedule is stepped through by the [`Stepping`] resource, only the systems it
+    /// allows are run. Stepping only applies to schedules run through [`Schedules`], which
+    /// gives them the label they are stepped by.
     pub fn run(&mut self, world: &mut World) {
         world.check_change_ticks();
         self.initialize(world).unwrap_or_else(|e| panic!("{e}"));
-        self.executor.run(&mut self.executable, world);
+
+        let skip_systems = world
+            .get_resource_mut::<Stepping>()
+            .and_then(|mut stepping| stepping.skipped_systems(self));
+        self.executor
+            .run(&mut self.executable, skip_systems.as_ref(), world);
+        if skip_systems.is_some() {
+            if let Some(mut stepping) = world.get_resource_mut::<Stepping>() {
+                stepping.record_skipped_by_conditions(self, self.executor.skipped_systems());
+            }
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -239,6 +292,26 @@ impl Schedule {
                 .update_schedule(&mut self.executable, world.components())?;
             self.graph.changed = false;
             self.executor_initialized = false;
+            // newly added systems use the world's default handler
+            self.error_handler_applied = self.error_handler.is_none();
+        }
+
+        if !self.error_handler_applied {
+            if let Some(handler) = &self.error_handler {
+                let executable = &mut self.executable;
+                let conditions = executable
+                    .system_conditions
+                    .iter_mut()
+                    .chain(executable.set_conditions.iter_mut())
+                    .flatten();
+                for system in executable.systems.iter_mut() {
+                    system.set_error_handler(handler);
+                }
+                for condition in conditions {
+                    condition.set_error_handler(handler);
+                }
+            }
+            self.error_handler_applied = true;
         }
 
         if !self.executor_initialized {
@@ -386,7 +459,13 @@ pub struct ScheduleGraph {
     ambiguous_with: UnGraphMap<NodeId, ()>,
     ambiguous_with_flattened: UnGraphMap<NodeId, ()>,
     ambiguous_with_all: HashSet<NodeId>,
+    /// The sets whose systems run after every other system, see [`Schedule::order_last`].
+    last_sets: HashSet<NodeId>,
     conflicting_systems: Vec<(NodeId, NodeId, Vec<ComponentId>)>,
+    /// Dependency edges that opted out of automatic sync points.
+    no_sync_edges: HashSet<(NodeId, NodeId)>,
+    /// The automatically inserted sync points, by the number of sync points before them.
+    auto_sync_node_ids: HashMap<u32, NodeId>,
     changed: bool,
     settings: ScheduleBuildSettings,
 }
@@ -407,7 +486,10 @@ impl ScheduleGraph {
             ambiguous_with: UnGraphMap::new(),
             ambiguous_with_flattened: UnGraphMap::new(),
             ambiguous_with_all: HashSet::new(),
+            last_sets: HashSet::new(),
             conflicting_systems: Vec::new(),
+            no_sync_edges: HashSet::new(),
+            auto_sync_node_ids: HashMap::new(),
             changed: false,
             settings: default(),
         }
@@ -499,6 +581,35 @@ impl ScheduleGraph {
         &self.conflicting_systems
     }
 
+    /// Returns the [`apply_deferred`] systems that were automatically inserted when building the
+    /// schedule.
+    ///
+    /// See [`ScheduleBuildSettings::auto_insert_apply_deferred`].
+    pub fn auto_sync_points(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.auto_sync_node_ids.values().copied()
+    }
+
+    /// Returns the systems in `set`, directly or through nested sets.
+    pub(super) fn systems_in_set(&self, set: &dyn SystemSet) -> HashSet<NodeId> {
+        let mut systems = HashSet::new();
+        let Some(&set_id) = self.system_set_ids.get(set) else {
+            return systems;
+        };
+        let mut dfs = Dfs::new(&self.hierarchy.graph, set_id);
+        while let Some(id) = dfs.next(&self.hierarchy.graph) {
+            if id.is_system() {
+                systems.insert(id);
+            }
+        }
+        systems
+    }
+
+    /// Returns the [`Dag`] of the dependencies between systems, once sets have been flattened and
+    /// sync points inserted.
+    pub(super) fn dependency_flattened(&self) -> &Dag {
+        &self.dependency_flattened
+    }
+
     /// Adds the systems to the graph. Returns a vector of all node ids contained the nested `SystemConfigs`
     /// if `ancestor_chained` is true. Also returns true if "densely chained", meaning that all nested items
     /// are linearly chained in the order they are defined
@@ -682,6 +793,11 @@ impl ScheduleGraph {
         self.configure_set_inner(set).unwrap();
     }
 
+    fn order_last(&mut self, set: impl SystemSet) {
+        let id = self.configure_set_inner(set).unwrap();
+        self.last_sets.insert(id);
+    }
+
     fn configure_set_inner(
         &mut self,
         set: impl IntoSystemSetConfig,
@@ -807,10 +923,16 @@ impl ScheduleGraph {
             .map(|Dependency { kind, set }| (kind, self.system_set_ids[&set]))
         {
             let (lhs, rhs) = match kind {
-                DependencyKind::Before => (id, set),
-                DependencyKind::After => (set, id),
+                DependencyKind::Before | DependencyKind::BeforeNoSync => (id, set),
+                DependencyKind::After | DependencyKind::AfterNoSync => (set, id),
             };
             self.dependency.graph.add_edge(lhs, rhs, ());
+            if matches!(
+                kind,
+                DependencyKind::BeforeNoSync | DependencyKind::AfterNoSync
+            ) {
+                self.no_sync_edges.insert((lhs, rhs));
+            }
 
             // ensure set also appears in hierarchy graph
             self.hierarchy.graph.add_node(set);
@@ -980,18 +1102,29 @@ impl ScheduleGraph {
             if systems.is_empty() {
                 for a in dependency_flattened.neighbors_directed(set, Direction::Incoming) {
                     for b in dependency_flattened.neighbors_directed(set, Direction::Outgoing) {
+                        if self.no_sync_edges.contains(&(a, set))
+                            && self.no_sync_edges.contains(&(set, b))
+                        {
+                            self.no_sync_edges.insert((a, b));
+                        }
                         temp.push((a, b));
                     }
                 }
             } else {
                 for a in dependency_flattened.neighbors_directed(set, Direction::Incoming) {
                     for &sys in systems {
+                        if self.no_sync_edges.contains(&(a, set)) {
+                            self.no_sync_edges.insert((a, sys));
+                        }
                         temp.push((a, sys));
                     }
                 }
 
                 for b in dependency_flattened.neighbors_directed(set, Direction::Outgoing) {
                     for &sys in systems {
+                        if self.no_sync_edges.contains(&(set, b)) {
+                            self.no_sync_edges.insert((sys, b));
+                        }
                         temp.push((sys, b));
                     }
                 }
@@ -1003,6 +1136,30 @@ impl ScheduleGraph {
             }
         }
 
+        // order the systems of the sets ordered last after every other system
+        if !self.last_sets.is_empty() {
+            let last_systems: HashSet<NodeId> = self
+                .last_sets
+                .iter()
+                .flat_map(|set| &set_systems[set])
+                .copied()
+                .collect();
+            let other_systems: Vec<NodeId> = dependency_flattened
+                .nodes()
+                .filter(|node| !last_systems.contains(node))
+                .collect();
+            for &last in &last_systems {
+                for &other in &other_systems {
+                    dependency_flattened.add_edge(other, last, ());
+                }
+            }
+        }
+
+        // insert sync points after systems with deferred parameters
+        if self.settings.auto_insert_apply_deferred {
+            dependency_flattened = self.auto_insert_apply_deferred(&dependency_flattened)?;
+        }
+
         // topsort
         self.dependency_flattened.topsort = self
             .topsort_graph(&dependency_flattened, ReportCycles::Dependency)
@@ -1178,6 +1335,67 @@ impl ScheduleGraph {
         })
     }
 
+    /// Inserts [`apply_deferred`] sync points on the edges from systems with deferred parameters
+    /// to the systems ordered after them, unless the edge opted out of it.
+    ///
+    /// Sync points are shared by all the edges with the same number of sync points before them,
+    /// so that as few as possible are inserted.
+    fn auto_insert_apply_deferred(
+        &mut self,
+        dependency_flattened: &DiGraphMap<NodeId, ()>,
+    ) -> Result<DiGraphMap<NodeId, ()>, ScheduleBuildError> {
+        let topsort = self
+            .topsort_graph(dependency_flattened, ReportCycles::Dependency)
+            .map_err(|_| ScheduleBuildError::DependencyCycle)?;
+
+        let mut sync_point_graph = dependency_flattened.clone();
+        // the number of sync points that run before each system
+        let mut distances: HashMap<NodeId, u32> = HashMap::with_capacity(topsort.len());
+        for &node in &topsort {
+            let distance = distances.get(&node).copied().unwrap_or(0);
+            let add_sync_after = self.systems[node.index()].get().unwrap().has_deferred();
+
+            for target in dependency_flattened.neighbors_directed(node, Direction::Outgoing) {
+                let add_sync_on_edge = add_sync_after
+                    && !is_apply_deferred(self.systems[target.index()].get().unwrap())
+                    && !self.no_sync_edges.contains(&(node, target));
+
+                let target_distance = distances.entry(target).or_insert(0);
+                if add_sync_on_edge {
+                    *target_distance = (*target_distance).max(distance + 1);
+                    let sync_point = self.get_sync_point(distance + 1);
+                    sync_point_graph.add_edge(node, sync_point, ());
+                    sync_point_graph.add_edge(sync_point, target, ());
+                    // the edge is now implied by the sync point
+                    sync_point_graph.remove_edge(node, target);
+                } else {
+                    *target_distance = (*target_distance).max(distance);
+                }
+            }
+        }
+
+        Ok(sync_point_graph)
+    }
+
+    /// Returns the sync point with `distance` sync points before it, creating it if needed.
+    fn get_sync_point(&mut self, distance: u32) -> NodeId {
+        if let Some(&id) = self.auto_sync_node_ids.get(&distance) {
+            return id;
+        }
+
+        // executors never run `apply_deferred` itself, so the system does not need initializing
+        let id = NodeId::System(self.systems.len());
+        self.systems
+            .push(SystemNode::new(Box::new(IntoSystem::into_system(
+                apply_deferred,
+            ))));
+        self.system_conditions.push(Vec::new());
+        // sync points are not under the control of users, so ambiguities with them are ignored
+        self.ambiguous_with_all.insert(id);
+        self.auto_sync_node_ids.insert(distance, id);
+        id
+    }
+
     fn update_schedule(
         &mut self,
         schedule: &mut SystemSchedule,
@@ -1550,6 +1768,17 @@ pub struct ScheduleBuildSettings {
     ///
     /// Defaults to `true`.
     pub report_sets: bool,
+    /// If set to true, an [`apply_deferred`] sync point is automatically inserted between each
+    /// system with [`Deferred`](crate::system::Deferred) parameters, like
+    /// [`Commands`](crate::system::Commands), and the systems ordered after it, so that they see
+    /// its changes.
+    ///
+    /// Systems at the same depth share a sync point. Edges can opt out with
+    /// [`before_ignore_deferred`](IntoSystemConfigs::before_ignore_deferred) and
+    /// [`after_ignore_deferred`](IntoSystemConfigs::after_ignore_deferred).
+    ///
+    /// Defaults to `true`.
+    pub auto_insert_apply_deferred: bool,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -1567,6 +1796,7 @@ impl ScheduleBuildSettings {
             hierarchy_detection: LogLevel::Warn,
             use_shortnames: true,
             report_sets: true,
+            auto_insert_apply_deferred: true,
         }
     }
 }
@@ -1575,7 +1805,10 @@ impl ScheduleBuildSettings {
 mod tests {
     use crate::{
         self as bevy_ecs,
-        schedule::{IntoSystemConfigs, IntoSystemSetConfig, Schedule, SystemSet},
+        prelude::*,
+        schedule::{
+            IntoSystemConfigs, IntoSystemSetConfig, Schedule, ScheduleBuildSettings, SystemSet,
+        },
         world::World,
     };
 
@@ -1596,4 +1829,73 @@ mod tests {
         );
         schedule.run(&mut world);
     }
+
+    #[derive(Resource, Default)]
+    struct Counts(Vec<usize>);
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+
+    fn record_count(world: &mut World) {
+        let count = world.entities().len() as usize;
+        world.resource_mut::<Counts>().0.push(count);
+    }
+
+    #[test]
+    fn auto_insert_apply_deferred() {
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems((spawn, record_count).chain());
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counts>().0, vec![1]);
+        assert_eq!(schedule.graph().auto_sync_points().count(), 1);
+    }
+
+    #[test]
+    fn auto_sync_points_are_shared() {
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems((
+            spawn,
+            (|mut commands: Commands| {
+                commands.spawn_empty();
+            })
+            .before(record_count),
+            record_count.after(spawn),
+        ));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counts>().0, vec![2]);
+        assert_eq!(schedule.graph().auto_sync_points().count(), 1);
+    }
+
+    #[test]
+    fn ignore_deferred_edges() {
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(record_count.after_ignore_deferred(spawn));
+        schedule.add_systems(spawn);
+        schedule.run(&mut world);
+        // the commands are only applied at the end of the schedule
+        assert_eq!(world.resource::<Counts>().0, vec![0]);
+        assert_eq!(world.entities().len(), 1);
+        assert_eq!(schedule.graph().auto_sync_points().count(), 0);
+    }
+
+    #[test]
+    fn disable_auto_insert_apply_deferred() {
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let mut schedule = Schedule::new();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            auto_insert_apply_deferred: false,
+            ..Default::default()
+        });
+        schedule.add_systems((spawn, record_count).chain());
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counts>().0, vec![0]);
+    }
 }
@@ -1,19 +1,22 @@
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
 
 use crate as bevy_ecs;
 use crate::change_detection::DetectChangesMut;
-use crate::schedule::ScheduleLabel;
+use crate::schedule::{IntoSystemConfigs, ScheduleLabel, SystemConfigs, SystemSet};
 use crate::system::Resource;
 use crate::world::World;
+use bevy_utils::{all_tuples, tracing::warn};
 
 pub use bevy_ecs_macros::States;
 
 /// Types that can define world-wide states in a finite-state machine.
 ///
-/// The [`Default`] trait defines the starting state.
+/// For states added with `App::add_state`, the [`Default`] trait defines the starting state.
+/// [`ComputedStates`] and [`SubStates`] don't need to implement it.
 /// Multiple states can be defined for the same world,
 /// allowing you to classify the state of the world across orthogonal dimensions.
 /// You can access the current state of type `T` with the [`State<T>`] resource,
@@ -36,7 +39,7 @@ pub use bevy_ecs_macros::States;
 /// }
 ///
 /// ```
-pub trait States: 'static + Send + Sync + Clone + PartialEq + Eq + Hash + Debug + Default {
+pub trait States: 'static + Send + Sync + Clone + PartialEq + Eq + Hash + Debug {
     /// The type returned when iterating over all [`variants`](States::variants) of this type.
     type Iter: Iterator<Item = Self>;
 
@@ -73,7 +76,7 @@ pub struct OnTransition<S: States> {
 /// queue a transition in the [`NextState<S>`] resource, and it will be applied by the next
 /// [`apply_state_transition::<S>`] system.
 ///
-/// The starting state is defined via the [`Default`] implementation for `S`.
+/// The starting state is defined via the [`Default`] implementation for `S`, if it has one.
 #[derive(Resource, Default, Debug)]
 pub struct State<S: States>(S);
 
@@ -110,9 +113,15 @@ impl<S: States> Deref for State<S> {
 /// To queue a transition, just set the contained value to `Some(next_state)`.
 /// Note that these transitions can be overridden by other systems:
 /// only the actual value of this resource at the time of [`apply_state_transition`] matters.
-#[derive(Resource, Default, Debug)]
+#[derive(Resource, Debug)]
 pub struct NextState<S: States>(pub Option<S>);
 
+impl<S: States> Default for NextState<S> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
 impl<S: States> NextState<S> {
     /// Tentatively set a planned state transition to `Some(state)`.
     pub fn set(&mut self, state: S) {
@@ -120,6 +129,112 @@ impl<S: States> NextState<S> {
     }
 }
 
+/// The label of a [`Schedule`](super::Schedule) that runs whenever [`State<S>`] is paused in this
+/// state by [`StateStack::push`].
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnPause<S: States>(pub S);
+
+/// The label of a [`Schedule`](super::Schedule) that runs whenever [`State<S>`] resumes this state
+/// after [`StateStack::pop`].
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnResume<S: States>(pub S);
+
+/// An operation queued in a [`StateStack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StackOperation<S: States> {
+    Push(S),
+    Pop,
+    Replace(S),
+}
+
+/// The states paused below the current [`State<S>`], for push and pop transitions.
+///
+/// [`StateStack::push`] pauses the current state and enters a new one on top of it, and
+/// [`StateStack::pop`] exits the current state to resume the state below it. Unlike
+/// [`NextState<S>`], where only the last queued state matters, every queued operation is applied
+/// in order by the next [`apply_state_transition::<S>`], after the transition queued in
+/// [`NextState<S>`] if any.
+///
+/// The current state always stays in [`State<S>`], while the paused states are kept here.
+/// [`in_state`](super::common_conditions::in_state) considers both, so systems of a paused state
+/// keep running under the states pushed on top of it.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::StateStack;
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum Screen {
+///     #[default]
+///     Game,
+///     PauseMenu,
+///     Settings,
+/// }
+///
+/// fn open_pause_menu(mut stack: ResMut<StateStack<Screen>>) {
+///     // pauses `Screen::Game`, running `OnPause(Screen::Game)` then `OnEnter(Screen::PauseMenu)`
+///     stack.push(Screen::PauseMenu);
+/// }
+///
+/// fn close_menu(mut stack: ResMut<StateStack<Screen>>) {
+///     // runs `OnExit` of the menu then `OnResume` of the state below it
+///     stack.pop();
+/// }
+/// # bevy_ecs::system::assert_is_system(open_pause_menu);
+/// # bevy_ecs::system::assert_is_system(close_menu);
+/// ```
+#[derive(Resource, Debug)]
+pub struct StateStack<S: States> {
+    paused: Vec<S>,
+    queued: Vec<StackOperation<S>>,
+}
+
+impl<S: States> Default for StateStack<S> {
+    fn default() -> Self {
+        Self {
+            paused: Vec::new(),
+            queued: Vec::new(),
+        }
+    }
+}
+
+impl<S: States> StateStack<S> {
+    /// Returns the paused states, from the bottom of the stack to the one right below the
+    /// current state.
+    pub fn paused(&self) -> &[S] {
+        &self.paused
+    }
+
+    /// Queues pausing the current state and entering `state` on top of it.
+    ///
+    /// This runs the [`OnPause`] schedule of the current state, then the [`OnEnter`] schedule of
+    /// `state`.
+    pub fn push(&mut self, state: S) {
+        self.queued.push(StackOperation::Push(state));
+    }
+
+    /// Queues exiting the current state and resuming the state below it.
+    ///
+    /// This runs the [`OnExit`] schedule of the current state, then the [`OnResume`] schedule of
+    /// the resumed state. Popping with no paused state is ignored with a warning.
+    pub fn pop(&mut self) {
+        self.queued.push(StackOperation::Pop);
+    }
+
+    /// Queues replacing the current state with `state`, keeping the paused states.
+    ///
+    /// This is the same transition as the one queued by [`NextState::set`], ordered with the
+    /// other operations of the stack.
+    pub fn replace(&mut self, state: S) {
+        self.queued.push(StackOperation::Replace(state));
+    }
+
+    /// Removes the paused states and the queued operations.
+    pub fn clear(&mut self) {
+        self.paused.clear();
+        self.queued.clear();
+    }
+}
+
 /// Run the enter schedule (if it exists) for the current state.
 pub fn run_enter_schedule<S: States>(world: &mut World) {
     world
@@ -132,25 +247,481 @@ pub fn run_enter_schedule<S: States>(world: &mut World) {
 /// - Runs the [`OnExit(exited_state)`] schedule, if it exists.
 /// - Runs the [`OnTransition { from: exited_state, to: entered_state }`](OnTransition), if it exists.
 /// - Runs the [`OnEnter(entered_state)`] schedule, if it exists.
+///
+/// It then applies the operations queued in the [`StateStack<S>`] resource, if it exists.
 pub fn apply_state_transition<S: States>(world: &mut World) {
     // We want to take the `NextState` resource,
     // but only mark it as changed if it wasn't empty.
     let mut next_state_resource = world.resource_mut::<NextState<S>>();
     if let Some(entered) = next_state_resource.bypass_change_detection().0.take() {
         next_state_resource.set_changed();
+        replace_state(world, entered);
+    }
+
+    let Some(mut stack) = world.get_resource_mut::<StateStack<S>>() else {
+        return;
+    };
+    if stack.queued.is_empty() {
+        return;
+    }
+    for operation in mem::take(&mut stack.queued) {
+        match operation {
+            StackOperation::Push(entered) => {
+                let paused = mem::replace(&mut world.resource_mut::<State<S>>().0, entered.clone());
+                world
+                    .resource_mut::<StateStack<S>>()
+                    .paused
+                    .push(paused.clone());
+                world.try_run_schedule(OnPause(paused)).ok();
+                world.try_run_schedule(OnEnter(entered)).ok();
+            }
+            StackOperation::Pop => {
+                let Some(resumed) = world.resource_mut::<StateStack<S>>().paused.pop() else {
+                    warn!(
+                        "Tried to pop the state stack of {} without any paused state.",
+                        std::any::type_name::<S>()
+                    );
+                    continue;
+                };
+                let exited = mem::replace(&mut world.resource_mut::<State<S>>().0, resumed.clone());
+                world.try_run_schedule(OnExit(exited)).ok();
+                world.try_run_schedule(OnResume(resumed)).ok();
+            }
+            StackOperation::Replace(entered) => replace_state(world, entered),
+        }
+    }
+}
+
+/// Replaces the current value of [`State<S>`] with `entered`, running the [`OnExit`],
+/// [`OnTransition`] and [`OnEnter`] schedules if it changed.
+fn replace_state<S: States>(world: &mut World, entered: S) {
+    let mut state_resource = world.resource_mut::<State<S>>();
+    if *state_resource != entered {
+        let exited = mem::replace(&mut state_resource.0, entered.clone());
+        // Try to run the schedules if they exist.
+        world.try_run_schedule(OnExit(exited.clone())).ok();
+        world
+            .try_run_schedule(OnTransition {
+                from: exited,
+                to: entered.clone(),
+            })
+            .ok();
+        world.try_run_schedule(OnEnter(entered)).ok();
+    }
+}
+
+/// The [`SystemSet`] of the systems cleaning up after a state in its [`OnExit`] schedule, like
+/// the despawning of state-scoped entities and the clearing of state-scoped events.
+///
+/// The set is [ordered last](super::Schedule::order_last) in the [`OnExit`] schedules it is used
+/// in, so the other systems of these schedules run before it, and can still read what it cleans
+/// up.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StateScopedCleanup;
+
+/// The [`SystemSet`] of the system applying the transitions of the state `S`, whether it is a
+/// regular state driven by [`apply_state_transition::<S>`], or one of [`ComputedStates`] and
+/// [`SubStates`].
+///
+/// The systems of [`ComputedStates`] and [`SubStates`] are ordered after the sets of their
+/// [`SourceStates`](ComputedStates::SourceStates).
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StateTransitionSet<S: States>(PhantomData<fn() -> S>);
+
+impl<S: States> Default for StateTransitionSet<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
 
-        let mut state_resource = world.resource_mut::<State<S>>();
-        if *state_resource != entered {
-            let exited = mem::replace(&mut state_resource.0, entered.clone());
-            // Try to run the schedules if they exist.
-            world.try_run_schedule(OnExit(exited.clone())).ok();
-            world
-                .try_run_schedule(OnTransition {
-                    from: exited,
-                    to: entered.clone(),
-                })
-                .ok();
-            world.try_run_schedule(OnEnter(entered)).ok();
+/// The [`States`] that [`ComputedStates`] and [`SubStates`] are derived from.
+///
+/// This is implemented for any [`States`], for `Option<S>` of any [`States`], which is `None`
+/// when [`State<S>`] does not exist, and for tuples of those.
+pub trait StateSet: Sized + 'static {
+    /// Returns the current values of the states in this set, or `None` if one of them does not
+    /// exist in the world.
+    fn get_states(world: &World) -> Option<Self>;
+
+    /// Orders `configs` after the [`StateTransitionSet`] of every state in this set.
+    fn after_sources(configs: SystemConfigs) -> SystemConfigs;
+}
+
+impl<S: States> StateSet for S {
+    fn get_states(world: &World) -> Option<Self> {
+        world
+            .get_resource::<State<S>>()
+            .map(|state| state.0.clone())
+    }
+
+    fn after_sources(configs: SystemConfigs) -> SystemConfigs {
+        configs.after(StateTransitionSet::<S>::default())
+    }
+}
+
+impl<S: States> StateSet for Option<S> {
+    fn get_states(world: &World) -> Option<Self> {
+        Some(S::get_states(world))
+    }
+
+    fn after_sources(configs: SystemConfigs) -> SystemConfigs {
+        S::after_sources(configs)
+    }
+}
+
+macro_rules! impl_state_set {
+    ($($param: ident),*) => {
+        impl<$($param: StateSet),*> StateSet for ($($param,)*) {
+            fn get_states(world: &World) -> Option<Self> {
+                Some(($($param::get_states(world)?,)*))
+            }
+
+            fn after_sources(configs: SystemConfigs) -> SystemConfigs {
+                $(let configs = $param::after_sources(configs);)*
+                configs
+            }
         }
+    };
+}
+
+all_tuples!(impl_state_set, 1, 8, S);
+
+/// A state whose value is computed from one or more other states, instead of being set through
+/// [`NextState`].
+///
+/// The state is recomputed by [`apply_computed_state::<Self>`], after the transitions of its
+/// [`SourceStates`](ComputedStates::SourceStates) have been applied. [`State<Self>`] only exists
+/// while [`ComputedStates::compute`] returns `Some`, and run conditions like
+/// [`in_state`](super::common_conditions::in_state) are `false` while it doesn't.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::ComputedStates;
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum AppState {
+///     #[default]
+///     Menu,
+///     InGame,
+///     Paused,
+/// }
+///
+/// /// Exists while the game is running, paused or not.
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, States)]
+/// enum InGame {
+///     Running,
+/// }
+///
+/// impl ComputedStates for InGame {
+///     type SourceStates = AppState;
+///
+///     fn compute(app_state: AppState) -> Option<Self> {
+///         match app_state {
+///             AppState::InGame | AppState::Paused => Some(InGame::Running),
+///             AppState::Menu => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait ComputedStates: States {
+    /// The states this state is computed from.
+    type SourceStates: StateSet;
+
+    /// Computes the value of this state from its sources, or `None` if it should not exist.
+    ///
+    /// This is not called when one of the sources does not exist: use `Option<S>` as a source
+    /// to handle that case.
+    fn compute(sources: Self::SourceStates) -> Option<Self>;
+}
+
+/// A state that only exists while its [`SourceStates`](SubStates::SourceStates) have certain
+/// values, and is otherwise driven by [`NextState`] like regular states.
+///
+/// When the sources start matching, [`State<Self>`] is created with the value returned by
+/// [`SubStates::should_exist`] and its [`OnEnter`] schedule runs. When they stop matching, its
+/// [`OnExit`] schedule runs and [`State<Self>`] is removed. Transitions queued in
+/// [`NextState<Self>`] while the state does not exist are discarded.
+///
+/// As with [`ComputedStates`], run conditions like
+/// [`in_state`](super::common_conditions::in_state) are `false` while the state doesn't exist.
+pub trait SubStates: States {
+    /// The states deciding whether this state exists.
+    type SourceStates: StateSet;
+
+    /// Returns the initial value of this state if it should exist, `None` otherwise.
+    ///
+    /// This is only called to decide whether the state should exist: while it does, its value
+    /// is only changed through [`NextState<Self>`].
+    fn should_exist(sources: Self::SourceStates) -> Option<Self>;
+}
+
+/// Runs the schedules of a transition of `S` from `exited` to `entered`, where `None` means the
+/// state did not or does not exist anymore.
+fn run_transition_schedules<S: States>(world: &mut World, exited: Option<S>, entered: Option<S>) {
+    if let Some(exited) = exited.clone() {
+        world.try_run_schedule(OnExit(exited)).ok();
+    }
+    if let (Some(from), Some(to)) = (exited, entered.clone()) {
+        world.try_run_schedule(OnTransition { from, to }).ok();
+    }
+    if let Some(entered) = entered {
+        world.try_run_schedule(OnEnter(entered)).ok();
+    }
+}
+
+/// Recomputes the [`ComputedStates`] `S` from its sources, updating [`State<S>`] and running
+/// the [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules if its value changed.
+pub fn apply_computed_state<S: ComputedStates>(world: &mut World) {
+    let entered = S::SourceStates::get_states(world).and_then(S::compute);
+    let exited = world
+        .get_resource::<State<S>>()
+        .map(|state| state.0.clone());
+    if entered == exited {
+        return;
+    }
+
+    match entered.clone() {
+        Some(entered) => world.insert_resource(State(entered)),
+        None => {
+            world.remove_resource::<State<S>>();
+        }
+    }
+    run_transition_schedules(world, exited, entered);
+}
+
+/// Creates or removes the [`SubStates`] `S` according to its sources, then applies the
+/// transition queued in [`NextState<S>`] like [`apply_state_transition::<S>`] if it exists.
+pub fn apply_sub_state<S: SubStates>(world: &mut World) {
+    let exists = world.contains_resource::<State<S>>();
+    match S::SourceStates::get_states(world).and_then(S::should_exist) {
+        Some(_) if exists => apply_state_transition::<S>(world),
+        Some(initial) => {
+            world.resource_mut::<NextState<S>>().0 = None;
+            world.insert_resource(State(initial.clone()));
+            run_transition_schedules(world, None, Some(initial));
+        }
+        None => {
+            world.resource_mut::<NextState<S>>().0 = None;
+            if let Some(mut stack) = world.get_resource_mut::<StateStack<S>>() {
+                stack.clear();
+            }
+            if let Some(State(exited)) = world.remove_resource::<State<S>>() {
+                run_transition_schedules(world, Some(exited), None);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum AppState {
+        #[default]
+        Menu,
+        InGame,
+        Paused,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, States)]
+    enum InGame {
+        Running,
+        Paused,
+    }
+
+    impl ComputedStates for InGame {
+        type SourceStates = AppState;
+
+        fn compute(app_state: AppState) -> Option<Self> {
+            match app_state {
+                AppState::Menu => None,
+                AppState::InGame => Some(InGame::Running),
+                AppState::Paused => Some(InGame::Paused),
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, States)]
+    enum MenuPage {
+        Main,
+        Settings,
+    }
+
+    impl SubStates for MenuPage {
+        type SourceStates = Option<AppState>;
+
+        fn should_exist(app_state: Option<AppState>) -> Option<Self> {
+            (app_state == Some(AppState::Menu)).then_some(MenuPage::Main)
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<String>);
+
+    fn add_logger(world: &mut World, label: impl ScheduleLabel, message: String) {
+        let mut schedule = Schedule::new();
+        schedule.add_systems(move |mut log: ResMut<Log>| log.0.push(message.clone()));
+        world.add_schedule(schedule, label);
+    }
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.init_resource::<State<AppState>>();
+        world.init_resource::<NextState<AppState>>();
+        world.init_resource::<NextState<MenuPage>>();
+
+        for (state, name) in [(InGame::Running, "running"), (InGame::Paused, "paused")] {
+            add_logger(&mut world, OnEnter(state), format!("enter {name}"));
+            add_logger(&mut world, OnExit(state), format!("exit {name}"));
+        }
+        for (state, name) in [(MenuPage::Main, "main"), (MenuPage::Settings, "settings")] {
+            add_logger(&mut world, OnEnter(state), format!("enter {name}"));
+            add_logger(&mut world, OnExit(state), format!("exit {name}"));
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            apply_state_transition::<AppState>.in_set(StateTransitionSet::<AppState>::default()),
+            <InGame as ComputedStates>::SourceStates::after_sources(
+                apply_computed_state::<InGame>.in_set(StateTransitionSet::<InGame>::default()),
+            ),
+            <MenuPage as SubStates>::SourceStates::after_sources(
+                apply_sub_state::<MenuPage>.in_set(StateTransitionSet::<MenuPage>::default()),
+            ),
+        ));
+        (world, schedule)
+    }
+
+    fn take_log(world: &mut World) -> Vec<String> {
+        std::mem::take(&mut world.resource_mut::<Log>().0)
+    }
+
+    #[test]
+    fn computed_states() {
+        let (mut world, mut schedule) = setup();
+        schedule.run(&mut world);
+        assert!(!world.contains_resource::<State<InGame>>());
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<State<InGame>>().get(), &InGame::Running);
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Paused);
+        schedule.run(&mut world);
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Menu);
+        schedule.run(&mut world);
+        assert!(!world.contains_resource::<State<InGame>>());
+
+        let log: Vec<_> = take_log(&mut world)
+            .into_iter()
+            .filter(|message| !message.contains("main"))
+            .collect();
+        assert_eq!(
+            log,
+            [
+                "enter running",
+                "exit running",
+                "enter paused",
+                "exit paused"
+            ]
+        );
+    }
+
+    #[test]
+    fn state_stack() {
+        let (mut world, mut schedule) = setup();
+        world.init_resource::<StateStack<AppState>>();
+        for (state, name) in [(AppState::InGame, "game"), (AppState::Paused, "pause")] {
+            add_logger(&mut world, OnEnter(state), format!("enter {name}"));
+            add_logger(&mut world, OnExit(state), format!("exit {name}"));
+            add_logger(&mut world, OnPause(state), format!("pause {name}"));
+            add_logger(&mut world, OnResume(state), format!("resume {name}"));
+        }
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        schedule.run(&mut world);
+        take_log(&mut world);
+
+        world
+            .resource_mut::<StateStack<AppState>>()
+            .push(AppState::Paused);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<State<AppState>>().get(), &AppState::Paused);
+        assert_eq!(
+            world.resource::<StateStack<AppState>>().paused(),
+            [AppState::InGame]
+        );
+
+        let mut stack = world.resource_mut::<StateStack<AppState>>();
+        stack.pop();
+        // popping an empty stack is ignored
+        stack.pop();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<State<AppState>>().get(), &AppState::InGame);
+        assert!(world.resource::<StateStack<AppState>>().paused().is_empty());
+
+        let log: Vec<_> = take_log(&mut world)
+            .into_iter()
+            .filter(|message| !message.contains("running") && !message.contains("paused"))
+            .collect();
+        assert_eq!(
+            log,
+            ["pause game", "enter pause", "exit pause", "resume game"]
+        );
+    }
+
+    #[test]
+    fn sub_states() {
+        let (mut world, mut schedule) = setup();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<State<MenuPage>>().get(), &MenuPage::Main);
+
+        world
+            .resource_mut::<NextState<MenuPage>>()
+            .set(MenuPage::Settings);
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<State<MenuPage>>().get(),
+            &MenuPage::Settings
+        );
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        schedule.run(&mut world);
+        assert!(!world.contains_resource::<State<MenuPage>>());
+
+        // transitions of a sub state that does not exist are discarded
+        world
+            .resource_mut::<NextState<MenuPage>>()
+            .set(MenuPage::Settings);
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Menu);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<State<MenuPage>>().get(), &MenuPage::Main);
+
+        let log: Vec<_> = take_log(&mut world)
+            .into_iter()
+            .filter(|message| !message.contains("running"))
+            .collect();
+        assert_eq!(
+            log,
+            [
+                "enter main",
+                "exit main",
+                "enter settings",
+                "exit settings",
+                "enter main"
+            ]
+        );
     }
 }
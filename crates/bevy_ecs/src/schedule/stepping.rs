@@ -0,0 +1,563 @@
+use std::{borrow::Cow, cmp::Ordering};
+
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    self as bevy_ecs,
+    schedule::{BoxedScheduleLabel, BoxedSystemSet, IntoSystemSet, Schedule, ScheduleLabel},
+    system::Resource,
+};
+
+/// A resource to pause the systems of chosen [`Schedule`]s and run them one at a time.
+///
+/// Stepping only applies to schedules that were added with [`Stepping::add_schedule`], and run
+/// through the [`Schedules`](crate::schedule::Schedules) resource, like with
+/// [`World::run_schedule`](crate::world::World::run_schedule). Other schedules keep running
+/// normally, so that the app stays responsive while it is paused. This includes a [`Schedule`]
+/// that is run directly with [`Schedule::run`] without being added to
+/// [`Schedules`](crate::schedule::Schedules), since it has no label to step it by.
+///
+/// The systems are skipped by the schedule's executor, which receives the set of systems to skip
+/// when it runs.
+///
+/// While stepping is [enabled](Stepping::enable), the systems of the stepped schedules only run
+/// when asked to:
+/// - [`step`](Stepping::step) runs the next system, the one at the [cursor](Stepping::cursor).
+/// - [`continue_frame`](Stepping::continue_frame) runs the systems from the cursor up to the end
+///   of the frame, or up to the next system with a [breakpoint](Stepping::set_breakpoint).
+///
+/// Schedules are stepped through in the order they were added, which should be the order they
+/// run in during a frame. A stepped schedule that does not run in a frame holds up the cursor
+/// until it runs again.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ScheduleLabel, Schedules, Stepping};
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct Update;
+///
+/// fn first() {}
+/// fn second() {}
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::new();
+/// schedule.add_systems((first, second).chain());
+/// world.add_schedule(schedule, Update);
+///
+/// let mut stepping = Stepping::new();
+/// stepping.add_schedule(Update).enable();
+/// world.insert_resource(stepping);
+///
+/// // nothing runs until asked to
+/// world.run_schedule(Update);
+///
+/// world.resource_mut::<Stepping>().step();
+/// world.run_schedule(Update);
+/// // `first` ran, and `second` is next
+/// let stepping = world.resource::<Stepping>();
+/// assert!(stepping.next_system().unwrap().1.ends_with("second"));
+/// ```
+#[derive(Resource, Default)]
+pub struct Stepping {
+    schedules: Vec<SteppedSchedule>,
+    enabled: bool,
+    action: Action,
+    cursor: Cursor,
+    /// Whether continuing ignores the breakpoint at the cursor, which it stopped at last time.
+    /// Cleared when the cursor moves to another schedule.
+    ignore_breakpoint: bool,
+}
+
+/// What the stepped schedules will do the next time they run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Action {
+    /// Wait for the next action.
+    #[default]
+    Wait,
+    /// Run the system at the cursor.
+    Step,
+    /// Run the systems from the cursor to the end of the frame, or to the next breakpoint.
+    Continue,
+}
+
+/// The position of the next system to run.
+#[derive(Clone, Copy, Debug, Default)]
+struct Cursor {
+    schedule: usize,
+    system: usize,
+}
+
+struct SteppedSchedule {
+    label: BoxedScheduleLabel,
+    /// The sets with a breakpoint, as set by the user.
+    breakpoints: Vec<BoxedSystemSet>,
+    /// The systems of the schedule with a breakpoint, by their index in the executable schedule.
+    breakpoint_systems: FixedBitSet,
+    /// The names of the systems, in the order they run, as of the last time the schedule ran.
+    system_names: Vec<Cow<'static, str>>,
+    /// The systems skipped by run conditions since the cursor entered the schedule.
+    skipped_by_conditions: FixedBitSet,
+}
+
+impl SteppedSchedule {
+    /// Updates the names and breakpoints of the systems, since the schedule could have changed.
+    fn refresh(&mut self, schedule: &Schedule) {
+        self.system_names.clear();
+        self.system_names.extend(
+            schedule
+                .executable
+                .systems
+                .iter()
+                .map(|system| system.name()),
+        );
+
+        let count = schedule.executable.systems.len();
+        self.breakpoint_systems.clear();
+        self.breakpoint_systems.grow(count);
+        self.skipped_by_conditions.grow(count);
+        for set in &self.breakpoints {
+            let systems = schedule.graph().systems_in_set(&**set);
+            for (index, id) in schedule.executable.system_ids.iter().enumerate() {
+                if systems.contains(id) {
+                    self.breakpoint_systems.insert(index);
+                }
+            }
+        }
+    }
+}
+
+impl Stepping {
+    /// Creates a [`Stepping`] resource that steps through no schedule, and is disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Steps through the schedule with the given label, after the schedules already added.
+    pub fn add_schedule(&mut self, label: impl ScheduleLabel) -> &mut Self {
+        if self.schedule_index(&label).is_none() {
+            self.schedules.push(SteppedSchedule {
+                label: label.dyn_clone(),
+                breakpoints: Vec::new(),
+                breakpoint_systems: FixedBitSet::new(),
+                system_names: Vec::new(),
+                skipped_by_conditions: FixedBitSet::new(),
+            });
+        }
+        self
+    }
+
+    /// Stops stepping through the schedule with the given label, which then runs normally.
+    ///
+    /// If the cursor was in that schedule, it moves to the start of the next one.
+    pub fn remove_schedule(&mut self, label: impl ScheduleLabel) -> &mut Self {
+        if let Some(index) = self.schedule_index(&label) {
+            self.schedules.remove(index);
+            match index.cmp(&self.cursor.schedule) {
+                Ordering::Less => self.cursor.schedule -= 1,
+                Ordering::Equal => {
+                    self.cursor.system = 0;
+                    self.ignore_breakpoint = false;
+                    if self.cursor.schedule >= self.schedules.len() {
+                        self.cursor.schedule = 0;
+                    }
+                }
+                Ordering::Greater => {}
+            }
+        }
+        self
+    }
+
+    /// Returns an iterator over the labels of the stepped schedules, in the order they are
+    /// stepped through.
+    pub fn schedules(&self) -> impl Iterator<Item = &dyn ScheduleLabel> {
+        self.schedules.iter().map(|schedule| &*schedule.label)
+    }
+
+    /// Pauses the stepped schedules, which then wait for [`step`](Stepping::step) or
+    /// [`continue_frame`](Stepping::continue_frame).
+    pub fn enable(&mut self) -> &mut Self {
+        self.enabled = true;
+        self.action = Action::Wait;
+        self
+    }
+
+    /// Lets the stepped schedules run normally again.
+    ///
+    /// The cursor is moved back to the start of the first schedule, so that enabling stepping
+    /// again starts from the beginning of a frame.
+    pub fn disable(&mut self) -> &mut Self {
+        self.enabled = false;
+        self.action = Action::Wait;
+        self.cursor = Cursor::default();
+        self.ignore_breakpoint = false;
+        self
+    }
+
+    /// Returns `true` if stepping is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Runs the system at the cursor the next time its schedule runs, then waits again.
+    pub fn step(&mut self) -> &mut Self {
+        self.action = Action::Step;
+        self
+    }
+
+    /// Runs the systems from the cursor up to the end of the frame, stopping before the next
+    /// system with a breakpoint.
+    ///
+    /// A breakpoint on the system at the cursor is ignored, so that continuing from a breakpoint
+    /// moves on to the next one.
+    pub fn continue_frame(&mut self) -> &mut Self {
+        self.action = Action::Continue;
+        self.ignore_breakpoint = true;
+        self
+    }
+
+    /// Sets a breakpoint on a system, or on all the systems of a system set, of the schedule
+    /// with the given label.
+    ///
+    /// [`continue_frame`](Stepping::continue_frame) stops before the systems with a breakpoint.
+    /// Breakpoints can be set on schedules that are not stepped through, but only take effect
+    /// once they are.
+    pub fn set_breakpoint<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        set: impl IntoSystemSet<M>,
+    ) -> &mut Self {
+        self.add_schedule_if_missing(&schedule);
+        let set: BoxedSystemSet = Box::new(set.into_system_set());
+        let index = self.schedule_index(&schedule).unwrap();
+        let breakpoints = &mut self.schedules[index].breakpoints;
+        if !breakpoints.contains(&set) {
+            breakpoints.push(set);
+        }
+        self
+    }
+
+    /// Removes a breakpoint set with [`set_breakpoint`](Stepping::set_breakpoint).
+    pub fn clear_breakpoint<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        set: impl IntoSystemSet<M>,
+    ) -> &mut Self {
+        let set: BoxedSystemSet = Box::new(set.into_system_set());
+        if let Some(index) = self.schedule_index(&schedule) {
+            self.schedules[index]
+                .breakpoints
+                .retain(|breakpoint| **breakpoint != *set);
+        }
+        self
+    }
+
+    /// Removes all breakpoints.
+    pub fn clear_breakpoints(&mut self) -> &mut Self {
+        for schedule in &mut self.schedules {
+            schedule.breakpoints.clear();
+        }
+        self
+    }
+
+    /// Returns the label of the schedule the cursor is in, and the index of the next system to
+    /// run in that schedule, in the order its systems run.
+    pub fn cursor(&self) -> Option<(&dyn ScheduleLabel, usize)> {
+        let schedule = self.schedules.get(self.cursor.schedule)?;
+        Some((&*schedule.label, self.cursor.system))
+    }
+
+    /// Returns the label of the schedule the cursor is in, and the name of the next system to
+    /// run.
+    ///
+    /// Returns `None` if no schedule is stepped through, or if the schedule of the cursor has not
+    /// run yet.
+    pub fn next_system(&self) -> Option<(&dyn ScheduleLabel, &str)> {
+        let schedule = self.schedules.get(self.cursor.schedule)?;
+        let name = schedule.system_names.get(self.cursor.system)?;
+        Some((&*schedule.label, name))
+    }
+
+    /// Returns the names of the systems of the schedule with the given label that were skipped
+    /// by their run conditions, or the run conditions of their sets, since the cursor entered the
+    /// schedule.
+    pub fn skipped_by_conditions(
+        &self,
+        schedule: impl ScheduleLabel,
+    ) -> impl Iterator<Item = &str> {
+        self.schedule_index(&schedule)
+            .map(|index| &self.schedules[index])
+            .into_iter()
+            .flat_map(|schedule| {
+                schedule
+                    .skipped_by_conditions
+                    .ones()
+                    .filter_map(|index| schedule.system_names.get(index))
+                    .map(|name| &**name)
+            })
+    }
+
+    fn schedule_index(&self, label: &dyn ScheduleLabel) -> Option<usize> {
+        self.schedules
+            .iter()
+            .position(|schedule| &*schedule.label == label)
+    }
+
+    fn add_schedule_if_missing(&mut self, label: &dyn ScheduleLabel) {
+        if self.schedule_index(label).is_none() {
+            self.add_schedule(label.dyn_clone());
+        }
+    }
+
+    /// Moves the cursor to the start of the next stepped schedule, or back to the first one at
+    /// the end of the frame, where continuing stops.
+    fn next_schedule(&mut self) {
+        self.cursor.system = 0;
+        self.ignore_breakpoint = false;
+        self.cursor.schedule += 1;
+        if self.cursor.schedule >= self.schedules.len() {
+            self.cursor.schedule = 0;
+            if self.action == Action::Continue {
+                self.action = Action::Wait;
+            }
+        }
+    }
+
+    /// Returns the systems of `schedule` that should not run this time, and moves the cursor
+    /// past the ones that should.
+    ///
+    /// Returns `None` if `schedule` is not stepped through, in which case it runs normally.
+    pub(super) fn skipped_systems(&mut self, schedule: &Schedule) -> Option<FixedBitSet> {
+        if !self.enabled {
+            return None;
+        }
+        let index = self.schedule_index(schedule.label()?)?;
+        let stepped = &mut self.schedules[index];
+        stepped.refresh(schedule);
+
+        let count = schedule.executable.systems.len();
+        let mut skip = FixedBitSet::with_capacity(count);
+        skip.insert_range(..);
+        if index != self.cursor.schedule || self.action == Action::Wait {
+            return Some(skip);
+        }
+
+        if self.cursor.system == 0 {
+            stepped.skipped_by_conditions.clear();
+        }
+        match self.action {
+            Action::Wait => {}
+            Action::Step => {
+                if self.cursor.system < count {
+                    skip.set(self.cursor.system, false);
+                    self.cursor.system += 1;
+                    self.action = Action::Wait;
+                }
+            }
+            Action::Continue => {
+                while self.cursor.system < count {
+                    if stepped.breakpoint_systems.contains(self.cursor.system)
+                        && !self.ignore_breakpoint
+                    {
+                        self.action = Action::Wait;
+                        break;
+                    }
+                    self.ignore_breakpoint = false;
+                    skip.set(self.cursor.system, false);
+                    self.cursor.system += 1;
+                }
+            }
+        }
+        if self.cursor.system >= count {
+            self.next_schedule();
+        }
+        Some(skip)
+    }
+
+    /// Records the systems of `schedule` that were skipped by run conditions while stepping.
+    pub(super) fn record_skipped_by_conditions(
+        &mut self,
+        schedule: &Schedule,
+        skipped: &FixedBitSet,
+    ) {
+        let Some(index) = schedule
+            .label()
+            .and_then(|label| self.schedule_index(label))
+        else {
+            return;
+        };
+        self.schedules[index]
+            .skipped_by_conditions
+            .union_with(skipped);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stepping;
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        schedule::{ExecutorKind, ScheduleLabel},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct First;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Second;
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Breakpoints;
+
+    #[derive(Resource, Default)]
+    struct Ran(Vec<&'static str>);
+
+    fn a(mut ran: ResMut<Ran>) {
+        ran.0.push("a");
+    }
+
+    fn b(mut ran: ResMut<Ran>) {
+        ran.0.push("b");
+    }
+
+    fn c(mut ran: ResMut<Ran>) {
+        ran.0.push("c");
+    }
+
+    fn d(mut ran: ResMut<Ran>) {
+        ran.0.push("d");
+    }
+
+    fn setup(kind: ExecutorKind) -> World {
+        let mut world = World::new();
+        world.init_resource::<Ran>();
+        let mut first = Schedule::new();
+        first
+            .set_executor_kind(kind)
+            .add_systems((a, b.run_if(|| false), c.in_set(Breakpoints)).chain());
+        world.add_schedule(first, First);
+        let mut second = Schedule::new();
+        second.set_executor_kind(kind).add_systems(d);
+        world.add_schedule(second, Second);
+
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(First).add_schedule(Second).enable();
+        world.insert_resource(stepping);
+        world
+    }
+
+    fn frame(world: &mut World) -> Vec<&'static str> {
+        world.run_schedule(First);
+        world.run_schedule(Second);
+        std::mem::take(&mut world.resource_mut::<Ran>().0)
+    }
+
+    fn step_through(kind: ExecutorKind) {
+        let mut world = setup(kind);
+        assert!(frame(&mut world).is_empty());
+
+        world.resource_mut::<Stepping>().step();
+        assert_eq!(frame(&mut world), vec!["a"]);
+        let stepping = world.resource::<Stepping>();
+        assert!(stepping.next_system().unwrap().1.ends_with("::b"));
+        assert_eq!(stepping.cursor().unwrap().1, 1);
+
+        world.resource_mut::<Stepping>().step();
+        assert!(frame(&mut world).is_empty());
+        let stepping = world.resource::<Stepping>();
+        let skipped: Vec<_> = stepping.skipped_by_conditions(First).collect();
+        assert_eq!(skipped.len(), 1);
+        assert!(skipped[0].ends_with("::b"));
+
+        world.resource_mut::<Stepping>().step();
+        assert_eq!(frame(&mut world), vec!["c"]);
+        assert_eq!(world.resource::<Stepping>().cursor().unwrap().1, 0);
+
+        world.resource_mut::<Stepping>().step();
+        assert_eq!(frame(&mut world), vec!["d"]);
+        assert!(frame(&mut world).is_empty());
+
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(frame(&mut world), vec!["a", "c", "d"]);
+        assert!(frame(&mut world).is_empty());
+
+        world.resource_mut::<Stepping>().disable();
+        assert_eq!(frame(&mut world), vec!["a", "c", "d"]);
+    }
+
+    #[test]
+    fn step_single_threaded() {
+        step_through(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn step_simple() {
+        step_through(ExecutorKind::Simple);
+    }
+
+    #[test]
+    fn step_multi_threaded() {
+        step_through(ExecutorKind::MultiThreaded);
+    }
+
+    #[test]
+    fn breakpoints() {
+        let mut world = setup(ExecutorKind::SingleThreaded);
+        world
+            .resource_mut::<Stepping>()
+            .set_breakpoint(First, c)
+            .set_breakpoint(Second, d)
+            .continue_frame();
+        assert_eq!(frame(&mut world), vec!["a"]);
+
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(frame(&mut world), vec!["c"]);
+
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(frame(&mut world), vec!["d"]);
+
+        world
+            .resource_mut::<Stepping>()
+            .clear_breakpoints()
+            .set_breakpoint(First, Breakpoints)
+            .continue_frame();
+        assert_eq!(frame(&mut world), vec!["a"]);
+
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(frame(&mut world), vec!["c", "d"]);
+    }
+
+    #[test]
+    fn breakpoint_after_empty_schedule() {
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct Empty;
+
+        let mut world = setup(ExecutorKind::SingleThreaded);
+        world.add_schedule(Schedule::new(), Empty);
+        world
+            .resource_mut::<Stepping>()
+            .remove_schedule(Second)
+            .add_schedule(Empty)
+            .add_schedule(Second)
+            .set_breakpoint(Second, d);
+        let frame = |world: &mut World| {
+            world.run_schedule(First);
+            world.run_schedule(Empty);
+            world.run_schedule(Second);
+            std::mem::take(&mut world.resource_mut::<Ran>().0)
+        };
+
+        for _ in 0..3 {
+            world.resource_mut::<Stepping>().step();
+            frame(&mut world);
+        }
+        assert_eq!(world.resource::<Stepping>().cursor().unwrap().1, 0);
+
+        // continuing from the empty schedule still stops at the breakpoint of the next one
+        world.resource_mut::<Stepping>().continue_frame();
+        assert!(frame(&mut world).is_empty());
+
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(frame(&mut world), vec!["d"]);
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
     world::unsafe_world_cell::UnsafeWorldCell,
 };
 
-use super::{ReadOnlySystem, System};
+use super::{ErrorHandler, ReadOnlySystem, System};
 
 /// Customizes the behavior of a [`CombinatorSystem`].
 ///
@@ -161,6 +161,10 @@ where
         self.a.is_exclusive() || self.b.is_exclusive()
     }
 
+    fn has_deferred(&self) -> bool {
+        self.a.has_deferred() || self.b.has_deferred()
+    }
+
     unsafe fn run_unsafe(&mut self, input: Self::In, world: UnsafeWorldCell) -> Self::Out {
         Func::combine(
             input,
@@ -231,6 +235,11 @@ where
         default_sets.append(&mut self.b.default_system_sets());
         default_sets
     }
+
+    fn set_error_handler(&mut self, handler: &ErrorHandler) {
+        self.a.set_error_handler(handler);
+        self.b.set_error_handler(handler);
+    }
 }
 
 /// SAFETY: Both systems are read-only, so any system created by combining them will only read from the world.
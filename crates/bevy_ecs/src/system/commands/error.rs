@@ -0,0 +1,165 @@
+use std::{borrow::Cow, sync::Arc};
+
+use bevy_utils::tracing::warn;
+use thiserror::Error;
+
+use crate::{self as bevy_ecs, entity::Entity, system::Resource, world::World};
+
+use super::Command;
+
+/// An error that occurred while applying a [`FallibleCommand`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// The entity targeted by the command doesn't exist, for example because it was despawned
+    /// by an earlier command.
+    #[error("error[B0003]: Could not {action} entity {entity:?} because it doesn't exist in this World.")]
+    NoSuchEntity {
+        /// The entity that doesn't exist.
+        entity: Entity,
+        /// What the command tried to do with the entity.
+        action: Cow<'static, str>,
+    },
+}
+
+impl CommandError {
+    /// Creates a [`CommandError::NoSuchEntity`] error for a command that tried to `action` the
+    /// `entity`.
+    pub fn no_such_entity(entity: Entity, action: impl Into<Cow<'static, str>>) -> Self {
+        Self::NoSuchEntity {
+            entity,
+            action: action.into(),
+        }
+    }
+}
+
+/// Decides what happens when a [`FallibleCommand`] fails.
+///
+/// The handler of a command is, in order of precedence:
+/// - the one given to [`EntityCommands::on_error`](super::EntityCommands::on_error) or
+///   [`FallibleCommand::with_error_handler`],
+/// - the [`DefaultCommandErrorHandler`] of the world when the command is applied,
+/// - the [`FallibleCommand::default_error_handler`] of the command.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{CommandErrorHandler, CommandQueue, DefaultCommandErrorHandler};
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// let mut world = World::new();
+/// // warn instead of panicking when inserting components on despawned entities
+/// world.insert_resource(DefaultCommandErrorHandler(CommandErrorHandler::Warn));
+///
+/// let entity = world.spawn_empty().id();
+/// let mut queue = CommandQueue::default();
+/// let mut commands = Commands::new(&mut queue, &world);
+/// commands.entity(entity).despawn();
+/// commands.entity(entity).insert(Health(100));
+/// commands
+///     .entity(entity)
+///     .on_error(CommandErrorHandler::custom(|error, _world| println!("{error}")))
+///     .insert(Health(50));
+/// queue.apply(&mut world);
+/// ```
+#[derive(Clone, Default)]
+pub enum CommandErrorHandler {
+    /// Panics with the error.
+    #[default]
+    Panic,
+    /// Logs the error at the warning level.
+    Warn,
+    /// Ignores the error.
+    Ignore,
+    /// Calls the given function with the error and the world the command was applied to.
+    Custom(Arc<dyn Fn(CommandError, &mut World) + Send + Sync>),
+}
+
+impl CommandErrorHandler {
+    /// Creates a [`CommandErrorHandler::Custom`] handler calling `handler` with each error.
+    pub fn custom(handler: impl Fn(CommandError, &mut World) + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(handler))
+    }
+
+    /// Handles the `error` of a command applied to `world`.
+    pub fn handle(&self, error: CommandError, world: &mut World) {
+        match self {
+            Self::Panic => panic!("{error}"),
+            Self::Warn => warn!("{error}"),
+            Self::Ignore => {}
+            Self::Custom(handler) => handler(error, world),
+        }
+    }
+}
+
+impl std::fmt::Debug for CommandErrorHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Panic => write!(f, "Panic"),
+            Self::Warn => write!(f, "Warn"),
+            Self::Ignore => write!(f, "Ignore"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// The [`CommandErrorHandler`] used by the [`FallibleCommand`]s that don't set their own handler.
+///
+/// The resource is read when the commands are applied.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DefaultCommandErrorHandler(pub CommandErrorHandler);
+
+/// A [`Command`] that can fail, whose errors are passed to a [`CommandErrorHandler`] instead of
+/// panicking unconditionally.
+///
+/// Implementors should also implement [`Command`] by calling [`apply_fallible`], so that the
+/// command uses the [`DefaultCommandErrorHandler`] of the world when it is added with
+/// [`Commands::add`](super::Commands::add).
+pub trait FallibleCommand: Send + 'static {
+    /// Applies this command, returning an error instead of mutating the `world` if it can't.
+    fn try_apply(self, world: &mut World) -> Result<(), CommandError>;
+
+    /// The handler of the command's errors when neither the command nor the world set one.
+    fn default_error_handler() -> CommandErrorHandler
+    where
+        Self: Sized,
+    {
+        CommandErrorHandler::Panic
+    }
+
+    /// Returns a [`Command`] which applies this command and passes its errors to `handler`.
+    fn with_error_handler(self, handler: CommandErrorHandler) -> WithErrorHandler<Self>
+    where
+        Self: Sized,
+    {
+        WithErrorHandler {
+            command: self,
+            handler,
+        }
+    }
+}
+
+/// Applies the fallible `command`, passing its error to the [`DefaultCommandErrorHandler`] of the
+/// `world`, or else to the [`FallibleCommand::default_error_handler`] of the command.
+pub fn apply_fallible<C: FallibleCommand>(command: C, world: &mut World) {
+    if let Err(error) = command.try_apply(world) {
+        let handler = world
+            .get_resource::<DefaultCommandErrorHandler>()
+            .map_or_else(C::default_error_handler, |handler| handler.0.clone());
+        handler.handle(error, world);
+    }
+}
+
+/// Turns a [`FallibleCommand`] into a [`Command`] passing its errors to a specific
+/// [`CommandErrorHandler`].
+pub struct WithErrorHandler<C: FallibleCommand> {
+    command: C,
+    handler: CommandErrorHandler,
+}
+
+impl<C: FallibleCommand> Command for WithErrorHandler<C> {
+    #[inline]
+    fn apply(self, world: &mut World) {
+        if let Err(error) = self.command.try_apply(world) {
+            self.handler.handle(error, world);
+        }
+    }
+}
@@ -1,19 +1,28 @@
 mod command_queue;
+mod error;
 mod parallel_scope;
 
+#[cfg(feature = "bevy_reflect")]
+use crate::world::EntityCloneOptions;
 use crate::{
     self as bevy_ecs,
     bundle::Bundle,
     entity::{Entities, Entity},
+    event::Event,
+    observer::{insert_observer, Trigger},
+    relation::Relation,
     world::{FromWorld, World},
 };
 use bevy_ecs_macros::SystemParam;
 use bevy_utils::tracing::{error, info};
 pub use command_queue::CommandQueue;
+pub use error::*;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
 
-use super::{Deferred, Resource, SystemBuffer, SystemMeta};
+use super::{
+    BoxedSystem, Deferred, IntoSystem, Resource, RunSystemById, SystemBuffer, SystemId, SystemMeta,
+};
 
 /// A [`World`] mutation.
 ///
@@ -187,6 +196,7 @@ impl<'w, 's> Commands<'w, 's> {
         EntityCommands {
             entity,
             commands: self,
+            error_handler: None,
         }
     }
 
@@ -211,6 +221,7 @@ impl<'w, 's> Commands<'w, 's> {
         EntityCommands {
             entity,
             commands: self,
+            error_handler: None,
         }
     }
 
@@ -359,6 +370,7 @@ impl<'w, 's> Commands<'w, 's> {
         self.entities.contains(entity).then_some(EntityCommands {
             entity,
             commands: self,
+            error_handler: None,
         })
     }
 
@@ -519,6 +531,50 @@ impl<'w, 's> Commands<'w, 's> {
         self.queue.push(RemoveResource::<R>::new());
     }
 
+    /// Runs the system corresponding to the given [`SystemId`].
+    /// Systems are ran in an exclusive and single threaded way.
+    /// Running slow systems can become a bottleneck.
+    ///
+    /// Calls [`World::run_system_by_id`] when the command is applied, logging any error.
+    pub fn run_system_by_id(&mut self, id: SystemId) {
+        self.queue.push(RunSystemById::new(id));
+    }
+
+    /// Spawns a global observer of the event `E`, which runs every time `E` is triggered.
+    ///
+    /// See [`World::observe`] for more details.
+    pub fn observe<E: Event, M>(
+        &mut self,
+        observer: impl IntoSystem<Trigger<E>, (), M>,
+    ) -> EntityCommands<'w, 's, '_> {
+        let system: BoxedSystem<Trigger<E>> = Box::new(IntoSystem::into_system(observer));
+        let entity = self.spawn_empty().id();
+        self.add(move |world: &mut World| insert_observer(world, entity, None, system));
+        self.entity(entity)
+    }
+
+    /// Triggers `event`, running every global observer of `E`.
+    ///
+    /// See [`World::trigger`] for more details.
+    pub fn trigger<E: Event>(&mut self, event: E) {
+        self.add(move |world: &mut World| world.trigger(event));
+    }
+
+    /// Triggers `event` on `entity`, running the observers of `E` attached to `entity`, then the
+    /// global observers of `E`.
+    ///
+    /// See [`World::trigger_entity`] for more details.
+    pub fn trigger_entity<E: Event>(&mut self, event: E, entity: Entity) {
+        self.add(move |world: &mut World| world.trigger_entity(event, entity));
+    }
+
+    /// Triggers `event` on `entity`, then propagates it up the relation `R`.
+    ///
+    /// See [`World::trigger_propagating`] for more details.
+    pub fn trigger_propagating<E: Event, R: Relation>(&mut self, event: E, entity: Entity) {
+        self.add(move |world: &mut World| world.trigger_propagating::<E, R>(event, entity));
+    }
+
     /// Pushes a generic [`Command`] to the command queue.
     ///
     /// `command` can be a built-in command, custom struct that implements [`Command`] or a closure
@@ -644,6 +700,7 @@ impl<C: EntityCommand> Command for WithEntity<C> {
 pub struct EntityCommands<'w, 's, 'a> {
     entity: Entity,
     commands: &'a mut Commands<'w, 's>,
+    error_handler: Option<CommandErrorHandler>,
 }
 
 impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
@@ -665,13 +722,51 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self.entity
     }
 
+    /// Sets the [`CommandErrorHandler`] of the [fallible commands](FallibleCommand) added after
+    /// this call, like [`insert`](Self::insert) or [`despawn`](Self::despawn), overriding the
+    /// world's [`DefaultCommandErrorHandler`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::CommandErrorHandler;
+    /// # #[derive(Resource)]
+    /// # struct Target { entity: Entity }
+    /// #[derive(Component)]
+    /// struct Hit;
+    ///
+    /// fn hit_system(mut commands: Commands, target: Res<Target>) {
+    ///     commands
+    ///         .entity(target.entity)
+    ///         // the target may be despawned by another system before the commands are applied
+    ///         .on_error(CommandErrorHandler::Ignore)
+    ///         .insert(Hit);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(hit_system);
+    /// ```
+    pub fn on_error(&mut self, handler: CommandErrorHandler) -> &mut Self {
+        self.error_handler = Some(handler);
+        self
+    }
+
+    fn add_fallible<C: FallibleCommand + Command>(&mut self, command: C) {
+        match &self.error_handler {
+            Some(handler) => self
+                .commands
+                .add(command.with_error_handler(handler.clone())),
+            None => self.commands.add(command),
+        }
+    }
+
     /// Adds a [`Bundle`] of components to the entity.
     ///
     /// This will overwrite any previous value(s) of the same component type.
     ///
     /// # Panics
     ///
-    /// The command will panic when applied if the associated entity does not exist.
+    /// By default, the command will panic when applied if the associated entity does not exist.
+    /// See [`on_error`](Self::on_error) to handle the error differently.
     ///
     /// # Example
     ///
@@ -715,7 +810,7 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     /// # bevy_ecs::system::assert_is_system(add_combat_stats_system);
     /// ```
     pub fn insert(&mut self, bundle: impl Bundle) -> &mut Self {
-        self.commands.add(Insert {
+        self.add_fallible(Insert {
             entity: self.entity,
             bundle,
         });
@@ -727,6 +822,9 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     /// See [`EntityMut::remove`](crate::world::EntityMut::remove) for more
     /// details.
     ///
+    /// By default, nothing happens if the associated entity does not exist when the command is
+    /// applied.
+    ///
     /// # Example
     ///
     /// ```
@@ -764,17 +862,57 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     where
         T: Bundle,
     {
-        self.commands.add(Remove::<T>::new(self.entity));
+        self.add_fallible(Remove::<T>::new(self.entity));
         self
     }
 
-    /// Despawns the entity.
+    /// Inserts `relation` on the entity, linking it to [`Relation::target`] and adding the entity
+    /// to the target's [`Relation::Sources`].
     ///
-    /// See [`World::despawn`] for more details.
+    /// See [`World::insert_relation`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if the entity or the target do not exist.
+    pub fn insert_relation<R: Relation>(&mut self, relation: R) -> &mut Self {
+        self.add(move |entity: Entity, world: &mut World| {
+            world.insert_relation(entity, relation);
+        })
+    }
+
+    /// Removes the relation `R` from the entity, and removes the entity from the sources of its target.
+    ///
+    /// See [`World::remove_relation`] for more details.
+    pub fn remove_relation<R: Relation>(&mut self) -> &mut Self {
+        self.add(|entity: Entity, world: &mut World| {
+            world.remove_relation::<R>(entity);
+        })
+    }
+
+    /// Spawns an observer of the event `E` that runs when `E` is triggered on this entity.
+    ///
+    /// See [`EntityMut::observe`](crate::world::EntityMut::observe) for more details.
     ///
     /// # Panics
     ///
     /// The command will panic when applied if the associated entity does not exist.
+    pub fn observe<E: Event, M>(
+        &mut self,
+        observer: impl IntoSystem<Trigger<E>, (), M>,
+    ) -> &mut Self {
+        let system: BoxedSystem<Trigger<E>> = Box::new(IntoSystem::into_system(observer));
+        let observer = self.commands.spawn_empty().id();
+        self.add(move |entity: Entity, world: &mut World| {
+            insert_observer(world, observer, Some(entity), system);
+        })
+    }
+
+    /// Despawns the entity.
+    ///
+    /// See [`World::despawn`] for more details.
+    ///
+    /// By default, a warning is logged if the associated entity does not exist when the command
+    /// is applied. See [`on_error`](Self::on_error) to handle the error differently.
     ///
     /// # Example
     ///
@@ -794,11 +932,71 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     /// # bevy_ecs::system::assert_is_system(remove_character_system);
     /// ```
     pub fn despawn(&mut self) {
-        self.commands.add(Despawn {
+        self.add_fallible(Despawn {
             entity: self.entity,
         });
     }
 
+    /// Spawns a clone of the entity with all its reflected components, and returns the
+    /// [`EntityCommands`] of the clone.
+    ///
+    /// See [`World::clone_entity`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// By default, the command will panic when applied if the associated entity does not exist.
+    /// See [`on_error`](Self::on_error) to handle the error differently.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn clone_entity(&mut self) -> EntityCommands<'w, 's, '_> {
+        self.clone_entity_with(EntityCloneOptions::default())
+    }
+
+    /// Spawns a clone of the entity with the components selected by `options`, and returns the
+    /// [`EntityCommands`] of the clone.
+    ///
+    /// See [`World::clone_entity_with`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// By default, the command will panic when applied if the associated entity does not exist.
+    /// See [`on_error`](Self::on_error) to handle the error differently. In that case the clone
+    /// is despawned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::world::EntityCloneOptions;
+    /// # #[derive(Resource)]
+    /// # struct Prefab { entity: Entity }
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// fn spawn_enemy(mut commands: Commands, prefab: Res<Prefab>) {
+    ///     commands
+    ///         .entity(prefab.entity)
+    ///         .clone_entity_with(EntityCloneOptions::new().deny::<Name>())
+    ///         .insert(Enemy);
+    /// }
+    /// # #[derive(Component)]
+    /// # struct Name;
+    /// # bevy_ecs::system::assert_is_system(spawn_enemy);
+    /// ```
+    #[cfg(feature = "bevy_reflect")]
+    pub fn clone_entity_with(&mut self, options: EntityCloneOptions) -> EntityCommands<'w, 's, '_> {
+        let clone = self.commands.spawn_empty().id();
+        self.add_fallible(CloneEntity {
+            source: self.entity,
+            destination: clone,
+            options,
+        });
+        EntityCommands {
+            entity: clone,
+            commands: self.commands,
+            error_handler: self.error_handler.clone(),
+        }
+    }
+
     /// Pushes an [`EntityCommand`] to the queue, which will get executed for the current [`Entity`].
     ///
     /// # Examples
@@ -824,9 +1022,10 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     ///
     /// # Panics
     ///
-    /// The command will panic when applied if the associated entity does not exist.
+    /// By default, the command will panic when applied if the associated entity does not exist.
+    /// See [`on_error`](Self::on_error) to handle the error differently.
     pub fn log_components(&mut self) {
-        self.commands.add(LogComponents {
+        self.add_fallible(LogComponents {
             entity: self.entity,
         });
     }
@@ -934,7 +1133,21 @@ pub struct Despawn {
 
 impl Command for Despawn {
     fn apply(self, world: &mut World) {
+        apply_fallible(self, world);
+    }
+}
+
+impl FallibleCommand for Despawn {
+    fn try_apply(self, world: &mut World) -> Result<(), CommandError> {
+        if world.get_entity(self.entity).is_none() {
+            return Err(CommandError::no_such_entity(self.entity, "despawn"));
+        }
         world.despawn(self.entity);
+        Ok(())
+    }
+
+    fn default_error_handler() -> CommandErrorHandler {
+        CommandErrorHandler::Warn
     }
 }
 
@@ -951,11 +1164,69 @@ where
     T: Bundle + 'static,
 {
     fn apply(self, world: &mut World) {
-        if let Some(mut entity) = world.get_entity_mut(self.entity) {
-            entity.insert(self.bundle);
-        } else {
-            panic!("error[B0003]: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World.", std::any::type_name::<T>(), self.entity);
+        apply_fallible(self, world);
+    }
+}
+
+impl<T> FallibleCommand for Insert<T>
+where
+    T: Bundle + 'static,
+{
+    fn try_apply(self, world: &mut World) -> Result<(), CommandError> {
+        let Some(mut entity) = world.get_entity_mut(self.entity) else {
+            return Err(CommandError::no_such_entity(
+                self.entity,
+                format!(
+                    "insert a bundle (of type `{}`) for",
+                    std::any::type_name::<T>()
+                ),
+            ));
+        };
+        entity.insert(self.bundle);
+        Ok(())
+    }
+}
+
+/// A [`Command`] that clones an entity into another.
+///
+/// If the source entity does not exist, the destination entity is despawned instead, as it
+/// would otherwise be left empty.
+///
+/// See [`World::clone_entity_into`] for more details.
+#[cfg(feature = "bevy_reflect")]
+pub struct CloneEntity {
+    /// The entity that will be cloned.
+    pub source: Entity,
+    /// The entity into which the components will be cloned.
+    pub destination: Entity,
+    /// The components that will be cloned.
+    pub options: EntityCloneOptions,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl Command for CloneEntity {
+    fn apply(self, world: &mut World) {
+        apply_fallible(self, world);
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl FallibleCommand for CloneEntity {
+    fn try_apply(self, world: &mut World) -> Result<(), CommandError> {
+        if world.get_entity(self.source).is_none() {
+            if let Some(destination) = world.get_entity_mut(self.destination) {
+                destination.despawn();
+            }
+            return Err(CommandError::no_such_entity(self.source, "clone"));
+        }
+        if world.get_entity(self.destination).is_none() {
+            return Err(CommandError::no_such_entity(
+                self.destination,
+                "clone an entity into",
+            ));
         }
+        world.clone_entity_into(self.source, self.destination, &self.options);
+        Ok(())
     }
 }
 
@@ -974,9 +1245,30 @@ where
     T: Bundle,
 {
     fn apply(self, world: &mut World) {
-        if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
-            entity_mut.remove::<T>();
-        }
+        apply_fallible(self, world);
+    }
+}
+
+impl<T> FallibleCommand for Remove<T>
+where
+    T: Bundle,
+{
+    fn try_apply(self, world: &mut World) -> Result<(), CommandError> {
+        let Some(mut entity_mut) = world.get_entity_mut(self.entity) else {
+            return Err(CommandError::no_such_entity(
+                self.entity,
+                format!(
+                    "remove a bundle (of type `{}`) from",
+                    std::any::type_name::<T>()
+                ),
+            ));
+        };
+        entity_mut.remove::<T>();
+        Ok(())
+    }
+
+    fn default_error_handler() -> CommandErrorHandler {
+        CommandErrorHandler::Ignore
     }
 }
 
@@ -1050,12 +1342,25 @@ pub struct LogComponents {
 
 impl Command for LogComponents {
     fn apply(self, world: &mut World) {
+        apply_fallible(self, world);
+    }
+}
+
+impl FallibleCommand for LogComponents {
+    fn try_apply(self, world: &mut World) -> Result<(), CommandError> {
+        if world.get_entity(self.entity).is_none() {
+            return Err(CommandError::no_such_entity(
+                self.entity,
+                "log the components of",
+            ));
+        }
         let debug_infos: Vec<_> = world
             .inspect_entity(self.entity)
             .into_iter()
             .map(|component_info| component_info.name())
             .collect();
         info!("Entity {:?}: {:?}", self.entity, debug_infos);
+        Ok(())
     }
 }
 
@@ -1065,12 +1370,15 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         component::Component,
-        system::{CommandQueue, Commands, Resource},
+        system::{
+            CommandError, CommandErrorHandler, CommandQueue, Commands, DefaultCommandErrorHandler,
+            Resource,
+        },
         world::World,
     };
     use std::sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     };
 
     #[derive(Component)]
@@ -1219,4 +1527,57 @@ mod tests {
         assert!(!world.contains_resource::<W<i32>>());
         assert!(world.contains_resource::<W<f64>>());
     }
+
+    #[test]
+    #[should_panic = "Could not insert a bundle (of type `bevy_ecs::system::commands::tests::W<u32>`) for entity"]
+    fn insert_on_despawned_entity_panics_by_default() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let entity = world.spawn_empty().id();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(entity).despawn();
+            commands.entity(entity).insert(W(1u32));
+        }
+        queue.apply(&mut world);
+    }
+
+    #[test]
+    fn command_error_handlers() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let collect_errors = {
+            let errors = errors.clone();
+            CommandErrorHandler::custom(move |error, _world| errors.lock().unwrap().push(error))
+        };
+        world.insert_resource(DefaultCommandErrorHandler(collect_errors));
+
+        let entity = world.spawn_empty().id();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(entity).despawn();
+            commands.entity(entity).insert(W(1u32)).remove::<W<u64>>();
+            // the handler given to the entity commands takes precedence
+            commands
+                .entity(entity)
+                .on_error(CommandErrorHandler::Ignore)
+                .insert(W(2u32))
+                .despawn();
+            commands.entity(entity).despawn();
+        }
+        queue.apply(&mut world);
+
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            &errors[0],
+            CommandError::NoSuchEntity { entity: e, action } if *e == entity && action.starts_with("insert")
+        ));
+        assert!(matches!(
+            &errors[1],
+            CommandError::NoSuchEntity { action, .. } if action.starts_with("remove")
+        ));
+        assert_eq!(errors[2], CommandError::no_such_entity(entity, "despawn"));
+    }
 }
@@ -115,6 +115,12 @@ where
         true
     }
 
+    #[inline]
+    fn has_deferred(&self) -> bool {
+        // exclusive systems have no deferred system parameters to be applied
+        false
+    }
+
     fn get_last_run(&self) -> Tick {
         self.system_meta.last_run
     }
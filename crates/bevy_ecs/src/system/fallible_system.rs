@@ -0,0 +1,412 @@
+use crate::{
+    self as bevy_ecs,
+    archetype::ArchetypeComponentId,
+    component::{ComponentId, Tick},
+    query::Access,
+    system::{IntoSystem, ReadOnlySystem, Resource, System},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+use bevy_utils::tracing::{error, warn};
+use std::{any::TypeId, borrow::Cow, sync::Arc};
+
+/// The error type of fallible systems, which any error returned by a system is converted into.
+pub type SystemError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Decides what happens when a fallible system returns an error.
+///
+/// Systems, including run conditions wrapped with [`fallible`], may return a [`Result`] instead
+/// of their usual output.
+/// When they return an `Err`, the error is passed to the handler along with the name of the
+/// system, and the system's output is the [`Default`] value of its `Ok` type: `()` for systems,
+/// and `false` for run conditions.
+///
+/// The handler of a system is the one set with [`Schedule::set_error_handler`] for the schedule
+/// the system is part of, or else the [`DefaultErrorHandler`] of the world, or else
+/// [`ErrorHandler::Panic`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::{query::QuerySingleError, system::ErrorHandler};
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn player_system(query: Query<Entity, With<Player>>) -> Result<(), QuerySingleError> {
+///     let player = query.get_single()?;
+///     println!("{player:?}");
+///     Ok(())
+/// }
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::new();
+/// schedule.set_error_handler(ErrorHandler::custom(|error, system| {
+///     println!("{system} failed: {error}");
+/// }));
+/// schedule.add_systems(player_system);
+/// // there is no player, but the error is handled instead of panicking
+/// schedule.run(&mut world);
+/// ```
+///
+/// [`Schedule::set_error_handler`]: crate::schedule::Schedule::set_error_handler
+/// [`QuerySingleError`]: crate::query::QuerySingleError
+#[derive(Clone, Default)]
+pub enum ErrorHandler {
+    /// Panics with the error.
+    #[default]
+    Panic,
+    /// Logs the error at the error level.
+    Log,
+    /// Logs the first error returned by each system at the warning level, and ignores the others.
+    WarnOnce,
+    /// Calls the given function with the error and the name of the system.
+    Custom(Arc<dyn Fn(SystemError, &str) + Send + Sync>),
+}
+
+impl ErrorHandler {
+    /// Creates an [`ErrorHandler::Custom`] handler calling `handler` with each error and the name
+    /// of the system that returned it.
+    pub fn custom(handler: impl Fn(SystemError, &str) + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(handler))
+    }
+}
+
+impl std::fmt::Debug for ErrorHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Panic => write!(f, "Panic"),
+            Self::Log => write!(f, "Log"),
+            Self::WarnOnce => write!(f, "WarnOnce"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// The [`ErrorHandler`] used by fallible systems of schedules that don't set their own handler.
+///
+/// Systems read this resource when they are initialized, so changing it doesn't affect the
+/// systems that were already initialized.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DefaultErrorHandler(pub ErrorHandler);
+
+/// A [`System`] that returns a [`Result`], wrapped so that its errors are passed to an
+/// [`ErrorHandler`].
+///
+/// Systems returning `Result<(), E>` are wrapped in a `FallibleSystem` when they are added to a
+/// schedule, including a [`PipeSystem`](crate::system::PipeSystem) ending with a fallible
+/// system. Other systems, like run conditions returning `Result<bool, E>`, are wrapped with
+/// [`fallible`].
+///
+/// Fallible systems are not wrapped when they are run with
+/// [`RunSystem`](crate::system::RunSystem), which returns their [`Result`] to the caller, or
+/// registered with [`World::register_system`], which only accepts them once wrapped with
+/// [`fallible`].
+pub struct FallibleSystem<S> {
+    system: S,
+    handler: ErrorHandler,
+    // whether `handler` was set with `set_error_handler`, instead of read from the world
+    handler_overridden: bool,
+    warned: bool,
+}
+
+impl<S> FallibleSystem<S> {
+    /// Wraps the fallible `system`.
+    pub fn new(system: S) -> Self {
+        Self {
+            system,
+            handler: ErrorHandler::default(),
+            handler_overridden: false,
+            warned: false,
+        }
+    }
+
+    fn handle_error<Out: Default>(&mut self, error: SystemError, name: &str) -> Out {
+        match &self.handler {
+            ErrorHandler::Panic => panic!("System {name} failed: {error}"),
+            ErrorHandler::Log => error!("System {name} failed: {error}"),
+            ErrorHandler::WarnOnce => {
+                if !self.warned {
+                    self.warned = true;
+                    warn!("System {name} failed: {error}");
+                }
+            }
+            ErrorHandler::Custom(handler) => handler(error, name),
+        }
+        Out::default()
+    }
+}
+
+impl<S, Out, E> System for FallibleSystem<S>
+where
+    S: System<Out = Result<Out, E>>,
+    Out: Default,
+    E: Into<SystemError>,
+{
+    type In = S::In;
+    type Out = Out;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.system.component_access()
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.system.archetype_component_access()
+    }
+
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    fn has_deferred(&self) -> bool {
+        self.system.has_deferred()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: Self::In, world: UnsafeWorldCell) -> Self::Out {
+        // SAFETY: The caller guarantees the same requirements for the wrapped system.
+        match unsafe { self.system.run_unsafe(input, world) } {
+            Ok(output) => output,
+            Err(error) => self.handle_error(error.into(), &self.system.name()),
+        }
+    }
+
+    fn run(&mut self, input: Self::In, world: &mut World) -> Self::Out {
+        match self.system.run(input, world) {
+            Ok(output) => output,
+            Err(error) => self.handle_error(error.into(), &self.system.name()),
+        }
+    }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        if !self.handler_overridden {
+            if let Some(default) = world.get_resource::<DefaultErrorHandler>() {
+                self.handler = default.0.clone();
+            }
+        }
+        self.system.initialize(world);
+    }
+
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.system.update_archetype_component_access(world);
+    }
+
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    fn default_system_sets(&self) -> Vec<Box<dyn crate::schedule::SystemSet>> {
+        self.system.default_system_sets()
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+
+    fn set_error_handler(&mut self, handler: &ErrorHandler) {
+        self.handler = handler.clone();
+        self.handler_overridden = true;
+        self.system.set_error_handler(handler);
+    }
+}
+
+// SAFETY: The wrapped system is read-only, and handling its errors doesn't access the world.
+unsafe impl<S, Out, E> ReadOnlySystem for FallibleSystem<S>
+where
+    S: ReadOnlySystem<Out = Result<Out, E>>,
+    Out: Default,
+    E: Into<SystemError>,
+{
+}
+
+/// Wraps `system`, which returns a [`Result`], in a [`FallibleSystem`] passing its errors to an
+/// [`ErrorHandler`].
+///
+/// Systems returning `Result<(), E>` are wrapped automatically when they are added to a schedule.
+/// This is needed for run conditions returning `Result<bool, E>`, and for fallible systems that
+/// are run in other ways, like [`World::register_system`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::fallible;
+/// #[derive(Resource)]
+/// struct Enabled(bool);
+///
+/// fn is_enabled(enabled: Option<Res<Enabled>>) -> Result<bool, String> {
+///     enabled.map(|enabled| enabled.0).ok_or_else(|| "`Enabled` is missing".to_string())
+/// }
+///
+/// # fn my_system() {}
+/// let mut schedule = Schedule::new();
+/// schedule.add_systems(my_system.run_if(fallible(is_enabled)));
+/// ```
+pub fn fallible<S, In, Out, E, Marker>(system: S) -> FallibleSystem<S::System>
+where
+    S: IntoSystem<In, Result<Out, E>, Marker>,
+    Out: Default,
+    E: Into<SystemError>,
+{
+    FallibleSystem::new(IntoSystem::into_system(system))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fallible, DefaultErrorHandler, ErrorHandler};
+    use crate::prelude::*;
+    use crate::{self as bevy_ecs, system::In};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    type Errors = Arc<Mutex<Vec<(String, String)>>>;
+
+    fn collect_errors(errors: &Errors) -> ErrorHandler {
+        let errors = errors.clone();
+        ErrorHandler::custom(move |error, system| {
+            let system = system.rsplit("::").next().unwrap().to_string();
+            errors.lock().unwrap().push((system, error.to_string()));
+        })
+    }
+
+    fn failing_system(mut counter: ResMut<Counter>) -> Result<(), String> {
+        counter.0 += 1;
+        Err(format!("failed {} times", counter.0))
+    }
+
+    #[test]
+    fn schedule_error_handler() {
+        let errors = Errors::default();
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::new();
+        schedule.set_error_handler(collect_errors(&errors));
+        schedule.add_systems(failing_system);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *errors.lock().unwrap(),
+            [
+                ("failing_system".to_string(), "failed 1 times".to_string()),
+                ("failing_system".to_string(), "failed 2 times".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic = "failed 1 times"]
+    fn panic_by_default() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(failing_system);
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn default_error_handler() {
+        let errors = Errors::default();
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.insert_resource(DefaultErrorHandler(collect_errors(&errors)));
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems(failing_system);
+        schedule.run(&mut world);
+        assert_eq!(errors.lock().unwrap().len(), 1);
+
+        // the schedule's own handler takes precedence, even for systems already initialized
+        schedule.set_error_handler(ErrorHandler::WarnOnce);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(errors.lock().unwrap().len(), 1);
+        assert_eq!(world.resource::<Counter>().0, 3);
+    }
+
+    #[test]
+    fn fallible_run_conditions() {
+        fn failing_condition() -> Result<bool, String> {
+            Err("condition failed".to_string())
+        }
+
+        fn count(mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        let errors = Errors::default();
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::new();
+        schedule.set_error_handler(collect_errors(&errors));
+        schedule.add_systems((
+            count.run_if(fallible(failing_condition)),
+            count.run_if(fallible(|| -> Result<bool, String> { Ok(true) })),
+            count.run_if(fallible(failing_condition).or_else(|| true)),
+        ));
+        schedule.run(&mut world);
+
+        // a failing condition evaluates to `false`
+        assert_eq!(world.resource::<Counter>().0, 2);
+        assert_eq!(errors.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn fallible_pipes_and_exclusive_systems() {
+        fn parse(In(input): In<&'static str>) -> Result<(), std::num::ParseIntError> {
+            input.parse::<u32>().map(|_| ())
+        }
+
+        fn exclusive(world: &mut World) -> Result<(), String> {
+            world.resource_mut::<Counter>().0 += 1;
+            Err("exclusive failed".to_string())
+        }
+
+        let errors = Errors::default();
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::new();
+        schedule.set_error_handler(collect_errors(&errors));
+        schedule.add_systems((
+            (|| "42").pipe(parse),
+            (|| "not a number").pipe(parse),
+            exclusive,
+        ));
+        schedule.run(&mut world);
+
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|(_, error)| error == "exclusive failed"));
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn registered_fallible_system() {
+        let errors = Errors::default();
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.insert_resource(DefaultErrorHandler(collect_errors(&errors)));
+
+        let id = world.register_system(fallible(failing_system));
+        world.run_system_by_id(id).unwrap();
+        world.run_system_by_id(id).unwrap();
+        assert_eq!(errors.lock().unwrap().len(), 2);
+    }
+}
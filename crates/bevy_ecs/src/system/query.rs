@@ -1,5 +1,5 @@
 use crate::{
-    component::{Component, Tick},
+    component::{Component, ComponentId, Tick},
     entity::Entity,
     query::{
         BatchingStrategy, QueryCombinationIter, QueryEntityError, QueryIter, QueryManyIter,
@@ -7,7 +7,7 @@ use crate::{
     },
     world::{unsafe_world_cell::UnsafeWorldCell, Mut},
 };
-use std::{any::TypeId, borrow::Borrow, fmt::Debug};
+use std::{any::TypeId, borrow::Borrow, cmp::Ordering, fmt::Debug};
 
 /// [System parameter] that provides selective access to the [`Component`] data stored in a [`World`].
 ///
@@ -399,6 +399,159 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Returns a [`QueryLens`] that can be used to get a query fetching `NewQ` from the entities
+    /// matched by this query.
+    ///
+    /// `NewQ` can only access components that this query accesses, and can only write components
+    /// that this query writes. This can be used to pass a query to a function expecting a
+    /// narrower query.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// # #[derive(Component)]
+    /// # struct Armor(u32);
+    /// #
+    /// fn report_health(query: Query<&Health>) {
+    ///     for health in &query {
+    ///         println!("{}", health.0);
+    ///     }
+    /// }
+    ///
+    /// fn system(mut query: Query<(&Health, &mut Armor)>) {
+    ///     let mut lens = query.transmute_lens::<&Health>();
+    ///     report_health(lens.query());
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    ///
+    /// The lens matches the entities that this query matches when it is created. Filters of this
+    /// query that aren't archetypal, like [`Changed`](crate::query::Changed), are not applied.
+    ///
+    /// # Panics
+    ///
+    /// - If `NewQ` accesses a component that this query doesn't access, or writes a component that
+    ///   this query only reads.
+    /// - If a component used by `NewQ` hasn't been initialized in the world.
+    pub fn transmute_lens<NewQ: WorldQuery>(&mut self) -> QueryLens<'_, NewQ> {
+        self.transmute_lens_filtered::<NewQ, ()>()
+    }
+
+    /// Returns a [`QueryLens`] that can be used to get a query fetching `NewQ` from the entities
+    /// matched by both this query and the `NewF` filter.
+    ///
+    /// See [`transmute_lens`](Self::transmute_lens) for more details.
+    pub fn transmute_lens_filtered<NewQ: WorldQuery, NewF: ReadOnlyWorldQuery>(
+        &mut self,
+    ) -> QueryLens<'_, NewQ, NewF> {
+        // SAFETY: `transmute_filtered` only reads world metadata.
+        let world = unsafe { self.world.world_metadata() };
+        let state = self.state.transmute_filtered::<NewQ, NewF>(world);
+        for id in state.component_access.access().writes() {
+            assert!(
+                self.lens_can_write(id),
+                "Transmuted state {} attempts to write a component that is only read by {}.",
+                std::any::type_name::<QueryState<NewQ, NewF>>(),
+                std::any::type_name::<Self>(),
+            );
+        }
+        QueryLens {
+            world: self.world,
+            state,
+            last_run: self.last_run,
+            this_run: self.this_run,
+        }
+    }
+
+    /// Returns a [`QueryLens`] that can be used to get a query fetching `NewQ` from the entities
+    /// matched by both this query and `other`.
+    ///
+    /// `NewQ` can only access components that one of the queries accesses, and can only write
+    /// components that one of the queries writes. This avoids calling [`get`](Self::get) on one
+    /// query for every item of the other.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Transform;
+    /// # #[derive(Component)]
+    /// # struct Player;
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// #
+    /// fn system(
+    ///     mut transforms: Query<&Transform>,
+    ///     mut enemies: Query<Entity, With<Enemy>>,
+    /// ) {
+    ///     let mut lens = transforms.join::<_, _, (Entity, &Transform)>(&mut enemies);
+    ///     for (enemy, transform) in lens.query().iter() {
+    ///         // ...
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    ///
+    /// The lens matches the entities that both queries match when it is created. Filters of either
+    /// query that aren't archetypal, like [`Changed`](crate::query::Changed), are not applied.
+    ///
+    /// # Panics
+    ///
+    /// - If `NewQ` accesses a component that neither query accesses, or writes a component that
+    ///   both queries only read.
+    /// - If a component used by `NewQ` hasn't been initialized in the world.
+    /// - If the queries come from different worlds.
+    pub fn join<'a, OtherQ: WorldQuery, OtherF: ReadOnlyWorldQuery, NewQ: WorldQuery>(
+        &'a mut self,
+        other: &'a mut Query<OtherQ, OtherF>,
+    ) -> QueryLens<'a, NewQ> {
+        self.join_filtered::<OtherQ, OtherF, NewQ, ()>(other)
+    }
+
+    /// Returns a [`QueryLens`] that can be used to get a query fetching `NewQ` from the entities
+    /// matched by this query, `other` and the `NewF` filter.
+    ///
+    /// See [`join`](Self::join) for more details.
+    pub fn join_filtered<
+        'a,
+        OtherQ: WorldQuery,
+        OtherF: ReadOnlyWorldQuery,
+        NewQ: WorldQuery,
+        NewF: ReadOnlyWorldQuery,
+    >(
+        &'a mut self,
+        other: &'a mut Query<OtherQ, OtherF>,
+    ) -> QueryLens<'a, NewQ, NewF> {
+        // SAFETY: `join_filtered` only reads world metadata.
+        let world = unsafe { self.world.world_metadata() };
+        let state = self
+            .state
+            .join_filtered::<OtherQ, OtherF, NewQ, NewF>(world, other.state);
+        for id in state.component_access.access().writes() {
+            assert!(
+                self.lens_can_write(id) || other.lens_can_write(id),
+                "Joined state {} attempts to write a component that is only read by {} and {}.",
+                std::any::type_name::<QueryState<NewQ, NewF>>(),
+                std::any::type_name::<Self>(),
+                std::any::type_name::<Query<OtherQ, OtherF>>(),
+            );
+        }
+        QueryLens {
+            world: self.world,
+            state,
+            last_run: self.last_run,
+            this_run: self.this_run,
+        }
+    }
+
+    /// Returns `true` if a lens derived from this query can write the component `id`.
+    fn lens_can_write(&self, id: ComponentId) -> bool {
+        // Queries made read-only by `to_readonly` keep the write access of the original query.
+        !self.force_read_only_component_access && self.state.component_access.access().has_write(id)
+    }
+
     /// Returns an [`Iterator`] over the read-only query items.
     ///
     /// # Example
@@ -468,6 +621,78 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the read-only query items, sorted by the key extracted from
+    /// each item by `f`.
+    ///
+    /// # Example
+    ///
+    /// Here, the `draw_system` draws the sprites from back to front:
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Sprite;
+    /// #
+    /// #[derive(Component)]
+    /// struct DrawOrder(i32);
+    ///
+    /// fn draw_system(query: Query<(&Sprite, &DrawOrder)>) {
+    ///     for (sprite, _) in query.iter_sorted_by_key(|(_, order)| order.0) {
+    ///         // draw the sprite
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(draw_system);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`iter_mut_sorted_by_key`](Self::iter_mut_sorted_by_key) for mutable query items.
+    /// - [`QueryIter::sort_by_key`] and the other sorting adapters of [`QueryIter`].
+    pub fn iter_sorted_by_key<'a, K: Ord>(
+        &'a self,
+        f: impl FnMut(&ROQueryItem<'a, Q>) -> K,
+    ) -> std::vec::IntoIter<ROQueryItem<'a, Q>> {
+        self.iter().sort_by_key(f)
+    }
+
+    /// Returns an [`Iterator`] over the query items, sorted by the key extracted from each item
+    /// by `f`.
+    ///
+    /// # See also
+    ///
+    /// - [`iter_sorted_by_key`](Self::iter_sorted_by_key) for read-only query items.
+    pub fn iter_mut_sorted_by_key<'a, K: Ord>(
+        &'a mut self,
+        f: impl FnMut(&Q::Item<'a>) -> K,
+    ) -> std::vec::IntoIter<Q::Item<'a>> {
+        self.iter_mut().sort_by_key(f)
+    }
+
+    /// Returns an [`Iterator`] over the read-only query items, sorted with the `compare` function.
+    ///
+    /// # See also
+    ///
+    /// - [`iter_mut_sorted_by`](Self::iter_mut_sorted_by) for mutable query items.
+    pub fn iter_sorted_by<'a>(
+        &'a self,
+        compare: impl FnMut(&ROQueryItem<'a, Q>, &ROQueryItem<'a, Q>) -> Ordering,
+    ) -> std::vec::IntoIter<ROQueryItem<'a, Q>> {
+        self.iter().sort_by(compare)
+    }
+
+    /// Returns an [`Iterator`] over the query items, sorted with the `compare` function.
+    ///
+    /// # See also
+    ///
+    /// - [`iter_sorted_by`](Self::iter_sorted_by) for read-only query items.
+    pub fn iter_mut_sorted_by<'a>(
+        &'a mut self,
+        compare: impl FnMut(&Q::Item<'a>, &Q::Item<'a>) -> Ordering,
+    ) -> std::vec::IntoIter<Q::Item<'a>> {
+        self.iter_mut().sort_by(compare)
+    }
+
     /// Returns a [`QueryCombinationIter`] over all combinations of `K` read-only query items without repetition.
     ///
     /// # Example
@@ -1427,6 +1652,25 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> IntoIterator for &'w mut Quer
     }
 }
 
+/// A narrower or combined view of one or two [`Query`]s, from which a [`Query`] can be obtained.
+///
+/// This struct is created by the [`Query::transmute_lens`] and [`Query::join`] methods.
+pub struct QueryLens<'w, Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
+    world: UnsafeWorldCell<'w>,
+    state: QueryState<Q, F>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryLens<'w, Q, F> {
+    /// Returns a [`Query`] over the entities of the lens.
+    pub fn query(&mut self) -> Query<'w, '_, Q, F> {
+        // SAFETY: The lens mutably borrows the queries it was derived from, and its state only
+        // accesses components that these queries have permission to access.
+        unsafe { Query::new(self.world, &self.state, self.last_run, self.this_run, false) }
+    }
+}
+
 /// An error that occurs when retrieving a specific [`Entity`]'s component from a [`Query`].
 #[derive(Debug, PartialEq, Eq)]
 pub enum QueryComponentError {
@@ -8,7 +8,7 @@ use crate::{archetype::ArchetypeComponentId, component::ComponentId, query::Acce
 use std::any::TypeId;
 use std::borrow::Cow;
 
-use super::IntoSystem;
+use super::{ErrorHandler, IntoSystem};
 
 /// An ECS system that can be added to a [`Schedule`](crate::schedule::Schedule)
 ///
@@ -41,6 +41,16 @@ pub trait System: Send + Sync + 'static {
     /// Returns true if the system must be run exclusively.
     fn is_exclusive(&self) -> bool;
 
+    /// Returns true if the system has [`Deferred`](crate::system::Deferred) system parameters
+    /// (like [`Commands`](crate::system::Commands)), which have to be applied with
+    /// [`apply_deferred`](System::apply_deferred).
+    ///
+    /// Conservatively returns `true` by default, so that automatic sync points are still inserted
+    /// after systems that don't override it.
+    fn has_deferred(&self) -> bool {
+        true
+    }
+
     /// Runs the system with the given input in the world. Unlike [`System::run`], this function
     /// can be called in parallel with other systems and may break Rust's aliasing rules
     /// if used incorrectly, making it unsafe to call.
@@ -105,6 +115,12 @@ pub trait System: Send + Sync + 'static {
     /// However, it can be an essential escape hatch when, for example,
     /// you are trying to synchronize representations using change detection and need to avoid infinite recursion.
     fn set_last_run(&mut self, last_run: Tick);
+
+    /// Sets the [`ErrorHandler`] receiving the errors of this system, if it is
+    /// [fallible](crate::system::FallibleSystem), or of the fallible systems it is made of.
+    ///
+    /// This is called by [`Schedule`](crate::schedule::Schedule)s that have their own handler.
+    fn set_error_handler(&mut self, _handler: &ErrorHandler) {}
 }
 
 /// [`System`] types that do not modify the [`World`] when run.
@@ -169,6 +185,9 @@ impl Debug for dyn System<In = (), Out = ()> {
 
 /// Trait used to run a system immediately on a [`World`].
 ///
+/// Unlike systems registered with [`World::register_system`], the system is initialized
+/// from scratch on every call, so any [`Local`](crate::system::Local) state is discarded.
+///
 /// # Warning
 /// This function is not an efficient method of running systems and its meant to be used as a utility
 /// for testing and/or diagnostics.
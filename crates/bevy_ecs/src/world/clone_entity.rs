@@ -0,0 +1,415 @@
+use std::any::TypeId;
+
+use bevy_utils::HashSet;
+
+use crate::{
+    component::Component,
+    entity::{Entity, EntityMap},
+    reflect::{AppTypeRegistry, ReflectCloneComponent, ReflectComponent, ReflectMapEntities},
+    relation::{Relation, RelationSources},
+    world::World,
+};
+
+/// Selects the components cloned by [`World::clone_entity_with`], and whether the related
+/// entities are cloned along with the entity.
+///
+/// By default, every component registered with [`ReflectComponent`] in the [`AppTypeRegistry`]
+/// is cloned, and no other entity is.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent};
+/// # use bevy_ecs::world::EntityCloneOptions;
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Selected;
+///
+/// let mut world = World::new();
+/// let registry = AppTypeRegistry::default();
+/// registry.write().register::<Health>();
+/// registry.write().register::<Selected>();
+/// world.insert_resource(registry);
+///
+/// let entity = world.spawn((Health(100), Selected)).id();
+/// let clone = world.clone_entity_with(entity, &EntityCloneOptions::new().deny::<Selected>());
+/// assert_eq!(world.get::<Health>(clone).unwrap().0, 100);
+/// assert!(!world.entity(clone).contains::<Selected>());
+/// ```
+#[derive(Clone, Default)]
+pub struct EntityCloneOptions {
+    allowed: Option<HashSet<TypeId>>,
+    denied: HashSet<TypeId>,
+    descendants: Option<Descendants>,
+}
+
+/// Returns the sources of a relation, which are cloned along with the cloned entity.
+type Descendants = fn(&mut World, Entity) -> Vec<Entity>;
+
+impl EntityCloneOptions {
+    /// Creates options cloning every reflected component, and no other entity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the component `T` to the allowlist.
+    ///
+    /// Once a component is allowed, only the allowed components are cloned.
+    pub fn allow<T: Component>(self) -> Self {
+        self.allow_by_id(TypeId::of::<T>())
+    }
+
+    /// Adds the component with the given [`TypeId`] to the allowlist.
+    ///
+    /// Once a component is allowed, only the allowed components are cloned.
+    pub fn allow_by_id(mut self, type_id: TypeId) -> Self {
+        self.allowed
+            .get_or_insert_with(HashSet::new)
+            .insert(type_id);
+        self
+    }
+
+    /// Adds the component `T` to the denylist, which prevents it from being cloned.
+    pub fn deny<T: Component>(self) -> Self {
+        self.deny_by_id(TypeId::of::<T>())
+    }
+
+    /// Adds the component with the given [`TypeId`] to the denylist, which prevents it from
+    /// being cloned.
+    pub fn deny_by_id(mut self, type_id: TypeId) -> Self {
+        self.denied.insert(type_id);
+        self
+    }
+
+    /// Returns `true` if the component with the given [`TypeId`] is cloned with these options.
+    pub fn is_allowed_by_id(&self, type_id: TypeId) -> bool {
+        let allowed = match &self.allowed {
+            Some(allowed) => allowed.contains(&type_id),
+            None => true,
+        };
+        allowed && !self.denied.contains(&type_id)
+    }
+
+    /// Also clones the [sources](Relation::Sources) of the relation `R`, recursively.
+    ///
+    /// For example, `with_descendants::<Parent>()` clones the whole `Children` subtree of the
+    /// entity, with the relations between the clones pointing at the clones.
+    pub fn with_descendants<R: Relation>(mut self) -> Self {
+        self.descendants = Some(|world, entity| {
+            // the sources of the clones are rebuilt from their relations
+            world.init_relation::<R>();
+            world
+                .get::<R::Sources>(entity)
+                .map(|sources| sources.sources().to_vec())
+                .unwrap_or_default()
+        });
+        self
+    }
+}
+
+impl World {
+    /// Spawns a clone of `entity` with all its reflected components, and returns its id.
+    ///
+    /// See [`World::clone_entity_with`] for more details.
+    pub fn clone_entity(&mut self, entity: Entity) -> Entity {
+        self.clone_entity_with(entity, &EntityCloneOptions::default())
+    }
+
+    /// Spawns a clone of `entity` with the components selected by `options`, and returns its id.
+    ///
+    /// See [`World::clone_entity_into`] for more details.
+    pub fn clone_entity_with(&mut self, entity: Entity, options: &EntityCloneOptions) -> Entity {
+        let clone = self.spawn_empty().id();
+        self.clone_entity_into(entity, clone, options);
+        clone
+    }
+
+    /// Inserts clones of the components of `source` selected by `options` into `destination`.
+    ///
+    /// The components are looked up in the [`AppTypeRegistry`], and cloned through their
+    /// [`ReflectCloneComponent`] if it is registered, or else through their
+    /// [`ReflectComponent`]. Other components are not cloned.
+    ///
+    /// The entity references of the clones are then updated with the [`ReflectMapEntities`] of
+    /// their components, so that the references to the cloned entities point at their clones.
+    /// References to other entities are left unchanged: for example, the clone of an entity
+    /// with a `Parent` is a sibling of the entity.
+    ///
+    /// The clones are linked to the targets of their [registered relations](World::init_relation)
+    /// like with [`World::insert_relation`], and the [`Relation::Sources`] components of these
+    /// relations are not cloned, so that the clone of a target only has the cloned sources, cloned
+    /// with [`EntityCloneOptions::with_descendants`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or `destination` do not exist, or if the world has no
+    /// [`AppTypeRegistry`].
+    pub fn clone_entity_into(
+        &mut self,
+        source: Entity,
+        destination: Entity,
+        options: &EntityCloneOptions,
+    ) {
+        assert!(
+            self.get_entity(source).is_some(),
+            "Could not clone entity {source:?} because it doesn't exist in this World."
+        );
+        assert!(
+            self.get_entity(destination).is_some(),
+            "Could not clone an entity into {destination:?} because it doesn't exist in this World."
+        );
+        let registry = self.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let mut entity_map = EntityMap::default();
+        entity_map.insert(source, destination);
+        let mut clones = vec![(source, destination)];
+        if let Some(descendants) = options.descendants {
+            let mut i = 0;
+            while let Some(&(entity, _)) = clones.get(i) {
+                for descendant in descendants(self, entity) {
+                    if entity_map.get(descendant).is_none() && self.get_entity(descendant).is_some()
+                    {
+                        let clone = self.spawn_empty().id();
+                        entity_map.insert(descendant, clone);
+                        clones.push((descendant, clone));
+                    }
+                }
+                i += 1;
+            }
+        }
+
+        for &(entity, clone) in &clones {
+            // the sources of the relations are rebuilt when the clones are linked to their targets
+            let type_ids: Vec<_> = self
+                .inspect_entity(entity)
+                .into_iter()
+                .filter(|info| !self.relations.contains_sources(info.id()))
+                .filter_map(|info| info.type_id())
+                .filter(|type_id| options.is_allowed_by_id(*type_id))
+                .collect();
+            for type_id in type_ids {
+                let Some(registration) = registry.get(type_id) else {
+                    continue;
+                };
+                if let Some(clone_component) = registration.data::<ReflectCloneComponent>() {
+                    clone_component.clone_component(self, entity, clone);
+                } else if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                    let Some(component) = reflect_component
+                        .reflect(self.entity(entity))
+                        .map(|component| component.clone_value())
+                    else {
+                        continue;
+                    };
+                    reflect_component.insert(&mut self.entity_mut(clone), &*component);
+                }
+            }
+        }
+
+        let clones: Vec<_> = clones.into_iter().map(|(_, clone)| clone).collect();
+        // the clones were linked to the targets of the originals when their relations were
+        // inserted, so they are moved to the clones of these targets
+        self.remap_relations(&clones, |world| {
+            for registration in registry.iter() {
+                if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+                    map_entities.map_entities_preserving_unmapped(world, &mut entity_map, &clones);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntityCloneOptions;
+    use crate as bevy_ecs;
+    use crate::{
+        entity::{Entity, EntityMapper, MapEntities},
+        prelude::*,
+        reflect::{AppTypeRegistry, ReflectCloneComponent, ReflectComponent, ReflectMapEntities},
+        relation::{Relation, Sources},
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct A(u32);
+
+    #[derive(Component, Reflect, Default, Clone, Debug, PartialEq)]
+    #[reflect(Component, CloneComponent)]
+    struct B(String);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Option<Entity>);
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            self.0 = self.0.map(|entity| entity_mapper.get_or_reserve(entity));
+        }
+    }
+
+    #[derive(Component)]
+    struct NotReflected;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct ChildOf(Entity);
+
+    impl FromWorld for ChildOf {
+        fn from_world(_world: &mut World) -> Self {
+            ChildOf(Entity::PLACEHOLDER)
+        }
+    }
+
+    impl MapEntities for ChildOf {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            self.0 = entity_mapper.get_or_reserve(self.0);
+        }
+    }
+
+    impl Relation for ChildOf {
+        type Sources = Sources<ChildOf>;
+
+        fn target(&self) -> Entity {
+            self.0
+        }
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<A>();
+            registry.register::<B>();
+            registry.register::<Target>();
+            registry.register::<ChildOf>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn clone_entity() {
+        let mut world = world();
+        let other = world.spawn_empty().id();
+        let entity = world
+            .spawn((A(1), B("b".to_string()), Target(Some(other)), NotReflected))
+            .id();
+
+        let clone = world.clone_entity(entity);
+        assert_ne!(clone, entity);
+        assert_eq!(world.get::<A>(clone), Some(&A(1)));
+        assert_eq!(world.get::<B>(clone), Some(&B("b".to_string())));
+        // references to entities that aren't cloned are left unchanged
+        assert_eq!(world.get::<Target>(clone), Some(&Target(Some(other))));
+        assert!(!world.entity(clone).contains::<NotReflected>());
+        assert_eq!(world.get::<A>(entity), Some(&A(1)));
+    }
+
+    #[test]
+    fn filters() {
+        let mut world = world();
+        let entity = world.spawn((A(1), B("b".to_string()))).id();
+
+        let clone = world.clone_entity_with(entity, &EntityCloneOptions::new().allow::<A>());
+        assert!(world.entity(clone).contains::<A>());
+        assert!(!world.entity(clone).contains::<B>());
+
+        let options = EntityCloneOptions::new().allow::<A>().deny::<A>();
+        let clone = world.clone_entity_with(entity, &options);
+        assert_eq!(world.entity(clone).archetype().components().count(), 0);
+    }
+
+    #[test]
+    fn clone_descendants() {
+        let mut world = world();
+        let parent = world.spawn_empty().id();
+        let root = world.spawn(A(0)).insert_relation(ChildOf(parent)).id();
+        let child = world.spawn(A(1)).insert_relation(ChildOf(root)).id();
+        let grandchild = world
+            .spawn((A(2), Target(Some(root))))
+            .insert_relation(ChildOf(child))
+            .id();
+
+        // without descendants, the sources aren't cloned
+        let clone = world.clone_entity(root);
+        assert!(!world.entity(clone).contains::<Sources<ChildOf>>());
+        assert_eq!(world.get::<ChildOf>(clone), Some(&ChildOf(parent)));
+
+        let options = EntityCloneOptions::new().with_descendants::<ChildOf>();
+        let root_clone = world.clone_entity_with(root, &options);
+        assert_eq!(world.get::<A>(root_clone), Some(&A(0)));
+        assert_eq!(world.get::<ChildOf>(root_clone), Some(&ChildOf(parent)));
+        assert_eq!(
+            world.get::<Sources<ChildOf>>(parent).unwrap().sources(),
+            &[root, clone, root_clone]
+        );
+
+        let &[child_clone] = world.get::<Sources<ChildOf>>(root_clone).unwrap().sources() else {
+            panic!("the root clone should have a single child");
+        };
+        assert_ne!(child_clone, child);
+        assert_eq!(world.get::<A>(child_clone), Some(&A(1)));
+        assert_eq!(
+            world.get::<ChildOf>(child_clone),
+            Some(&ChildOf(root_clone))
+        );
+
+        let &[grandchild_clone] = world
+            .get::<Sources<ChildOf>>(child_clone)
+            .unwrap()
+            .sources()
+        else {
+            panic!("the child clone should have a single child");
+        };
+        assert_ne!(grandchild_clone, grandchild);
+        assert_eq!(
+            world.get::<Target>(grandchild_clone),
+            Some(&Target(Some(root_clone)))
+        );
+        // the originals are untouched
+        assert_eq!(
+            world.get::<Sources<ChildOf>>(root).unwrap().sources(),
+            &[child]
+        );
+    }
+
+    #[test]
+    fn clone_entity_command() {
+        let mut world = world();
+        let entity = world.spawn(A(1)).id();
+
+        let mut queue = crate::system::CommandQueue::default();
+        let clone = Commands::new(&mut queue, &world)
+            .entity(entity)
+            .clone_entity()
+            .id();
+        queue.apply(&mut world);
+        assert_eq!(world.get::<A>(clone), Some(&A(1)));
+    }
+
+    #[test]
+    fn clone_missing_entity_command() {
+        let mut world = world();
+        let entity = world.spawn(A(1)).id();
+
+        let mut queue = crate::system::CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(entity).despawn();
+        let clone = commands
+            .entity(entity)
+            .on_error(crate::system::CommandErrorHandler::Ignore)
+            .clone_entity()
+            .id();
+        queue.apply(&mut world);
+        // the clone reserved by the command is not left behind empty
+        assert!(world.get_entity(clone).is_none());
+        assert_eq!(world.entities().len(), 0);
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleInfo, BundleInserter, DynamicBundle},
+    bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
     change_detection::MutUntyped,
-    component::{Component, ComponentId, ComponentTicks, Components, StorageType},
+    component::{
+        Component, ComponentHook, ComponentHooks, ComponentId, ComponentTicks, Components,
+        StorageType,
+    },
     entity::{Entities, Entity, EntityLocation},
+    query::DebugCheckedUnwrap,
     removal_detection::RemovedComponentEvents,
     storage::Storages,
     world::{Mut, World},
@@ -326,10 +330,15 @@ impl<'w> EntityMut<'w> {
     /// This will overwrite any previous value(s) of the same component type.
     pub fn insert<T: Bundle>(&mut self, bundle: T) -> &mut Self {
         let change_tick = self.world.change_tick();
-        let bundle_info = self
+        let bundle_id = self
             .world
             .bundles
-            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        self.trigger_replace_hooks(bundle_id);
+        // SAFETY: the bundle was initialized above.
+        let bundle_info = unsafe { self.world.bundles.get(bundle_id).debug_checked_unwrap() };
+        let old_archetype_id = self.location.archetype_id;
         let mut bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
@@ -342,6 +351,8 @@ impl<'w> EntityMut<'w> {
         unsafe {
             self.location = bundle_inserter.insert(self.entity, self.location, bundle);
         }
+        self.trigger_insert_hooks(bundle_id, old_archetype_id);
+        self.report_invariant_violations();
 
         self
     }
@@ -363,10 +374,19 @@ impl<'w> EntityMut<'w> {
     ) -> &mut Self {
         let change_tick = self.world.change_tick();
 
+        let bundle_id = self
+            .world
+            .bundles
+            .init_component_info(&mut self.world.components, component_id)
+            .0
+            .id();
+        self.trigger_replace_hooks(bundle_id);
+
         let bundles = &mut self.world.bundles;
         let components = &mut self.world.components;
 
         let (bundle_info, storage_type) = bundles.init_component_info(components, component_id);
+        let old_archetype_id = self.location.archetype_id;
         let bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
@@ -383,6 +403,8 @@ impl<'w> EntityMut<'w> {
             Some(component).into_iter(),
             Some(storage_type).into_iter(),
         );
+        self.trigger_insert_hooks(bundle_id, old_archetype_id);
+        self.report_invariant_violations();
 
         self
     }
@@ -406,10 +428,19 @@ impl<'w> EntityMut<'w> {
     ) -> &mut Self {
         let change_tick = self.world.change_tick();
 
+        let bundle_id = self
+            .world
+            .bundles
+            .init_dynamic_info(&mut self.world.components, component_ids)
+            .0
+            .id();
+        self.trigger_replace_hooks(bundle_id);
+
         let bundles = &mut self.world.bundles;
         let components = &mut self.world.components;
 
         let (bundle_info, storage_types) = bundles.init_dynamic_info(components, component_ids);
+        let old_archetype_id = self.location.archetype_id;
         let bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
@@ -426,6 +457,8 @@ impl<'w> EntityMut<'w> {
             iter_components,
             storage_types.iter().cloned(),
         );
+        self.trigger_insert_hooks(bundle_id, old_archetype_id);
+        self.report_invariant_violations();
 
         self
     }
@@ -437,6 +470,13 @@ impl<'w> EntityMut<'w> {
     // TODO: BundleRemover?
     #[must_use]
     pub fn take<T: Bundle>(&mut self) -> Option<T> {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        self.trigger_remove_hooks(bundle_id, true);
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -497,6 +537,7 @@ impl<'w> EntityMut<'w> {
                 new_archetype_id,
             );
         }
+        self.report_invariant_violations();
 
         Some(result)
     }
@@ -585,6 +626,13 @@ impl<'w> EntityMut<'w> {
     /// Removes any components in the [`Bundle`] from the entity.
     // TODO: BundleRemover?
     pub fn remove<T: Bundle>(&mut self) -> &mut Self {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        self.trigger_remove_hooks(bundle_id, false);
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -643,13 +691,45 @@ impl<'w> EntityMut<'w> {
                 new_archetype_id,
             );
         }
+        self.report_invariant_violations();
 
         self
     }
 
+    /// Reports the [`ArchetypeInvariant`](crate::archetype::ArchetypeInvariant)s violated by the
+    /// archetype this entity has been moved to.
+    pub(crate) fn report_invariant_violations(&self) {
+        self.world.archetypes.report_invariant_violations(
+            self.entity,
+            self.location.archetype_id,
+            &self.world.components,
+        );
+    }
+
     /// Despawns the current entity.
-    pub fn despawn(self) {
+    pub fn despawn(mut self) {
         debug!("Despawning entity {:?}", self.entity);
+        if self.world.components.has_hooks() {
+            let archetype = &self.world.archetypes[self.location.archetype_id];
+            let components = &self.world.components;
+            let mut hooks = Vec::new();
+            collect_hooks(
+                components,
+                archetype.components(),
+                |h| &h.on_replace,
+                &mut hooks,
+            );
+            collect_hooks(
+                components,
+                archetype.components(),
+                |h| &h.on_remove,
+                &mut hooks,
+            );
+            // The entity may have been despawned by one of the hooks, e.g. through a relation cycle.
+            if !self.run_hooks(hooks) {
+                return;
+            }
+        }
         let world = self.world;
         world.flush();
         let location = world
@@ -789,6 +869,153 @@ impl<'w> EntityMut<'w> {
     pub fn update_location(&mut self) {
         self.location = self.world.entities().get(self.entity).unwrap();
     }
+
+    /// Runs the `on_add` hooks of the components in the bundle that were not present in
+    /// `old_archetype_id`, followed by the `on_insert` hooks of every component in the bundle.
+    ///
+    /// Required components of the bundle count as part of it if they were added.
+    pub(crate) fn trigger_insert_hooks(
+        &mut self,
+        bundle_id: BundleId,
+        old_archetype_id: ArchetypeId,
+    ) {
+        if !self.world.components.has_hooks() {
+            return;
+        }
+        let components = &self.world.components;
+        let old_archetype = &self.world.archetypes[old_archetype_id];
+        // SAFETY: the bundle was initialized by the caller.
+        let bundle_info = unsafe { self.world.bundles.get(bundle_id).debug_checked_unwrap() };
+        let is_added = |id: &&ComponentId| !old_archetype.contains(**id);
+        let added_required_components = bundle_info.required_components().iter().filter(is_added);
+        let added_components = bundle_info
+            .components()
+            .iter()
+            .filter(is_added)
+            .chain(added_required_components.clone());
+        let inserted_components = bundle_info
+            .components()
+            .iter()
+            .chain(added_required_components);
+        let mut hooks = Vec::new();
+        collect_hooks(
+            components,
+            added_components.copied(),
+            |h| &h.on_add,
+            &mut hooks,
+        );
+        collect_hooks(
+            components,
+            inserted_components.copied(),
+            |h| &h.on_insert,
+            &mut hooks,
+        );
+        if !self.run_hooks(hooks) {
+            panic!(
+                "Entity {:?} was despawned by a component hook while inserting components.",
+                self.entity
+            );
+        }
+    }
+
+    /// Runs the `on_replace` hooks of the components in the bundle that are present on the entity,
+    /// before their values are overwritten.
+    ///
+    /// Required components of the bundle are not overwritten, so their hooks don't run.
+    fn trigger_replace_hooks(&mut self, bundle_id: BundleId) {
+        if !self.world.components.has_hooks() {
+            return;
+        }
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        // SAFETY: the bundle was initialized by the caller.
+        let bundle_info = unsafe { self.world.bundles.get(bundle_id).debug_checked_unwrap() };
+        let replaced_components = bundle_info
+            .components()
+            .iter()
+            .copied()
+            .filter(|&id| archetype.contains(id));
+        let mut hooks = Vec::new();
+        collect_hooks(
+            &self.world.components,
+            replaced_components,
+            |h| &h.on_replace,
+            &mut hooks,
+        );
+        if !self.run_hooks(hooks) {
+            panic!(
+                "Entity {:?} was despawned by a component hook while inserting components.",
+                self.entity
+            );
+        }
+    }
+
+    /// Runs the `on_replace` hooks of the components in the bundle that are present on the
+    /// entity, followed by their `on_remove` hooks.
+    ///
+    /// If `require_all` is `true`, no hooks run unless the entity has every component in the bundle.
+    fn trigger_remove_hooks(&mut self, bundle_id: BundleId, require_all: bool) {
+        if !self.world.components.has_hooks() {
+            return;
+        }
+        let components = &self.world.components;
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        // SAFETY: the bundle was initialized by the caller.
+        let bundle_info = unsafe { self.world.bundles.get(bundle_id).debug_checked_unwrap() };
+        let bundle_components = bundle_info.components();
+        if require_all && !bundle_components.iter().all(|&id| archetype.contains(id)) {
+            return;
+        }
+        let removed_components = || {
+            bundle_components
+                .iter()
+                .copied()
+                .filter(|&id| archetype.contains(id))
+        };
+        let mut hooks = Vec::new();
+        collect_hooks(
+            components,
+            removed_components(),
+            |h| &h.on_replace,
+            &mut hooks,
+        );
+        collect_hooks(
+            components,
+            removed_components(),
+            |h| &h.on_remove,
+            &mut hooks,
+        );
+        if !self.run_hooks(hooks) {
+            panic!(
+                "Entity {:?} was despawned by a component hook while removing components.",
+                self.entity
+            );
+        }
+    }
+
+    /// Runs the given component hooks for this entity, skipping hooks of components which were
+    /// removed by an earlier hook, and refreshes the entity's location afterwards.
+    ///
+    /// Returns `false` if the entity was despawned by one of the hooks.
+    fn run_hooks(&mut self, hooks: Vec<(ComponentHook, ComponentId)>) -> bool {
+        if hooks.is_empty() {
+            return true;
+        }
+        for (hook, component_id) in hooks {
+            let Some(location) = self.world.entities.get(self.entity) else {
+                return false;
+            };
+            if self.world.archetypes[location.archetype_id].contains(component_id) {
+                hook(self.world, self.entity, component_id);
+            }
+        }
+        match self.world.entities.get(self.entity) {
+            Some(location) => {
+                self.location = location;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl<'w> EntityMut<'w> {
@@ -825,6 +1052,20 @@ impl<'w> EntityMut<'w> {
     }
 }
 
+/// Appends the hooks of the kind selected by `kind` of each of the given components to `hooks`.
+fn collect_hooks(
+    components: &Components,
+    component_ids: impl IntoIterator<Item = ComponentId>,
+    kind: fn(&ComponentHooks) -> &Vec<ComponentHook>,
+    hooks: &mut Vec<(ComponentHook, ComponentId)>,
+) {
+    for id in component_ids {
+        if let Some(info) = components.get_info(id) {
+            hooks.extend(kind(info.hooks()).iter().map(|&hook| (hook, id)));
+        }
+    }
+}
+
 /// Inserts a dynamic [`Bundle`] into the entity.
 ///
 /// # Safety
@@ -1369,4 +1610,99 @@ mod tests {
 
         assert_eq!(dynamic_components, static_components);
     }
+
+    #[derive(Resource, Default)]
+    struct HookLog(Vec<&'static str>);
+
+    fn hook_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<HookLog>();
+        world
+            .register_component_hooks::<TestComponent>()
+            .on_add(|world, _, _| world.resource_mut::<HookLog>().0.push("add"))
+            .on_insert(|world, _, _| world.resource_mut::<HookLog>().0.push("insert"))
+            .on_replace(|world, entity, _| {
+                // The previous value can still be read while its `on_replace` hook runs.
+                assert!(world.get::<TestComponent>(entity).is_some());
+                world.resource_mut::<HookLog>().0.push("replace");
+            })
+            .on_remove(|world, entity, _| {
+                // The component can still be read while its `on_remove` hook runs.
+                assert!(world.get::<TestComponent>(entity).is_some());
+                world.resource_mut::<HookLog>().0.push("remove");
+            });
+        world
+    }
+
+    #[test]
+    fn component_hooks_order() {
+        let mut world = hook_world();
+
+        let entity = world.spawn(TestComponent(0)).id();
+        world.entity_mut(entity).insert(TestComponent(1));
+        world.entity_mut(entity).remove::<TestComponent>();
+        world.entity_mut(entity).insert(TestComponent(2));
+        let _ = world.entity_mut(entity).take::<TestComponent>();
+        world.entity_mut(entity).insert(TestComponent(3));
+        world.despawn(entity);
+
+        assert_eq!(
+            world.resource::<HookLog>().0,
+            [
+                "add", "insert", "replace", "insert", "replace", "remove", "add", "insert",
+                "replace", "remove", "add", "insert", "replace", "remove"
+            ]
+        );
+    }
+
+    #[test]
+    fn component_hooks_compose() {
+        let mut world = hook_world();
+        world
+            .register_component_hooks::<TestComponent>()
+            .on_add(|world, _, _| world.resource_mut::<HookLog>().0.push("second add"));
+
+        world.spawn(TestComponent(0));
+        assert_eq!(
+            world.resource::<HookLog>().0,
+            ["add", "second add", "insert"]
+        );
+    }
+
+    #[test]
+    fn component_hooks_batch_spawn() {
+        let mut world = hook_world();
+
+        world.spawn_batch([TestComponent(0), TestComponent(1)]);
+        let entity = world.spawn_empty().id();
+        world
+            .insert_or_spawn_batch([(entity, TestComponent(2))])
+            .unwrap();
+
+        assert_eq!(
+            world.resource::<HookLog>().0,
+            ["add", "insert", "add", "insert", "add", "insert"]
+        );
+    }
+
+    #[test]
+    fn component_hooks_can_modify_world() {
+        #[derive(Component)]
+        struct Marker;
+
+        let mut world = World::new();
+        world
+            .register_component_hooks::<TestComponent>()
+            .on_add(|world, entity, _| {
+                world.entity_mut(entity).insert(Marker);
+            })
+            .on_remove(|world, entity, _| {
+                world.entity_mut(entity).remove::<Marker>();
+            });
+
+        let entity = world.spawn(TestComponent(0)).id();
+        assert!(world.entity(entity).contains::<Marker>());
+        world.entity_mut(entity).remove::<TestComponent>();
+        assert!(!world.entity(entity).contains::<Marker>());
+    }
 }
@@ -1,24 +1,38 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+#[cfg(feature = "bevy_reflect")]
+mod clone_entity;
 mod entity_ref;
 pub mod error;
+mod snapshot;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 mod world_cell;
 
 pub use crate::change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD};
+#[cfg(feature = "bevy_reflect")]
+pub use clone_entity::EntityCloneOptions;
 pub use entity_ref::{EntityMut, EntityRef};
+pub use snapshot::{SnapshotConfig, WorldSnapshot};
 pub use spawn_batch::*;
 pub use world_cell::*;
 
 use crate::{
-    archetype::{ArchetypeComponentId, ArchetypeId, ArchetypeRow, Archetypes},
+    archetype::{
+        Archetype, ArchetypeComponentId, ArchetypeId, ArchetypeInvariant, ArchetypeInvariantPolicy,
+        ArchetypeRow, Archetypes,
+    },
     bundle::{Bundle, BundleInserter, BundleSpawner, Bundles},
     change_detection::{MutUntyped, TicksMut},
-    component::{Component, ComponentDescriptor, ComponentId, ComponentInfo, Components, Tick},
-    entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
+    component::{
+        Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, Components,
+        Tick,
+    },
+    entity::{AllocAtWithoutReplacement, Disabled, Entities, Entity, EntityLocation},
     event::{Event, Events},
+    observer::Observers,
     query::{DebugCheckedUnwrap, QueryState, ReadOnlyWorldQuery, WorldQuery},
+    relation::Relations,
     removal_detection::RemovedComponentEvents,
     schedule::{Schedule, ScheduleLabel, Schedules},
     storage::{ResourceData, Storages},
@@ -65,6 +79,10 @@ pub struct World {
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
     pub(crate) removed_components: RemovedComponentEvents,
+    pub(crate) relations: Relations,
+    pub(crate) observers: Observers,
+    /// The id of [`Disabled`], initialized when the world is created.
+    pub(crate) disabled_component: ComponentId,
     /// Access cache used by [`WorldCell`]. Is only accessed in the `Drop` impl of `WorldCell`.
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
     pub(crate) change_tick: AtomicU32,
@@ -74,14 +92,20 @@ pub struct World {
 
 impl Default for World {
     fn default() -> Self {
+        let mut components = Components::default();
+        let mut storages = Storages::default();
+        let disabled_component = components.init_component::<Disabled>(&mut storages);
         Self {
             id: WorldId::new().expect("More `bevy` `World`s have been created than is supported"),
             entities: Entities::new(),
-            components: Default::default(),
+            components,
             archetypes: Archetypes::new(),
-            storages: Default::default(),
+            storages,
             bundles: Default::default(),
             removed_components: Default::default(),
+            relations: Default::default(),
+            observers: Default::default(),
+            disabled_component,
             archetype_component_access: Default::default(),
             // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
             // are detected on first system runs and for direct world queries.
@@ -175,6 +199,30 @@ impl World {
         WorldCell::new(self)
     }
 
+    /// Adds an [`ArchetypeInvariant`] that every archetype of this world must satisfy.
+    ///
+    /// The invariant is checked against the existing archetypes, whose entities are reported
+    /// right away, and then whenever a new archetype is created. Every entity moved to an
+    /// archetype violating it is reported according to the [`ArchetypeInvariantPolicy`] of the
+    /// world, once the hooks of the structural change have run.
+    pub fn add_archetype_invariant(&mut self, invariant: ArchetypeInvariant) {
+        for archetype_id in self.archetypes.add_invariant(invariant) {
+            for entity in self.archetypes[archetype_id].entities() {
+                self.archetypes.report_invariant_violations(
+                    entity.entity(),
+                    archetype_id,
+                    &self.components,
+                );
+            }
+        }
+    }
+
+    /// Sets what happens when an entity is moved to an archetype violating one of the
+    /// [`ArchetypeInvariant`]s of this world. Defaults to [`ArchetypeInvariantPolicy::Panic`].
+    pub fn set_archetype_invariant_policy(&mut self, policy: ArchetypeInvariantPolicy) {
+        self.archetypes.set_invariant_policy(policy);
+    }
+
     /// Initializes a new [`Component`] type and returns the [`ComponentId`] created for it.
     pub fn init_component<T: Component>(&mut self) -> ComponentId {
         self.components.init_component::<T>(&mut self.storages)
@@ -197,6 +245,53 @@ impl World {
             .init_component_with_descriptor(&mut self.storages, descriptor)
     }
 
+    /// Returns a mutable reference to the [`ComponentHooks`] for a [`Component`] type,
+    /// initializing the component if needed.
+    ///
+    /// Hooks run synchronously inside [`World`] and [`EntityMut`] operations that add, insert,
+    /// replace or remove the component, including [`World::spawn`] and [`World::despawn`]. They
+    /// are given exclusive access to the world, and may freely read and modify it, but must not
+    /// despawn the entity they run for while it is being inserted to or removed from.
+    ///
+    /// Registering a hook never replaces the hooks already registered for the component, so
+    /// several features can hook the same component independently.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Resource, Default)]
+    /// struct Alive(usize);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Alive>();
+    /// world
+    ///     .register_component_hooks::<Health>()
+    ///     .on_add(|world, _, _| world.resource_mut::<Alive>().0 += 1)
+    ///     .on_remove(|world, _, _| world.resource_mut::<Alive>().0 -= 1);
+    ///
+    /// let entity = world.spawn(Health(10)).id();
+    /// assert_eq!(world.resource::<Alive>().0, 1);
+    /// world.despawn(entity);
+    /// assert_eq!(world.resource::<Alive>().0, 0);
+    /// ```
+    pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
+        let id = self.init_component::<T>();
+        self.components.get_hooks_mut(id).unwrap()
+    }
+
+    /// Returns a mutable reference to the [`ComponentHooks`] for the component with the given id.
+    ///
+    /// Returns `None` if the component has not been initialized.
+    /// See [`World::register_component_hooks`] for more details.
+    pub fn register_component_hooks_by_id(
+        &mut self,
+        id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        self.components.get_hooks_mut(id)
+    }
+
     /// Returns the [`ComponentId`] of the given [`Component`] type `T`.
     ///
     /// The returned `ComponentId` is specific to the `World` instance
@@ -366,29 +461,46 @@ impl World {
         Some(entity_ref)
     }
 
-    /// Returns an [`Entity`] iterator of current entities.
+    /// Returns an [`Entity`] iterator of current entities, except the [`Disabled`] ones.
     ///
     /// This is useful in contexts where you only have read-only access to the [`World`].
     #[inline]
     pub fn iter_entities(&self) -> impl Iterator<Item = EntityRef<'_>> + '_ {
-        self.archetypes.iter().flat_map(|archetype| {
-            archetype
-                .entities()
-                .iter()
-                .enumerate()
-                .map(|(archetype_row, archetype_entity)| {
-                    let entity = archetype_entity.entity();
-                    let location = EntityLocation {
-                        archetype_id: archetype.id(),
-                        archetype_row: ArchetypeRow::new(archetype_row),
-                        table_id: archetype.table_id(),
-                        table_row: archetype_entity.table_row(),
-                    };
+        let disabled = self.disabled_component;
+        self.iter_archetype_entities(move |archetype| !archetype.contains(disabled))
+    }
 
-                    // SAFETY: entity exists and location accurately specifies the archetype where the entity is stored
-                    unsafe { EntityRef::new(self, entity, location) }
-                })
-        })
+    /// Returns an [`Entity`] iterator of current entities, including the [`Disabled`] ones.
+    #[inline]
+    pub fn iter_entities_including_disabled(&self) -> impl Iterator<Item = EntityRef<'_>> + '_ {
+        self.iter_archetype_entities(|_| true)
+    }
+
+    fn iter_archetype_entities(
+        &self,
+        include: impl Fn(&Archetype) -> bool + 'static,
+    ) -> impl Iterator<Item = EntityRef<'_>> + '_ {
+        self.archetypes
+            .iter()
+            .filter(move |archetype| include(archetype))
+            .flat_map(|archetype| {
+                archetype
+                    .entities()
+                    .iter()
+                    .enumerate()
+                    .map(|(archetype_row, archetype_entity)| {
+                        let entity = archetype_entity.entity();
+                        let location = EntityLocation {
+                            archetype_id: archetype.id(),
+                            archetype_row: ArchetypeRow::new(archetype_row),
+                            table_id: archetype.table_id(),
+                            table_row: archetype_entity.table_row(),
+                        };
+
+                        // SAFETY: entity exists and location accurately specifies the archetype where the entity is stored
+                        unsafe { EntityRef::new(self, entity, location) }
+                    })
+            })
     }
 
     /// Retrieves an [`EntityMut`] that exposes read and write operations for the given `entity`.
@@ -512,7 +624,7 @@ impl World {
         self.flush();
         let change_tick = self.change_tick();
         let entity = self.entities.alloc();
-        let entity_location = {
+        let (entity_location, bundle_id) = {
             let bundle_info = self
                 .bundles
                 .init_info::<B>(&mut self.components, &mut self.storages);
@@ -525,11 +637,15 @@ impl World {
             );
 
             // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent
-            unsafe { spawner.spawn_non_existent(entity, bundle) }
+            let location = unsafe { spawner.spawn_non_existent(entity, bundle) };
+            (location, bundle_info.id())
         };
 
         // SAFETY: entity and location are valid, as they were just created above
-        unsafe { EntityMut::new(self, entity, entity_location) }
+        let mut entity = unsafe { EntityMut::new(self, entity, entity_location) };
+        entity.trigger_insert_hooks(bundle_id, ArchetypeId::EMPTY);
+        entity.report_invariant_violations();
+        entity
     }
 
     /// # Safety
@@ -1211,6 +1327,27 @@ impl World {
         let bundle_info = self
             .bundles
             .init_info::<B>(&mut self.components, &mut self.storages);
+
+        // Hooks need access to the whole world, so insert bundles one by one if there are any.
+        if self
+            .components
+            .any_hooks(bundle_info.contributed_components())
+        {
+            let mut invalid_entities = Vec::new();
+            for (entity, bundle) in iter {
+                match self.get_or_spawn(entity) {
+                    Some(mut entity) => {
+                        entity.insert(bundle);
+                    }
+                    None => invalid_entities.push(entity),
+                }
+            }
+            return if invalid_entities.is_empty() {
+                Ok(())
+            } else {
+                Err(invalid_entities)
+            };
+        }
         enum SpawnOrInsert<'a, 'b> {
             Spawn(BundleSpawner<'a, 'b>),
             Insert(BundleInserter<'a, 'b>, ArchetypeId),
@@ -1224,6 +1361,9 @@ impl World {
                 }
             }
         }
+        // Entities moved to archetypes violating invariants are reported once the batch is done.
+        let check_invariants = !self.archetypes.invariants().is_empty();
+        let mut moved_entities = Vec::new();
         let mut spawn_or_insert = SpawnOrInsert::Spawn(bundle_info.get_bundle_spawner(
             &mut self.entities,
             &mut self.archetypes,
@@ -1239,6 +1379,9 @@ impl World {
                 .alloc_at_without_replacement(entity)
             {
                 AllocAtWithoutReplacement::Exists(location) => {
+                    if check_invariants {
+                        moved_entities.push(entity);
+                    }
                     match spawn_or_insert {
                         SpawnOrInsert::Insert(ref mut inserter, archetype)
                             if location.archetype_id == archetype =>
@@ -1263,6 +1406,9 @@ impl World {
                     };
                 }
                 AllocAtWithoutReplacement::DidNotExist => {
+                    if check_invariants {
+                        moved_entities.push(entity);
+                    }
                     if let SpawnOrInsert::Spawn(ref mut spawner) = spawn_or_insert {
                         // SAFETY: `entity` is allocated (but non existent), bundle matches inserter
                         unsafe { spawner.spawn_non_existent(entity, bundle) };
@@ -1284,6 +1430,15 @@ impl World {
                 }
             }
         }
+        for entity in moved_entities {
+            // SAFETY: the entities were spawned or inserted into above.
+            let location = unsafe { self.entities.get(entity).debug_checked_unwrap() };
+            self.archetypes.report_invariant_violations(
+                entity,
+                location.archetype_id,
+                &self.components,
+            );
+        }
 
         if invalid_entities.is_empty() {
             Ok(())
@@ -0,0 +1,449 @@
+use std::any::{Any, TypeId};
+
+use bevy_utils::HashSet;
+
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::{ReflectComponent, ReflectResource};
+use crate::{
+    component::Component,
+    entity::{Entity, EntityAllocatorState},
+    system::Resource,
+    world::{EntityMut, EntityRef, World},
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{Reflect, TypeRegistry};
+
+/// A value captured in a [`WorldSnapshot`].
+enum Captured {
+    Clone(Box<dyn Any + Send + Sync>),
+    #[cfg(feature = "bevy_reflect")]
+    Reflect(Box<dyn Reflect>),
+}
+
+/// How the values of a component type are captured and restored.
+#[derive(Clone)]
+enum ComponentCloner {
+    Clone {
+        capture: fn(EntityRef) -> Option<Box<dyn Any + Send + Sync>>,
+        restore: fn(&mut EntityMut, &(dyn Any + Send + Sync)),
+        remove: fn(&mut EntityMut),
+    },
+    #[cfg(feature = "bevy_reflect")]
+    Reflect(ReflectComponent),
+}
+
+impl ComponentCloner {
+    fn capture(&self, entity: EntityRef) -> Option<Captured> {
+        match self {
+            Self::Clone { capture, .. } => capture(entity).map(Captured::Clone),
+            #[cfg(feature = "bevy_reflect")]
+            Self::Reflect(reflect) => reflect
+                .reflect(entity)
+                .map(|component| Captured::Reflect(component.clone_value())),
+        }
+    }
+
+    fn restore(&self, entity: &mut EntityMut, value: Option<&Captured>) {
+        match (self, value) {
+            (Self::Clone { restore, .. }, Some(Captured::Clone(value))) => {
+                restore(entity, &**value)
+            }
+            (Self::Clone { remove, .. }, None) => remove(entity),
+            #[cfg(feature = "bevy_reflect")]
+            (Self::Reflect(reflect), Some(Captured::Reflect(value))) => {
+                // Applying to the current value would keep the list elements and map entries
+                // added since the capture, so a fresh value replaces it instead.
+                reflect.insert(entity, &**value);
+            }
+            #[cfg(feature = "bevy_reflect")]
+            (Self::Reflect(reflect), None) => reflect.remove(entity),
+            #[cfg(feature = "bevy_reflect")]
+            _ => unreachable!("the value was captured by the same cloner"),
+        }
+    }
+}
+
+/// How the value of a resource type is captured and restored.
+#[derive(Clone)]
+enum ResourceCloner {
+    Clone {
+        capture: fn(&World) -> Option<Box<dyn Any + Send + Sync>>,
+        restore: fn(&mut World, &(dyn Any + Send + Sync)),
+        remove: fn(&mut World),
+    },
+    #[cfg(feature = "bevy_reflect")]
+    Reflect(ReflectResource),
+}
+
+impl ResourceCloner {
+    fn capture(&self, world: &World) -> Option<Captured> {
+        match self {
+            Self::Clone { capture, .. } => capture(world).map(Captured::Clone),
+            #[cfg(feature = "bevy_reflect")]
+            Self::Reflect(reflect) => reflect
+                .reflect(world)
+                .map(|resource| Captured::Reflect(resource.clone_value())),
+        }
+    }
+
+    fn restore(&self, world: &mut World, value: Option<&Captured>) {
+        match (self, value) {
+            (Self::Clone { restore, .. }, Some(Captured::Clone(value))) => restore(world, &**value),
+            (Self::Clone { remove, .. }, None) => remove(world),
+            #[cfg(feature = "bevy_reflect")]
+            (Self::Reflect(reflect), Some(Captured::Reflect(value))) => {
+                // See `ComponentCloner::restore`.
+                reflect.insert(world, &**value);
+            }
+            #[cfg(feature = "bevy_reflect")]
+            (Self::Reflect(reflect), None) => reflect.remove(world),
+            #[cfg(feature = "bevy_reflect")]
+            _ => unreachable!("the value was captured by the same cloner"),
+        }
+    }
+}
+
+/// Selects the components and resources captured by a [`WorldSnapshot`], and how they are
+/// cloned.
+///
+/// Types are cloned either through their [`Clone`] implementation, which is the fastest, or
+/// through reflection, for the types registered with [`ReflectComponent`] or [`ReflectResource`].
+/// Each type is cloned the way it was first added with.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::SnapshotConfig;
+/// #[derive(Component, Clone)]
+/// struct Position(f32);
+///
+/// let mut world = World::new();
+/// let config = SnapshotConfig::new().with_component::<Position>();
+/// let entity = world.spawn(Position(0.0)).id();
+/// let snapshot = config.capture(&mut world);
+///
+/// world.get_mut::<Position>(entity).unwrap().0 = 1.0;
+/// let other = world.spawn(Position(2.0)).id();
+///
+/// config.restore(&snapshot, &mut world);
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 0.0);
+/// assert!(world.get_entity(other).is_none());
+/// ```
+#[derive(Clone, Default)]
+pub struct SnapshotConfig {
+    components: Vec<(TypeId, ComponentCloner)>,
+    resources: Vec<(TypeId, ResourceCloner)>,
+}
+
+impl SnapshotConfig {
+    /// Creates a configuration that captures no component or resource.
+    ///
+    /// Snapshots still capture which entities are alive.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the component `T`, cloning it with [`Clone`].
+    pub fn with_component<T: Component + Clone>(mut self) -> Self {
+        if !self.has_component(TypeId::of::<T>()) {
+            self.components.push((
+                TypeId::of::<T>(),
+                ComponentCloner::Clone {
+                    capture: |entity| {
+                        let component = entity.get::<T>()?.clone();
+                        Some(Box::new(component))
+                    },
+                    restore: |entity, value| {
+                        entity.insert(value.downcast_ref::<T>().unwrap().clone());
+                    },
+                    remove: |entity| {
+                        entity.remove::<T>();
+                    },
+                },
+            ));
+        }
+        self
+    }
+
+    /// Captures the resource `T`, cloning it with [`Clone`].
+    pub fn with_resource<T: Resource + Clone>(mut self) -> Self {
+        if !self.has_resource(TypeId::of::<T>()) {
+            self.resources.push((
+                TypeId::of::<T>(),
+                ResourceCloner::Clone {
+                    capture: |world| {
+                        let resource = world.get_resource::<T>()?.clone();
+                        Some(Box::new(resource))
+                    },
+                    restore: |world, value| {
+                        world.insert_resource(value.downcast_ref::<T>().unwrap().clone());
+                    },
+                    remove: |world| {
+                        world.remove_resource::<T>();
+                    },
+                },
+            ));
+        }
+        self
+    }
+
+    /// Captures the components and resources of `registry` for which `filter` returns `true`,
+    /// cloning them through [`ReflectComponent`] and [`ReflectResource`].
+    ///
+    /// The filter is given the [`TypeId`] of each type. A `SceneFilter` from `bevy_scene` can be
+    /// used as an allowlist or a denylist with `|id| filter.is_allowed_by_id(id)`.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn with_reflected(
+        mut self,
+        registry: &TypeRegistry,
+        mut filter: impl FnMut(TypeId) -> bool,
+    ) -> Self {
+        for registration in registry.iter() {
+            let type_id = registration.type_id();
+            if !filter(type_id) {
+                continue;
+            }
+            if let Some(reflect) = registration.data::<ReflectComponent>() {
+                if !self.has_component(type_id) {
+                    self.components
+                        .push((type_id, ComponentCloner::Reflect(reflect.clone())));
+                }
+            }
+            if let Some(reflect) = registration.data::<ReflectResource>() {
+                if !self.has_resource(type_id) {
+                    self.resources
+                        .push((type_id, ResourceCloner::Reflect(reflect.clone())));
+                }
+            }
+        }
+        self
+    }
+
+    fn has_component(&self, type_id: TypeId) -> bool {
+        self.components.iter().any(|(id, _)| *id == type_id)
+    }
+
+    fn has_resource(&self, type_id: TypeId) -> bool {
+        self.resources.iter().any(|(id, _)| *id == type_id)
+    }
+
+    fn type_ids(&self) -> Vec<TypeId> {
+        self.components
+            .iter()
+            .map(|(id, _)| *id)
+            .chain(self.resources.iter().map(|(id, _)| *id))
+            .collect()
+    }
+
+    /// Captures the entities alive in `world`, and the selected components and resources.
+    pub fn capture(&self, world: &mut World) -> WorldSnapshot {
+        world.flush();
+        let entities = world
+            .iter_entities_including_disabled()
+            .map(|entity| EntitySnapshot {
+                entity: entity.id(),
+                components: self
+                    .components
+                    .iter()
+                    .map(|(_, cloner)| cloner.capture(entity))
+                    .collect(),
+            })
+            .collect();
+        let resources = self
+            .resources
+            .iter()
+            .map(|(_, cloner)| cloner.capture(world))
+            .collect();
+        WorldSnapshot {
+            type_ids: self.type_ids(),
+            entities,
+            resources,
+            allocator: world.entities.allocator_state(),
+        }
+    }
+
+    /// Restores `world` to the state captured in `snapshot`.
+    ///
+    /// Entities spawned since are despawned, and despawned entities are spawned again with their
+    /// original [`Entity`] ids. The selected components and resources are set back to their
+    /// captured values, or removed if they did not exist. Other components and resources are
+    /// left as they are.
+    ///
+    /// The ids of the entities spawned afterwards are the same as they were after the snapshot
+    /// was captured, which keeps rollbacks deterministic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `snapshot` was captured with a different configuration.
+    pub fn restore(&self, snapshot: &WorldSnapshot, world: &mut World) {
+        assert!(
+            snapshot.type_ids == self.type_ids(),
+            "the snapshot was captured with a different configuration"
+        );
+        world.flush();
+
+        let alive: HashSet<Entity> = snapshot.entities.iter().map(|e| e.entity).collect();
+        let spawned: Vec<Entity> = world
+            .iter_entities_including_disabled()
+            .map(|entity| entity.id())
+            .filter(|entity| !alive.contains(entity))
+            .collect();
+        for entity in spawned {
+            world.despawn(entity);
+        }
+
+        for snapshot in &snapshot.entities {
+            let mut entity = world
+                .get_or_spawn(snapshot.entity)
+                .expect("entities alive in the snapshot have not been replaced");
+            for ((_, cloner), value) in self.components.iter().zip(&snapshot.components) {
+                cloner.restore(&mut entity, value.as_ref());
+            }
+        }
+        world.flush();
+        world.entities.restore_allocator_state(&snapshot.allocator);
+
+        for ((_, cloner), value) in self.resources.iter().zip(&snapshot.resources) {
+            cloner.restore(world, value.as_ref());
+        }
+    }
+}
+
+struct EntitySnapshot {
+    entity: Entity,
+    /// The captured components, in the order of [`SnapshotConfig::components`].
+    components: Vec<Option<Captured>>,
+}
+
+/// The entities, components and resources of a [`World`] at some point, captured by
+/// [`SnapshotConfig::capture`] to be restored with [`SnapshotConfig::restore`].
+pub struct WorldSnapshot {
+    type_ids: Vec<TypeId>,
+    entities: Vec<EntitySnapshot>,
+    /// The captured resources, in the order of [`SnapshotConfig::resources`].
+    resources: Vec<Option<Captured>>,
+    allocator: EntityAllocatorState,
+}
+
+impl WorldSnapshot {
+    /// Returns the entities that were alive when the snapshot was captured.
+    pub fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + '_ {
+        self.entities.iter().map(|snapshot| snapshot.entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SnapshotConfig;
+    use crate::{self as bevy_ecs, prelude::*};
+    #[cfg(feature = "bevy_reflect")]
+    use bevy_reflect::{Reflect, TypeRegistry};
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[cfg(feature = "bevy_reflect")]
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Velocity(f32);
+
+    #[derive(Component)]
+    struct NotCaptured;
+
+    #[derive(Resource, Clone, Debug, PartialEq)]
+    struct Frame(u32);
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn restore_snapshot() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Velocity>();
+        let config = SnapshotConfig::new()
+            .with_component::<Health>()
+            .with_resource::<Frame>()
+            .with_reflected(&registry, |_| true);
+
+        let mut world = World::new();
+        world.insert_resource(Frame(0));
+        let a = world.spawn((Health(10), Velocity(1.0))).id();
+        let b = world.spawn(Health(5)).id();
+        let snapshot = config.capture(&mut world);
+
+        world.get_mut::<Health>(a).unwrap().0 = 0;
+        world.entity_mut(a).remove::<Velocity>().insert(NotCaptured);
+        world.entity_mut(b).insert(Velocity(2.0));
+        world.despawn(b);
+        let c = world.spawn(Health(1)).id();
+        let d = world.spawn_empty().id();
+        world.resource_mut::<Frame>().0 = 1;
+
+        config.restore(&snapshot, &mut world);
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        assert_eq!(world.get::<Velocity>(a), Some(&Velocity(1.0)));
+        // components that are not captured are left as they are
+        assert!(world.get::<NotCaptured>(a).is_some());
+        assert_eq!(world.get::<Health>(b), Some(&Health(5)));
+        assert_eq!(world.get::<Velocity>(b), None);
+        assert!(world.get_entity(c).is_none());
+        assert!(world.get_entity(d).is_none());
+        assert_eq!(world.resource::<Frame>(), &Frame(0));
+        assert_eq!(world.entities().len(), 2);
+
+        // replaying the same changes hands out the same ids
+        world.despawn(b);
+        assert_eq!(world.spawn_empty().id(), c);
+        assert_eq!(world.spawn_empty().id(), d);
+    }
+
+    #[test]
+    fn restore_several_times() {
+        let config = SnapshotConfig::new().with_component::<Health>();
+        let mut world = World::new();
+        let a = world.spawn(Health(1)).id();
+        let first = config.capture(&mut world);
+        world.despawn(a);
+        let b = world.spawn(Health(2)).id();
+        let second = config.capture(&mut world);
+
+        config.restore(&first, &mut world);
+        assert_eq!(world.get::<Health>(a), Some(&Health(1)));
+        assert!(world.get_entity(b).is_none());
+
+        config.restore(&second, &mut world);
+        assert!(world.get_entity(a).is_none());
+        assert_eq!(world.get::<Health>(b), Some(&Health(2)));
+        assert_eq!(second.entities().collect::<Vec<_>>(), vec![b]);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn restore_reflected_collections() {
+        use bevy_utils::HashMap;
+
+        #[derive(Component, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Inventory(Vec<u32>);
+
+        #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Resource)]
+        struct Scores(HashMap<String, u32>);
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Inventory>();
+        registry.register::<Scores>();
+        let config = SnapshotConfig::new().with_reflected(&registry, |_| true);
+
+        let mut world = World::new();
+        let mut scores = HashMap::default();
+        scores.insert("a".to_string(), 1);
+        world.insert_resource(Scores(scores.clone()));
+        let entity = world.spawn(Inventory(vec![1, 2])).id();
+        let snapshot = config.capture(&mut world);
+
+        world.get_mut::<Inventory>(entity).unwrap().0.extend([3, 4]);
+        world.resource_mut::<Scores>().0.insert("b".to_string(), 2);
+
+        config.restore(&snapshot, &mut world);
+        assert_eq!(world.get::<Inventory>(entity), Some(&Inventory(vec![1, 2])));
+        assert_eq!(world.resource::<Scores>(), &Scores(scores));
+    }
+}
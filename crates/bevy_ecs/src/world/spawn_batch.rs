@@ -1,4 +1,5 @@
 use crate::{
+    archetype::ArchetypeId,
     bundle::{Bundle, BundleSpawner},
     entity::Entity,
     world::World,
@@ -15,7 +16,17 @@ where
     I::Item: Bundle,
 {
     inner: I,
-    spawner: BundleSpawner<'w, 'w>,
+    spawner: BatchSpawner<'w>,
+}
+
+/// How a [`SpawnBatchIter`] spawns its entities.
+enum BatchSpawner<'w> {
+    /// Spawn all entities with a single cached [`BundleSpawner`].
+    Cached(BundleSpawner<'w, 'w>),
+    /// Spawn entities one by one, as some components of the bundle have hooks which need
+    /// access to the whole [`World`], or the entities violate an
+    /// [`ArchetypeInvariant`](crate::archetype::ArchetypeInvariant) and must each be reported.
+    Hooked(&'w mut World),
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -34,6 +45,25 @@ where
         let (lower, upper) = iter.size_hint();
         let length = upper.unwrap_or(lower);
 
+        let bundle_info = world
+            .bundles
+            .init_info::<I::Item>(&mut world.components, &mut world.storages);
+        let archetype_id = bundle_info.add_bundle_to_archetype(
+            &mut world.archetypes,
+            &mut world.storages,
+            &mut world.components,
+            ArchetypeId::EMPTY,
+        );
+        if world
+            .components
+            .any_hooks(bundle_info.contributed_components())
+            || world.archetypes.violates_invariants(archetype_id)
+        {
+            return Self {
+                inner: iter,
+                spawner: BatchSpawner::Hooked(world),
+            };
+        }
         let bundle_info = world
             .bundles
             .init_info::<I::Item>(&mut world.components, &mut world.storages);
@@ -49,7 +79,7 @@ where
 
         Self {
             inner: iter,
-            spawner,
+            spawner: BatchSpawner::Cached(spawner),
         }
     }
 }
@@ -60,6 +90,11 @@ where
     I::Item: Bundle,
 {
     fn drop(&mut self) {
+        // Spawning the remaining entities while unwinding from a panicking hook or archetype
+        // invariant would panic again and abort.
+        if std::thread::panicking() {
+            return;
+        }
         for _ in self {}
     }
 }
@@ -73,8 +108,11 @@ where
 
     fn next(&mut self) -> Option<Entity> {
         let bundle = self.inner.next()?;
-        // SAFETY: bundle matches spawner type
-        unsafe { Some(self.spawner.spawn(bundle)) }
+        match &mut self.spawner {
+            // SAFETY: bundle matches spawner type
+            BatchSpawner::Cached(spawner) => unsafe { Some(spawner.spawn(bundle)) },
+            BatchSpawner::Hooked(world) => Some(world.spawn(bundle).id()),
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
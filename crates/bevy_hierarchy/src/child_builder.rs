@@ -16,137 +16,71 @@ fn push_events(world: &mut World, events: impl IntoIterator<Item = HierarchyEven
     }
 }
 
-/// Adds `child` to `parent`'s [`Children`], without checking if it is already present there.
+/// Sets [`Parent`] of the `child` to `parent`, which also adds `child` to the end of `parent`'s
+/// [`Children`] and removes it from the [`Children`] of its previous parent, if any.
 ///
-/// This might cause unexpected results when removing duplicate children.
-fn push_child_unchecked(world: &mut World, parent: Entity, child: Entity) {
-    let mut parent = world.entity_mut(parent);
-    if let Some(mut children) = parent.get_mut::<Children>() {
-        children.0.push(child);
-    } else {
-        parent.insert(Children(smallvec::smallvec![child]));
+/// Returns the [`HierarchyEvent`] describing the change, or `None` if `child` was already a child
+/// of `parent`.
+fn relate_to_parent(world: &mut World, child: Entity, parent: Entity) -> Option<HierarchyEvent> {
+    match world.insert_relation(child, Parent(parent)) {
+        Some(previous_parent) if previous_parent == parent => None,
+        Some(previous_parent) => Some(HierarchyEvent::ChildMoved {
+            child,
+            previous_parent,
+            new_parent: parent,
+        }),
+        None => Some(HierarchyEvent::ChildAdded { child, parent }),
     }
 }
 
-/// Sets [`Parent`] of the `child` to `new_parent`. Inserts [`Parent`] if `child` doesn't have one.
-fn update_parent(world: &mut World, child: Entity, new_parent: Entity) -> Option<Entity> {
-    let mut child = world.entity_mut(child);
-    if let Some(mut parent) = child.get_mut::<Parent>() {
-        let previous = parent.0;
-        *parent = Parent(new_parent);
-        Some(previous)
-    } else {
-        child.insert(Parent(new_parent));
-        None
-    }
-}
-
-/// Remove child from the parent's [`Children`] component.
-///
-/// Removes the [`Children`] component from the parent if it's empty.
-fn remove_from_children(world: &mut World, parent: Entity, child: Entity) {
-    let Some(mut parent) = world.get_entity_mut(parent) else {
-        return;
-    };
-    let Some(mut children) = parent.get_mut::<Children>() else {
-        return;
-    };
-    children.0.retain(|x| *x != child);
-    if children.is_empty() {
-        parent.remove::<Children>();
-    }
-}
-
-/// Update the [`Parent`] component of the `child`.
-/// Removes the `child` from the previous parent's [`Children`].
-///
-/// Does not update the new parents [`Children`] component.
-///
-/// Does nothing if `child` was already a child of `parent`.
+/// Sets [`Parent`] of each of the `children` to `parent`, and moves them to `index` in `parent`'s
+/// [`Children`], or to the end if `index` is `None`.
 ///
 /// Sends [`HierarchyEvent`]'s.
-fn update_old_parent(world: &mut World, child: Entity, parent: Entity) {
-    let previous = update_parent(world, child, parent);
-    if let Some(previous_parent) = previous {
-        // Do nothing if the child was already parented to this entity.
-        if previous_parent == parent {
-            return;
-        }
-        remove_from_children(world, previous_parent, child);
-
-        push_events(
-            world,
-            [HierarchyEvent::ChildMoved {
-                child,
-                previous_parent,
-                new_parent: parent,
-            }],
-        );
-    } else {
-        push_events(world, [HierarchyEvent::ChildAdded { child, parent }]);
-    }
-}
-
-/// Update the [`Parent`] components of the `children`.
-/// Removes the `children` from their previous parent's [`Children`].
-///
-/// Does not update the new parents [`Children`] component.
-///
-/// Does nothing for a child if it was already a child of `parent`.
-///
-/// Sends [`HierarchyEvent`]'s.
-fn update_old_parents(world: &mut World, parent: Entity, children: &[Entity]) {
-    let mut events: SmallVec<[HierarchyEvent; 8]> = SmallVec::with_capacity(children.len());
-    for &child in children {
-        if let Some(previous) = update_parent(world, child, parent) {
-            // Do nothing if the entity already has the correct parent.
-            if parent == previous {
-                continue;
-            }
+fn relate_to_parent_at(
+    world: &mut World,
+    parent: Entity,
+    children: &[Entity],
+    index: Option<usize>,
+) {
+    let events: SmallVec<[HierarchyEvent; 8]> = children
+        .iter()
+        .filter_map(|&child| relate_to_parent(world, child, parent))
+        .collect();
+    push_events(world, events);
 
-            remove_from_children(world, previous, child);
-            events.push(HierarchyEvent::ChildMoved {
-                child,
-                previous_parent: previous,
-                new_parent: parent,
-            });
-        } else {
-            events.push(HierarchyEvent::ChildAdded { child, parent });
+    let Some(mut children_component) = world.get_mut::<Children>(parent) else {
+        return;
+    };
+    children_component
+        .0
+        .retain(|value| !children.contains(value));
+    match index {
+        Some(index) => {
+            let index = index.min(children_component.0.len());
+            children_component.0.insert_from_slice(index, children);
         }
+        None => children_component.0.extend_from_slice(children),
     }
-    push_events(world, events);
 }
 
 /// Removes entities in `children` from `parent`'s [`Children`], removing the component if it ends up empty.
 /// Also removes [`Parent`] component from `children`.
 fn remove_children(parent: Entity, children: &[Entity], world: &mut World) {
-    let mut events: SmallVec<[HierarchyEvent; 8]> = SmallVec::new();
-    if let Some(parent_children) = world.get::<Children>(parent) {
-        for &child in children {
-            if parent_children.contains(&child) {
-                events.push(HierarchyEvent::ChildRemoved { child, parent });
-            }
-        }
-    } else {
+    let Some(parent_children) = world.get::<Children>(parent) else {
         return;
-    }
+    };
+    let events: SmallVec<[HierarchyEvent; 8]> = children
+        .iter()
+        .filter(|child| parent_children.contains(child))
+        .map(|&child| HierarchyEvent::ChildRemoved { child, parent })
+        .collect();
     for event in &events {
         if let &HierarchyEvent::ChildRemoved { child, .. } = event {
-            world.entity_mut(child).remove::<Parent>();
+            world.remove_relation::<Parent>(child);
         }
     }
     push_events(world, events);
-
-    let mut parent = world.entity_mut(parent);
-    if let Some(mut parent_children) = parent.get_mut::<Children>() {
-        parent_children
-            .0
-            .retain(|parent_child| !children.contains(parent_child));
-
-        if parent_children.is_empty() {
-            parent.remove::<Children>();
-        }
-    }
 }
 
 /// Removes all children from `parent` by removing its [`Children`] component, as well as removing
@@ -154,11 +88,22 @@ fn remove_children(parent: Entity, children: &[Entity], world: &mut World) {
 fn clear_children(parent: Entity, world: &mut World) {
     if let Some(children) = world.entity_mut(parent).take::<Children>() {
         for &child in &children.0 {
-            world.entity_mut(child).remove::<Parent>();
+            if let Some(mut child) = world.get_entity_mut(child) {
+                child.remove::<Parent>();
+            }
         }
     }
 }
 
+/// Removes the [`Parent`] of `child`, and removes `child` from its parent's [`Children`].
+///
+/// Sends a [`HierarchyEvent`].
+fn remove_parent(world: &mut World, child: Entity) {
+    if let Some(parent) = world.remove_relation::<Parent>(child).map(|p| p.get()) {
+        push_events(world, [HierarchyEvent::ChildRemoved { child, parent }]);
+    }
+}
+
 /// Command that adds a child to an entity.
 #[derive(Debug)]
 pub struct AddChild {
@@ -461,30 +406,18 @@ impl<'w> WorldChildBuilder<'w> {
     /// Spawns an entity with the given bundle and inserts it into the parent entity's [`Children`].
     /// Also adds [`Parent`] component to the created entity.
     pub fn spawn(&mut self, bundle: impl Bundle + Send + Sync + 'static) -> EntityMut<'_> {
-        let entity = self.world.spawn((bundle, Parent(self.parent))).id();
-        push_child_unchecked(self.world, self.parent, entity);
-        push_events(
-            self.world,
-            [HierarchyEvent::ChildAdded {
-                child: entity,
-                parent: self.parent,
-            }],
-        );
+        let entity = self.world.spawn(bundle).id();
+        let event = relate_to_parent(self.world, entity, self.parent);
+        push_events(self.world, event);
         self.world.entity_mut(entity)
     }
 
     /// Spawns an [`Entity`] with no components and inserts it into the parent entity's [`Children`].
     /// Also adds [`Parent`] component to the created entity.
     pub fn spawn_empty(&mut self) -> EntityMut<'_> {
-        let entity = self.world.spawn(Parent(self.parent)).id();
-        push_child_unchecked(self.world, self.parent, entity);
-        push_events(
-            self.world,
-            [HierarchyEvent::ChildAdded {
-                child: entity,
-                parent: self.parent,
-            }],
-        );
+        let entity = self.world.spawn_empty().id();
+        let event = relate_to_parent(self.world, entity, self.parent);
+        push_events(self.world, event);
         self.world.entity_mut(entity)
     }
 
@@ -569,14 +502,8 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
             panic!("Cannot add entity as a child of itself.");
         }
         self.world_scope(|world| {
-            update_old_parent(world, child, parent);
+            relate_to_parent_at(world, parent, &[child], None);
         });
-        if let Some(mut children_component) = self.get_mut::<Children>() {
-            children_component.0.retain(|value| child != *value);
-            children_component.0.push(child);
-        } else {
-            self.insert(Children::from_entities(&[child]));
-        }
         self
     }
 
@@ -586,16 +513,8 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
             panic!("Cannot push entity as a child of itself.");
         }
         self.world_scope(|world| {
-            update_old_parents(world, parent, children);
+            relate_to_parent_at(world, parent, children, None);
         });
-        if let Some(mut children_component) = self.get_mut::<Children>() {
-            children_component
-                .0
-                .retain(|value| !children.contains(value));
-            children_component.0.extend(children.iter().cloned());
-        } else {
-            self.insert(Children::from_entities(children));
-        }
         self
     }
 
@@ -605,16 +524,8 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
             panic!("Cannot insert entity as a child of itself.");
         }
         self.world_scope(|world| {
-            update_old_parents(world, parent, children);
+            relate_to_parent_at(world, parent, children, Some(index));
         });
-        if let Some(mut children_component) = self.get_mut::<Children>() {
-            children_component
-                .0
-                .retain(|value| !children.contains(value));
-            children_component.0.insert_from_slice(index, children);
-        } else {
-            self.insert(Children::from_entities(children));
-        }
         self
     }
 
@@ -636,12 +547,9 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
 
     fn remove_parent(&mut self) -> &mut Self {
         let child = self.id();
-        if let Some(parent) = self.take::<Parent>().map(|p| p.get()) {
-            self.world_scope(|world| {
-                remove_from_children(world, parent, child);
-                push_events(world, [HierarchyEvent::ChildRemoved { child, parent }]);
-            });
-        }
+        self.world_scope(|world| {
+            remove_parent(world, child);
+        });
         self
     }
 }
@@ -659,8 +567,9 @@ mod tests {
         component::Component,
         entity::Entity,
         event::Events,
+        reflect::AppTypeRegistry,
         system::{CommandQueue, Commands},
-        world::World,
+        world::{EntityCloneOptions, World},
     };
 
     /// Assert the (non)existence and state of the child's [`Parent`] component.
@@ -1025,6 +934,21 @@ mod tests {
         assert!(world.get::<Parent>(child4).is_none());
     }
 
+    #[test]
+    fn insert_children_past_the_end() {
+        let world = &mut World::new();
+        let [parent, a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        // Inserting into a parent without children ignores the index.
+        world.entity_mut(parent).insert_children(3, &[a, b]);
+        assert_children(world, parent, Some(&[a, b]));
+
+        // An index past the end appends the children.
+        world.entity_mut(parent).insert_children(5, &[c]);
+        assert_children(world, parent, Some(&[a, b, c]));
+        assert_parent(world, c, Some(parent));
+    }
+
     /// Tests what happens when all children are removed from a parent using world functions
     #[test]
     fn children_removed_when_empty_world() {
@@ -1136,4 +1060,46 @@ mod tests {
         let children = query.get(&world, parent).unwrap();
         assert_eq!(**children, [child]);
     }
+
+    #[test]
+    fn despawn_keeps_hierarchy_consistent() {
+        let world = &mut World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.entity_mut(a).push_children(&[b, c]);
+
+        // Despawning a child removes it from its parent's `Children`.
+        world.despawn(b);
+        assert_children(world, a, Some(&[c]));
+
+        // Despawning a parent turns its children into root entities.
+        world.despawn(a);
+        assert_parent(world, c, None);
+    }
+
+    #[test]
+    fn clone_entity_with_children() {
+        let world = &mut World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Parent>();
+        registry.write().register::<Children>();
+        world.insert_resource(registry);
+
+        let [root, a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.entity_mut(root).push_children(&[a]);
+        world.entity_mut(a).push_children(&[b, c]);
+
+        let options = EntityCloneOptions::new().with_descendants::<Parent>();
+        let a_clone = world.clone_entity_with(a, &options);
+        // The clone is a sibling of the original entity.
+        assert_parent(world, a_clone, Some(root));
+        assert_children(world, root, Some(&[a, a_clone]));
+
+        let children = world.get::<Children>(a_clone).unwrap().to_vec();
+        assert_eq!(children.len(), 2);
+        assert!(!children.contains(&b) && !children.contains(&c));
+        for child in children {
+            assert_parent(world, child, Some(a_clone));
+        }
+        assert_children(world, a, Some(&[b, c]));
+    }
 }
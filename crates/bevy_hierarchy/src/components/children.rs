@@ -3,6 +3,7 @@ use bevy_ecs::{
     entity::{Entity, EntityMapper, MapEntities},
     prelude::FromWorld,
     reflect::{ReflectComponent, ReflectMapEntities},
+    relation::RelationSources,
     world::World,
 };
 use bevy_reflect::Reflect;
@@ -39,11 +40,6 @@ impl FromWorld for Children {
 }
 
 impl Children {
-    /// Constructs a [`Children`] component with the given entities.
-    pub(crate) fn from_entities(entities: &[Entity]) -> Self {
-        Self(SmallVec::from_slice(entities))
-    }
-
     /// Swaps the child at `a_index` with the child at `b_index`.
     pub fn swap(&mut self, a_index: usize, b_index: usize) {
         self.0.swap(a_index, b_index);
@@ -127,6 +123,24 @@ impl Children {
     }
 }
 
+impl RelationSources for Children {
+    fn from_source(source: Entity) -> Self {
+        Children(smallvec::smallvec![source])
+    }
+
+    fn sources(&self) -> &[Entity] {
+        &self.0
+    }
+
+    fn add_source(&mut self, source: Entity) {
+        self.0.push(source);
+    }
+
+    fn remove_source(&mut self, source: Entity) {
+        self.0.retain(|e| *e != source);
+    }
+}
+
 impl Deref for Children {
     type Target = [Entity];
 
@@ -1,7 +1,9 @@
+use crate::Children;
 use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityMapper, MapEntities},
     reflect::{ReflectComponent, ReflectMapEntities},
+    relation::{Relation, RelationCleanup},
     world::{FromWorld, World},
 };
 use bevy_reflect::Reflect;
@@ -10,6 +12,10 @@ use std::ops::Deref;
 /// Holds a reference to the parent entity of this entity.
 /// This component should only be present on entities that actually have a parent entity.
 ///
+/// [`Parent`] is a [`Relation`] whose sources are tracked by the parent's [`Children`].
+/// Despawning a parent with [`World::despawn`] turns its children into root entities,
+/// use [`DespawnRecursiveExt`](crate::DespawnRecursiveExt) to despawn them as well.
+///
 /// See [`HierarchyQueryExt`] for hierarchy related methods on [`Query`].
 ///
 /// [`HierarchyQueryExt`]: crate::query_extension::HierarchyQueryExt
@@ -41,6 +47,15 @@ impl MapEntities for Parent {
     }
 }
 
+impl Relation for Parent {
+    type Sources = Children;
+    const CLEANUP: RelationCleanup = RelationCleanup::RemoveEdge;
+
+    fn target(&self) -> Entity {
+        self.0
+    }
+}
+
 impl Deref for Parent {
     type Target = Entity;
 
@@ -47,10 +47,12 @@ fn despawn_with_children_recursive_inner(world: &mut World, entity: Entity) {
 }
 
 fn despawn_children_recursive(world: &mut World, entity: Entity) {
-    if let Some(children) = world.entity_mut(entity).take::<Children>() {
-        for e in children.0 {
-            despawn_with_children_recursive_inner(world, e);
+    if let Some(children) = world.get::<Children>(entity).map(|c| c.to_vec()) {
+        for e in children {
+            despawn_with_children_recursive(world, e);
         }
+        // the children have been forgotten one by one, drop the now empty component
+        world.entity_mut(entity).remove::<Children>();
     }
 }
 
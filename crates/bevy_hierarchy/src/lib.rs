@@ -23,12 +23,15 @@ pub use valid_parent_check_plugin::*;
 mod query_extension;
 pub use query_extension::*;
 
+mod state_scoped;
+pub use state_scoped::*;
+
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        child_builder::*, components::*, hierarchy::*, query_extension::*, HierarchyPlugin,
-        ValidParentCheckPlugin,
+        child_builder::*, components::*, hierarchy::*, query_extension::*, state_scoped::*,
+        HierarchyPlugin, ValidParentCheckPlugin,
     };
 }
 
@@ -44,5 +47,6 @@ impl Plugin for HierarchyPlugin {
             .register_type::<Parent>()
             .register_type::<smallvec::SmallVec<[bevy_ecs::entity::Entity; 8]>>()
             .add_event::<HierarchyEvent>();
+        app.world.init_relation::<Parent>();
     }
 }
@@ -0,0 +1,132 @@
+use crate::despawn_with_children_recursive;
+use bevy_app::App;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    schedule::{IntoSystemConfigs, OnExit, StateScopedCleanup, States},
+    world::World,
+};
+
+/// Marks an entity as belonging to a value of the state `S`.
+///
+/// Once [`StateScopedAppExt::enable_state_scoped_entities::<S>`] has been called, the entity and
+/// its descendants are despawned when `S` exits that value, after the other systems of its
+/// [`OnExit`] schedule.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::{StateScoped, StateScopedAppExt};
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// fn spawn_menu(mut commands: Commands) {
+///     commands.spawn(StateScoped(GameState::MainMenu));
+/// }
+///
+/// App::new()
+///     .add_state::<GameState>()
+///     .enable_state_scoped_entities::<GameState>()
+///     .add_systems(OnEnter(GameState::MainMenu), spawn_menu);
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct StateScoped<S: States>(pub S);
+
+/// Recursively despawns every entity scoped to the `exited` value of the state `S`.
+pub fn clear_state_scoped_entities<S: States>(world: &mut World, exited: &S) {
+    let entities: Vec<Entity> = world
+        .query::<(Entity, &StateScoped<S>)>()
+        .iter(world)
+        .filter(|(_, scope)| scope.0 == *exited)
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in entities {
+        // the entity may already have been despawned as the descendant of another one
+        if world.get_entity(entity).is_some() {
+            despawn_with_children_recursive(world, entity);
+        }
+    }
+}
+
+/// Extension trait for [`App`] to opt into the despawning of [`StateScoped`] entities.
+pub trait StateScopedAppExt {
+    /// Despawns the [`StateScoped<S>`] entities of a value of `S` in the [`OnExit`] schedule of
+    /// that value.
+    ///
+    /// The entities are despawned in the [`StateScopedCleanup`] set, which runs after the other
+    /// systems of the schedule, so these systems can still access the entities.
+    ///
+    /// This works for any state whose [`OnExit`] schedules are run, including computed and sub
+    /// states.
+    fn enable_state_scoped_entities<S: States>(&mut self) -> &mut Self;
+}
+
+impl StateScopedAppExt for App {
+    fn enable_state_scoped_entities<S: States>(&mut self) -> &mut Self {
+        for state in S::variants() {
+            let exited = state.clone();
+            self.add_systems(
+                OnExit(state.clone()),
+                (move |world: &mut World| clear_state_scoped_entities(world, &exited))
+                    .in_set(StateScopedCleanup),
+            )
+            .edit_schedule(OnExit(state), |schedule| {
+                schedule.order_last(StateScopedCleanup);
+            });
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StateScoped, StateScopedAppExt};
+    use crate::BuildWorldChildren;
+    use bevy_app::{App, StateTransition};
+    use bevy_ecs::prelude::*;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum GameState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    #[test]
+    fn despawn_on_exit() {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .enable_state_scoped_entities::<GameState>();
+        app.world.run_schedule(StateTransition);
+
+        let menu = app.world.spawn(StateScoped(GameState::Menu)).id();
+        let mut child = None;
+        app.world.entity_mut(menu).with_children(|parent| {
+            child = Some(parent.spawn_empty().id());
+        });
+        let game = app.world.spawn(StateScoped(GameState::InGame)).id();
+        // the entities are despawned after the other `OnExit` systems
+        app.add_systems(
+            OnExit(GameState::Menu),
+            move |query: Query<&StateScoped<GameState>>| assert!(query.get(menu).is_ok()),
+        );
+
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.world.run_schedule(StateTransition);
+        assert!(app.world.get_entity(menu).is_none());
+        assert!(app.world.get_entity(child.unwrap()).is_none());
+        assert!(app.world.get_entity(game).is_some());
+
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        app.world.run_schedule(StateTransition);
+        assert!(app.world.get_entity(game).is_none());
+    }
+}
@@ -31,6 +31,9 @@ const FROM_REFLECT_ATTR: &str = "from_reflect";
 // Attributes for `TypePath` implementation
 const TYPE_PATH_ATTR: &str = "type_path";
 
+// Attribute for registering methods as reflected functions
+const FUNCTIONS_ATTR: &str = "functions";
+
 // The error message to show when a trait/type is specified multiple times
 const CONFLICTING_TYPE_DATA_MESSAGE: &str = "conflicting type data registration";
 
@@ -212,6 +215,7 @@ pub(crate) struct ReflectTraits {
     from_reflect_attrs: FromReflectAttrs,
     type_path_attrs: TypePathAttrs,
     idents: Vec<Ident>,
+    functions: Vec<Ident>,
 }
 
 impl ReflectTraits {
@@ -267,6 +271,15 @@ impl ReflectTraits {
                     // Track the span where the trait is implemented for future errors
                     let span = ident.span();
 
+                    // Handles `#[reflect( functions(method_a, method_b) )]`
+                    if ident == FUNCTIONS_ATTR {
+                        list.parse_nested_meta(|meta| {
+                            let function = meta.path.require_ident()?.clone();
+                            add_unique_ident(&mut traits.functions, function)
+                        })?;
+                        continue;
+                    }
+
                     list.parse_nested_meta(|meta| {
                         // This should be the path of the custom function
                         let trait_func_ident = TraitImpl::Custom(meta.path, span);
@@ -322,6 +335,11 @@ impl ReflectTraits {
         &self.idents
     }
 
+    /// The methods to register as reflected functions, found within `#[reflect(functions(...))]`.
+    pub fn functions(&self) -> &[Ident] {
+        &self.functions
+    }
+
     /// The `FromReflect` configuration found within `#[reflect(...)]` attributes on this type.
     #[allow(clippy::wrong_self_convention)]
     pub fn from_reflect_attrs(&self) -> &FromReflectAttrs {
@@ -414,6 +432,9 @@ impl ReflectTraits {
         for ident in other.idents {
             add_unique_ident(&mut self.idents, ident)?;
         }
+        for function in other.functions {
+            add_unique_ident(&mut self.functions, function)?;
+        }
         Ok(())
     }
 }
@@ -135,6 +135,15 @@ pub(crate) static TYPE_NAME_ATTRIBUTE_NAME: &str = "type_name";
 /// This is useful for when a type can't or shouldn't implement `TypePath`,
 /// or if a manual implementation is desired.
 ///
+/// ## `#[reflect(functions(method_a, method_b, ...))]`
+///
+/// This attribute will register the given methods of the type as reflected functions
+/// on its `TypeRegistration`, where they can be looked up by name and called dynamically.
+///
+/// Each method must be convertible into a `DynamicFunction` using `IntoFunction`:
+/// its arguments (including the receiver) must be owned values implementing `FromReflect`
+/// or references to reflected values, and its return type must be an owned reflected value.
+///
 /// # Field Attributes
 ///
 /// Along with the container attributes, this macro comes with some attributes that may be applied
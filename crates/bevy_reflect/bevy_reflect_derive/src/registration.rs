@@ -15,6 +15,7 @@ pub(crate) fn impl_get_type_registration(
     let type_path = meta.type_path();
     let bevy_reflect_path = meta.bevy_reflect_path();
     let registration_data = meta.traits().idents();
+    let functions = meta.traits().functions();
     let (impl_generics, ty_generics, where_clause) = type_path.generics().split_for_impl();
     let where_reflect_clause = extend_where_clause(where_clause, where_clause_options);
 
@@ -43,6 +44,10 @@ pub(crate) fn impl_get_type_registration(
                 #from_reflect_data
                 #serialization_data
                 #(registration.insert::<#registration_data>(#bevy_reflect_path::FromType::<Self>::from_type());)*
+                #(registration.register_function(
+                    #bevy_reflect_path::func::IntoFunction::into_function(Self::#functions)
+                        .with_name(::core::stringify!(#functions))
+                );)*
                 registration
             }
         }
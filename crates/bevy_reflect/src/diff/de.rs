@@ -0,0 +1,288 @@
+use crate::diff::ser::{PATCH_OP, PATCH_OP_VARIANTS};
+use crate::diff::{Patch, PatchOp};
+use crate::serde::{Ident, UntypedReflectDeserializer};
+use crate::{Reflect, TypeRegistry};
+use serde::de::{DeserializeSeed, EnumAccess, Error, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::Deserializer;
+use std::fmt::Formatter;
+
+/// A deserializer for a [`Patch`] serialized by a [`PatchSerializer`].
+///
+/// The values of the patch are deserialized like an [`UntypedReflectDeserializer`] would,
+/// so they may be returned as dynamic types (e.g. [`DynamicStruct`]),
+/// which can still be applied to their concrete types.
+///
+/// [`PatchSerializer`]: crate::diff::PatchSerializer
+/// [`DynamicStruct`]: crate::DynamicStruct
+pub struct PatchDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> PatchDeserializer<'a> {
+    /// Creates a deserializer resolving the types of the patched values in the given registry.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PatchDeserializer<'a> {
+    type Value = Patch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PatchVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct PatchVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PatchVisitor<'a> {
+    type Value = Patch;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence of patch operations")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut ops = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(op) = seq.next_element_seed(PatchOpDeserializer {
+            registry: self.registry,
+        })? {
+            ops.push(op);
+        }
+        Ok(Patch::from_ops(ops))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum PatchOpKind {
+    Replace,
+    ChangeVariant,
+    ListInsert,
+    ListRemove,
+    MapInsert,
+    MapRemove,
+}
+
+impl PatchOpKind {
+    const ALL: [Self; 6] = [
+        Self::Replace,
+        Self::ChangeVariant,
+        Self::ListInsert,
+        Self::ListRemove,
+        Self::MapInsert,
+        Self::MapRemove,
+    ];
+
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Replace | Self::ChangeVariant => &["path", "old", "new"],
+            Self::ListInsert | Self::ListRemove => &["path", "index", "value"],
+            Self::MapInsert | Self::MapRemove => &["path", "key", "value"],
+        }
+    }
+
+    fn is_list_op(self) -> bool {
+        matches!(self, Self::ListInsert | Self::ListRemove)
+    }
+
+    fn build(
+        self,
+        path: String,
+        index: usize,
+        first: Option<Box<dyn Reflect>>,
+        second: Box<dyn Reflect>,
+    ) -> PatchOp {
+        match (self, first) {
+            (Self::Replace, Some(old)) => PatchOp::Replace {
+                path,
+                old,
+                new: second,
+            },
+            (Self::ChangeVariant, Some(old)) => PatchOp::ChangeVariant {
+                path,
+                old,
+                new: second,
+            },
+            (Self::MapInsert, Some(key)) => PatchOp::MapInsert {
+                path,
+                key,
+                value: second,
+            },
+            (Self::MapRemove, Some(key)) => PatchOp::MapRemove {
+                path,
+                key,
+                value: second,
+            },
+            (Self::ListInsert, _) => PatchOp::ListInsert {
+                path,
+                index,
+                value: second,
+            },
+            (Self::ListRemove, _) => PatchOp::ListRemove {
+                path,
+                index,
+                value: second,
+            },
+            _ => unreachable!("the first value of non-list operations is always deserialized"),
+        }
+    }
+}
+
+struct PatchOpKindVisitor;
+
+impl<'de> Visitor<'de> for PatchOpKindVisitor {
+    type Value = PatchOpKind;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a patch operation name")
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+        PatchOpKind::ALL
+            .get(value as usize)
+            .copied()
+            .ok_or_else(|| Error::invalid_value(serde::de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        PATCH_OP_VARIANTS
+            .iter()
+            .position(|name| *name == value)
+            .map(|index| PatchOpKind::ALL[index])
+            .ok_or_else(|| Error::unknown_variant(value, PATCH_OP_VARIANTS))
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for PatchOpKindVisitor {
+    type Value = PatchOpKind;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+struct PatchOpDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PatchOpDeserializer<'a> {
+    type Value = PatchOp;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum(PATCH_OP, PATCH_OP_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for PatchOpDeserializer<'a> {
+    type Value = PatchOp;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a patch operation")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (kind, variant) = data.variant_seed(PatchOpKindVisitor)?;
+        variant.struct_variant(
+            kind.fields(),
+            PatchOpFieldsVisitor {
+                kind,
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct PatchOpFieldsVisitor<'a> {
+    kind: PatchOpKind,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PatchOpFieldsVisitor<'a> {
+    type Value = PatchOp;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("the fields of a patch operation")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let path: String = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let mut index = 0;
+        let mut first = None;
+        if self.kind.is_list_op() {
+            index = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(1, &self))?;
+        } else {
+            first = Some(
+                seq.next_element_seed(UntypedReflectDeserializer::new(self.registry))?
+                    .ok_or_else(|| Error::invalid_length(1, &self))?,
+            );
+        }
+        let second = seq
+            .next_element_seed(UntypedReflectDeserializer::new(self.registry))?
+            .ok_or_else(|| Error::invalid_length(2, &self))?;
+        Ok(self.kind.build(path, index, first, second))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let fields = self.kind.fields();
+        let mut path: Option<String> = None;
+        let mut index: Option<usize> = None;
+        let mut first = None;
+        let mut second = None;
+        while let Some(Ident(key)) = map.next_key()? {
+            match key.as_str() {
+                "path" => path = Some(map.next_value()?),
+                "index" if self.kind.is_list_op() => index = Some(map.next_value()?),
+                name if name == fields[1] => {
+                    first =
+                        Some(map.next_value_seed(UntypedReflectDeserializer::new(self.registry))?);
+                }
+                name if name == fields[2] => {
+                    second =
+                        Some(map.next_value_seed(UntypedReflectDeserializer::new(self.registry))?);
+                }
+                name => return Err(Error::unknown_field(name, fields)),
+            }
+        }
+
+        let path = path.ok_or_else(|| Error::missing_field("path"))?;
+        let index = match index {
+            Some(index) => index,
+            None if self.kind.is_list_op() => return Err(Error::missing_field("index")),
+            None => 0,
+        };
+        if first.is_none() && !self.kind.is_list_op() {
+            return Err(Error::missing_field(fields[1]));
+        }
+        let second = second.ok_or_else(|| Error::missing_field(fields[2]))?;
+        Ok(self.kind.build(path, index, first, second))
+    }
+}
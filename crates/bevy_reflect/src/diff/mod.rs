@@ -0,0 +1,277 @@
+//! Diffing and patching of reflected values.
+//!
+//! [`Patch::diff`] computes the changes between two values of the same type as a list of
+//! [`PatchOp`]s, each targeting a nested value through its [path](crate::GetPath):
+//! replaced values, enum variant changes, list insertions and removals,
+//! and map insertions and removals.
+//!
+//! A [`Patch`] can then be applied to a value, which uses [`Reflect::apply`] for replaced values,
+//! and [inverted](Patch::inverse) to undo it.
+//! Patches can also be sent or stored using [`PatchSerializer`] and [`PatchDeserializer`].
+//!
+//! ```
+//! # use bevy_reflect::{diff::Patch, Reflect};
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! struct Inventory {
+//!     gold: u32,
+//!     items: Vec<String>,
+//! }
+//!
+//! let old = Inventory {
+//!     gold: 10,
+//!     items: vec!["sword".to_string(), "shield".to_string()],
+//! };
+//! let new = Inventory {
+//!     gold: 5,
+//!     items: vec!["shield".to_string()],
+//! };
+//!
+//! let patch = Patch::diff(&old, &new).unwrap();
+//! assert_eq!(patch.len(), 2);
+//!
+//! let mut value = old.clone();
+//! patch.apply(&mut value).unwrap();
+//! assert_eq!(value, new);
+//!
+//! patch.inverse().apply(&mut value).unwrap();
+//! assert_eq!(value, old);
+//! ```
+//!
+//! [`Reflect::apply`]: crate::Reflect::apply
+
+mod de;
+mod patch;
+mod ser;
+
+pub use de::*;
+pub use patch::*;
+pub use ser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{Reflect, TypeRegistry};
+    use bevy_utils::HashMap;
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        position: (f32, f32),
+        health: Health,
+        state: State,
+        items: Vec<Item>,
+        scores: [u32; 2],
+        stats: HashMap<String, i32>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum State {
+        Idle,
+        Moving { speed: f32 },
+        Attacking(u32),
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Item {
+        id: u32,
+    }
+
+    fn player() -> Player {
+        Player {
+            name: "Ferris".to_string(),
+            position: (0.0, 0.0),
+            health: Health(100),
+            state: State::Moving { speed: 1.0 },
+            items: vec![Item { id: 1 }, Item { id: 2 }, Item { id: 3 }],
+            scores: [0, 0],
+            stats: HashMap::from([("strength".to_string(), 1), ("speed".to_string(), 2)]),
+        }
+    }
+
+    fn changed_player() -> Player {
+        Player {
+            name: "Ferris".to_string(),
+            position: (1.0, 0.0),
+            health: Health(50),
+            state: State::Attacking(3),
+            items: vec![
+                Item { id: 2 },
+                Item { id: 4 },
+                Item { id: 3 },
+                Item { id: 5 },
+            ],
+            scores: [0, 7],
+            stats: HashMap::from([("strength".to_string(), 5), ("luck".to_string(), 3)]),
+        }
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<Health>();
+        registry.register::<State>();
+        registry.register::<Item>();
+        registry.register::<(f32, f32)>();
+        registry.register::<Vec<Item>>();
+        registry.register::<[u32; 2]>();
+        registry.register::<HashMap<String, i32>>();
+        registry
+    }
+
+    #[test]
+    fn should_diff_nothing() {
+        let patch = Patch::diff(&player(), &player()).unwrap();
+        assert!(patch.is_empty());
+    }
+
+    #[test]
+    fn should_diff_fields() {
+        let old = player();
+        let mut new = player();
+        new.position.0 = 2.0;
+        new.health.0 = 10;
+        new.scores[1] = 4;
+
+        let patch = Patch::diff(&old, &new).unwrap();
+        let paths = patch.ops().iter().map(PatchOp::path).collect::<Vec<_>>();
+        assert_eq!(paths, vec![".position.0", ".health.0", ".scores[1]"]);
+        assert!(patch
+            .ops()
+            .iter()
+            .all(|op| matches!(op, PatchOp::Replace { .. })));
+    }
+
+    #[test]
+    fn should_diff_enum_variants() {
+        let old = player();
+        let mut new = player();
+        new.state = State::Moving { speed: 2.0 };
+        let patch = Patch::diff(&old, &new).unwrap();
+        assert_eq!(patch.ops()[0].path(), ".state.speed");
+        assert!(matches!(patch.ops()[0], PatchOp::Replace { .. }));
+
+        new.state = State::Idle;
+        let patch = Patch::diff(&old, &new).unwrap();
+        assert_eq!(patch.ops()[0].path(), ".state");
+        assert!(matches!(patch.ops()[0], PatchOp::ChangeVariant { .. }));
+    }
+
+    #[test]
+    fn should_diff_list_insertions_and_removals() {
+        let old = vec![1, 2, 3, 4];
+        let new = vec![0, 2, 4, 5, 6];
+        let patch = Patch::diff(&old, &new).unwrap();
+        assert!(patch
+            .ops()
+            .iter()
+            .all(|op| matches!(op, PatchOp::ListInsert { .. } | PatchOp::ListRemove { .. })));
+        assert_eq!(patch.len(), 5);
+
+        let mut value = old.clone();
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+        patch.inverse().apply(&mut value).unwrap();
+        assert_eq!(value, old);
+    }
+
+    #[test]
+    fn should_diff_long_lists() {
+        let old: Vec<u32> = (0..5000).collect();
+        let mut new = old.clone();
+        new.insert(2500, 0);
+        let patch = Patch::diff(&old, &new).unwrap();
+        assert_eq!(patch.len(), 1);
+        assert!(matches!(
+            patch.ops()[0],
+            PatchOp::ListInsert { index: 2500, .. }
+        ));
+
+        let new: Vec<u32> = (5000..7000).collect();
+        let patch = Patch::diff(&old, &new).unwrap();
+        assert_eq!(patch.len(), 7000);
+
+        let mut value = old.clone();
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+        patch.inverse().apply(&mut value).unwrap();
+        assert_eq!(value, old);
+    }
+
+    #[test]
+    fn should_apply_and_invert() {
+        let old = player();
+        let new = changed_player();
+        let patch = Patch::diff(&old, &new).unwrap();
+
+        let mut value = old.clone();
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+
+        patch.inverse().apply(&mut value).unwrap();
+        assert_eq!(value, old);
+    }
+
+    #[test]
+    fn should_serialize_patch() {
+        let registry = registry();
+        let old = player();
+        let new = changed_player();
+        let patch = Patch::diff(&old, &new).unwrap();
+
+        let serialized = ron::ser::to_string(&PatchSerializer::new(&patch, &registry)).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = PatchDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(deserialized.len(), patch.len());
+
+        let mut value = old.clone();
+        deserialized.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+        deserialized.inverse().apply(&mut value).unwrap();
+        assert_eq!(value, old);
+
+        let serialized = bincode::serialize(&PatchSerializer::new(&patch, &registry)).unwrap();
+        let deserialized = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(PatchDeserializer::new(&registry), &serialized)
+            .unwrap();
+        let mut value = old.clone();
+        deserialized.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn should_error_on_mismatched_types() {
+        assert_eq!(
+            Patch::diff(&1_u32, &1_i32).unwrap_err(),
+            DiffError::TypeMismatch {
+                path: String::new(),
+                old: "u32".to_string(),
+                new: "i32".to_string(),
+            }
+        );
+
+        let patch = Patch::diff(&1_u32, &2_u32).unwrap();
+        assert!(matches!(
+            patch.apply(&mut 1_i32),
+            Err(PatchError::TypeMismatch { .. })
+        ));
+
+        let patch = Patch::diff(&vec![1], &vec![1, 2]).unwrap();
+        assert!(matches!(
+            patch.apply(&mut Vec::<i32>::new()),
+            Err(PatchError::IndexOutOfBounds {
+                index: 1,
+                len: 0,
+                ..
+            })
+        ));
+    }
+}
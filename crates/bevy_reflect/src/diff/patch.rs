@@ -0,0 +1,580 @@
+use crate::{Enum, GetPath, List, Map, Reflect, ReflectMut, ReflectRef};
+use std::fmt::Write;
+use thiserror::Error;
+
+/// An error returned when computing a [`Patch`] between two values fails.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DiffError {
+    /// The values at `path` have different types, so no operation can turn one into the other.
+    #[error("cannot diff `{old}` with `{new}` at `{path}`: the values must have the same type")]
+    TypeMismatch {
+        /// The path of the values, relative to the diffed values.
+        path: String,
+        /// The type name of the old value.
+        old: String,
+        /// The type name of the new value.
+        new: String,
+    },
+}
+
+/// An error returned when applying a [`Patch`] fails.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PatchError {
+    /// The path of an operation doesn't lead to a value.
+    #[error("invalid path `{path}`: {message}")]
+    InvalidPath {
+        /// The path of the operation.
+        path: String,
+        /// Why the path couldn't be followed.
+        message: String,
+    },
+
+    /// The value at the path of an operation doesn't have the type the operation expects.
+    #[error("expected a value of type `{expected}` at `{path}` but found `{found}`")]
+    TypeMismatch {
+        /// The path of the operation.
+        path: String,
+        /// The type name of the value the operation sets.
+        expected: String,
+        /// The type name of the value found at the path.
+        found: String,
+    },
+
+    /// The value at the path of an operation isn't a list or map, as the operation expects.
+    #[error("expected a {expected} at `{path}`")]
+    UnexpectedKind {
+        /// The path of the operation.
+        path: String,
+        /// The kind of value the operation expects, like `list` or `map`.
+        expected: &'static str,
+    },
+
+    /// A list operation targets an index past the end of the list.
+    #[error("index {index} is out of bounds for the list at `{path}` of length {len}")]
+    IndexOutOfBounds {
+        /// The path of the list.
+        path: String,
+        /// The index targeted by the operation.
+        index: usize,
+        /// The length of the list.
+        len: usize,
+    },
+
+    /// A map operation removes a key the map doesn't contain.
+    #[error("the map at `{path}` has no entry for the removed key")]
+    MissingKey {
+        /// The path of the map.
+        path: String,
+    },
+}
+
+/// A single change between two reflected values.
+///
+/// Every operation targets the value at its `path`, using the syntax of [`GetPath`],
+/// and stores enough data to be [inverted](Self::inverse).
+#[derive(Debug)]
+pub enum PatchOp {
+    /// The value was replaced, as done by [`Reflect::apply`].
+    Replace {
+        path: String,
+        old: Box<dyn Reflect>,
+        new: Box<dyn Reflect>,
+    },
+    /// The enum switched to another variant.
+    ChangeVariant {
+        path: String,
+        old: Box<dyn Reflect>,
+        new: Box<dyn Reflect>,
+    },
+    /// An element was inserted into the list.
+    ListInsert {
+        path: String,
+        index: usize,
+        value: Box<dyn Reflect>,
+    },
+    /// An element was removed from the list.
+    ListRemove {
+        path: String,
+        index: usize,
+        value: Box<dyn Reflect>,
+    },
+    /// An entry was inserted into the map.
+    MapInsert {
+        path: String,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+    /// An entry was removed from the map.
+    MapRemove {
+        path: String,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+}
+
+impl PatchOp {
+    /// Returns the path of the value targeted by this operation.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Replace { path, .. }
+            | Self::ChangeVariant { path, .. }
+            | Self::ListInsert { path, .. }
+            | Self::ListRemove { path, .. }
+            | Self::MapInsert { path, .. }
+            | Self::MapRemove { path, .. } => path,
+        }
+    }
+
+    /// Returns the operation undoing this one.
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::Replace { path, old, new } => Self::Replace {
+                path: path.clone(),
+                old: new.clone_value(),
+                new: old.clone_value(),
+            },
+            Self::ChangeVariant { path, old, new } => Self::ChangeVariant {
+                path: path.clone(),
+                old: new.clone_value(),
+                new: old.clone_value(),
+            },
+            Self::ListInsert { path, index, value } => Self::ListRemove {
+                path: path.clone(),
+                index: *index,
+                value: value.clone_value(),
+            },
+            Self::ListRemove { path, index, value } => Self::ListInsert {
+                path: path.clone(),
+                index: *index,
+                value: value.clone_value(),
+            },
+            Self::MapInsert { path, key, value } => Self::MapRemove {
+                path: path.clone(),
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+            Self::MapRemove { path, key, value } => Self::MapInsert {
+                path: path.clone(),
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+        }
+    }
+
+    /// Applies this operation to `root`.
+    pub fn apply(&self, root: &mut dyn Reflect) -> Result<(), PatchError> {
+        let path = self.path();
+        let target = root
+            .reflect_path_mut(path)
+            .map_err(|error| PatchError::InvalidPath {
+                path: path.to_string(),
+                message: error.to_string(),
+            })?;
+
+        match self {
+            Self::Replace { new, .. } | Self::ChangeVariant { new, .. } => {
+                if target.type_name() != new.type_name() {
+                    return Err(PatchError::TypeMismatch {
+                        path: path.to_string(),
+                        expected: new.type_name().to_string(),
+                        found: target.type_name().to_string(),
+                    });
+                }
+                target.apply(new.as_ref());
+            }
+            Self::ListInsert { index, value, .. } => {
+                let list = as_list(target, path)?;
+                check_index(list, path, *index, list.len() + 1)?;
+                list.insert(*index, value.clone_value());
+            }
+            Self::ListRemove { index, .. } => {
+                let list = as_list(target, path)?;
+                check_index(list, path, *index, list.len())?;
+                list.remove(*index);
+            }
+            Self::MapInsert { key, value, .. } => {
+                let ReflectMut::Map(map) = target.reflect_mut() else {
+                    return Err(unexpected_kind(path, "map"));
+                };
+                map.insert_boxed(key.clone_value(), value.clone_value());
+            }
+            Self::MapRemove { key, .. } => {
+                let ReflectMut::Map(map) = target.reflect_mut() else {
+                    return Err(unexpected_kind(path, "map"));
+                };
+                if map.remove(key.as_ref()).is_none() {
+                    return Err(PatchError::MissingKey {
+                        path: path.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Clone for PatchOp {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Replace { path, old, new } => Self::Replace {
+                path: path.clone(),
+                old: old.clone_value(),
+                new: new.clone_value(),
+            },
+            Self::ChangeVariant { path, old, new } => Self::ChangeVariant {
+                path: path.clone(),
+                old: old.clone_value(),
+                new: new.clone_value(),
+            },
+            Self::ListInsert { path, index, value } => Self::ListInsert {
+                path: path.clone(),
+                index: *index,
+                value: value.clone_value(),
+            },
+            Self::ListRemove { path, index, value } => Self::ListRemove {
+                path: path.clone(),
+                index: *index,
+                value: value.clone_value(),
+            },
+            Self::MapInsert { path, key, value } => Self::MapInsert {
+                path: path.clone(),
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+            Self::MapRemove { path, key, value } => Self::MapRemove {
+                path: path.clone(),
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+        }
+    }
+}
+
+fn as_list<'a>(target: &'a mut dyn Reflect, path: &str) -> Result<&'a mut dyn List, PatchError> {
+    match target.reflect_mut() {
+        ReflectMut::List(list) => Ok(list),
+        _ => Err(unexpected_kind(path, "list")),
+    }
+}
+
+fn check_index(list: &dyn List, path: &str, index: usize, bound: usize) -> Result<(), PatchError> {
+    if index < bound {
+        Ok(())
+    } else {
+        Err(PatchError::IndexOutOfBounds {
+            path: path.to_string(),
+            index,
+            len: list.len(),
+        })
+    }
+}
+
+fn unexpected_kind(path: &str, expected: &'static str) -> PatchError {
+    PatchError::UnexpectedKind {
+        path: path.to_string(),
+        expected,
+    }
+}
+
+/// The list of changes turning a reflected value into another value of the same type.
+///
+/// A patch is computed with [`Patch::diff`], applied with [`Patch::apply`]
+/// and undone by applying its [inverse](Patch::inverse).
+/// It can be serialized with [`PatchSerializer`] and deserialized with [`PatchDeserializer`].
+///
+/// See the [module-level documentation](crate::diff) for more information.
+///
+/// [`PatchSerializer`]: crate::diff::PatchSerializer
+/// [`PatchDeserializer`]: crate::diff::PatchDeserializer
+#[derive(Debug, Clone, Default)]
+pub struct Patch {
+    ops: Vec<PatchOp>,
+}
+
+impl Patch {
+    /// Creates a patch from a list of operations, applied in order.
+    pub fn from_ops(ops: Vec<PatchOp>) -> Self {
+        Self { ops }
+    }
+
+    /// Computes the changes turning `old` into `new`.
+    ///
+    /// Structs, tuple structs, tuples, arrays and enums keeping the same variant are compared
+    /// field by field, so that only the changed fields are replaced.
+    /// Lists of the same length are compared element by element, while lists of different lengths
+    /// produce the insertions and removals of a minimal edit script, unless too many elements
+    /// differ, in which case the differing elements are all removed and re-inserted.
+    /// Map entries are inserted, removed, or removed and re-inserted when their value changed.
+    /// Any other value is replaced when [`Reflect::reflect_partial_eq`] doesn't consider both
+    /// values equal.
+    ///
+    /// Returns an error if the values, or any of their fields, don't have the same type.
+    pub fn diff(old: &dyn Reflect, new: &dyn Reflect) -> Result<Self, DiffError> {
+        let mut ops = Vec::new();
+        diff_value(&mut String::new(), old, new, &mut ops)?;
+        Ok(Self { ops })
+    }
+
+    /// The operations of this patch, in the order they are applied.
+    pub fn ops(&self) -> &[PatchOp] {
+        &self.ops
+    }
+
+    /// Returns the number of operations in this patch.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Returns `true` if this patch contains no changes.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Applies the changes of this patch to `root`.
+    ///
+    /// If an operation fails, the previous operations are not rolled back.
+    pub fn apply(&self, root: &mut dyn Reflect) -> Result<(), PatchError> {
+        self.ops.iter().try_for_each(|op| op.apply(root))
+    }
+
+    /// Returns the patch undoing this one.
+    pub fn inverse(&self) -> Self {
+        Self {
+            ops: self.ops.iter().rev().map(PatchOp::inverse).collect(),
+        }
+    }
+}
+
+fn diff_value(
+    path: &mut String,
+    old: &dyn Reflect,
+    new: &dyn Reflect,
+    ops: &mut Vec<PatchOp>,
+) -> Result<(), DiffError> {
+    if old.type_name() != new.type_name() {
+        return Err(type_mismatch(path, old, new));
+    }
+
+    match (old.reflect_ref(), new.reflect_ref()) {
+        (ReflectRef::Struct(old_struct), ReflectRef::Struct(new_struct)) => {
+            for (index, old_field) in old_struct.iter_fields().enumerate() {
+                let name = old_struct.name_at(index).unwrap();
+                let new_field = new_struct
+                    .field(name)
+                    .ok_or_else(|| type_mismatch(path, old, new))?;
+                with_segment(path, format_args!(".{name}"), |path| {
+                    diff_value(path, old_field, new_field, ops)
+                })?;
+            }
+        }
+        (ReflectRef::TupleStruct(old_struct), ReflectRef::TupleStruct(new_struct)) => {
+            for (index, old_field) in old_struct.iter_fields().enumerate() {
+                let new_field = new_struct
+                    .field(index)
+                    .ok_or_else(|| type_mismatch(path, old, new))?;
+                with_segment(path, format_args!(".{index}"), |path| {
+                    diff_value(path, old_field, new_field, ops)
+                })?;
+            }
+        }
+        (ReflectRef::Tuple(old_tuple), ReflectRef::Tuple(new_tuple)) => {
+            for (index, old_field) in old_tuple.iter_fields().enumerate() {
+                let new_field = new_tuple
+                    .field(index)
+                    .ok_or_else(|| type_mismatch(path, old, new))?;
+                with_segment(path, format_args!(".{index}"), |path| {
+                    diff_value(path, old_field, new_field, ops)
+                })?;
+            }
+        }
+        (ReflectRef::Enum(old_enum), ReflectRef::Enum(new_enum)) => {
+            diff_enum(path, old_enum, new_enum, ops)?;
+        }
+        (ReflectRef::Array(old_array), ReflectRef::Array(new_array)) => {
+            if old_array.len() != new_array.len() {
+                return Err(type_mismatch(path, old, new));
+            }
+            diff_elements(path, old_array.iter(), new_array.iter(), ops)?;
+        }
+        (ReflectRef::List(old_list), ReflectRef::List(new_list)) => {
+            if old_list.len() == new_list.len() {
+                diff_elements(path, old_list.iter(), new_list.iter(), ops)?;
+            } else {
+                diff_list(path, old_list, new_list, ops);
+            }
+        }
+        (ReflectRef::Map(old_map), ReflectRef::Map(new_map)) => {
+            diff_map(path, old_map, new_map, ops);
+        }
+        (ReflectRef::Value(_), ReflectRef::Value(_)) => {
+            if old.reflect_partial_eq(new) != Some(true) {
+                ops.push(PatchOp::Replace {
+                    path: path.clone(),
+                    old: old.clone_value(),
+                    new: new.clone_value(),
+                });
+            }
+        }
+        _ => return Err(type_mismatch(path, old, new)),
+    }
+    Ok(())
+}
+
+fn diff_enum(
+    path: &mut String,
+    old: &dyn Enum,
+    new: &dyn Enum,
+    ops: &mut Vec<PatchOp>,
+) -> Result<(), DiffError> {
+    if old.variant_name() != new.variant_name() {
+        ops.push(PatchOp::ChangeVariant {
+            path: path.clone(),
+            old: old.clone_value(),
+            new: new.clone_value(),
+        });
+        return Ok(());
+    }
+
+    for (index, old_field) in old.iter_fields().enumerate() {
+        let (new_field, segment) = match old_field.name() {
+            Some(name) => (new.field(name), format!(".{name}")),
+            None => (new.field_at(index), format!(".{index}")),
+        };
+        let new_field =
+            new_field.ok_or_else(|| type_mismatch(path, old.as_reflect(), new.as_reflect()))?;
+        with_segment(path, segment, |path| {
+            diff_value(path, old_field.value(), new_field, ops)
+        })?;
+    }
+    Ok(())
+}
+
+/// Diffs arrays or lists of the same length element by element.
+fn diff_elements<'a>(
+    path: &mut String,
+    old: impl Iterator<Item = &'a dyn Reflect>,
+    new: impl Iterator<Item = &'a dyn Reflect>,
+    ops: &mut Vec<PatchOp>,
+) -> Result<(), DiffError> {
+    for (index, (old_element, new_element)) in old.zip(new).enumerate() {
+        with_segment(path, format_args!("[{index}]"), |path| {
+            diff_value(path, old_element, new_element, ops)
+        })?;
+    }
+    Ok(())
+}
+
+/// The maximum number of cells of the table used by [`diff_list`], above which the differing
+/// elements are all removed and re-inserted instead of searching for a minimal edit script.
+const MAX_LIST_DIFF_CELLS: usize = 1 << 20;
+
+/// Diffs lists of different lengths, using the longest common subsequence of their elements
+/// to produce a minimal list of insertions and removals.
+fn diff_list(path: &str, old: &dyn List, new: &dyn List, ops: &mut Vec<PatchOp>) {
+    let equal = |i: usize, j: usize| {
+        old.get(i)
+            .unwrap()
+            .reflect_partial_eq(new.get(j).unwrap())
+            .unwrap_or(false)
+    };
+
+    // Only the elements between the common prefix and suffix need to be compared.
+    let prefix = (0..old.len().min(new.len()))
+        .take_while(|&i| equal(i, i))
+        .count();
+    let suffix = (1..=old.len().min(new.len()) - prefix)
+        .take_while(|&k| equal(old.len() - k, new.len() - k))
+        .count();
+    let (old_len, new_len) = (old.len() - prefix - suffix, new.len() - prefix - suffix);
+    let equal = |i: usize, j: usize| equal(prefix + i, prefix + j);
+    let insert = |j: usize, index: usize| PatchOp::ListInsert {
+        path: path.to_string(),
+        index: prefix + index,
+        value: new.get(prefix + j).unwrap().clone_value(),
+    };
+    let remove = |i: usize, index: usize| PatchOp::ListRemove {
+        path: path.to_string(),
+        index: prefix + index,
+        value: old.get(prefix + i).unwrap().clone_value(),
+    };
+
+    if (old_len + 1).saturating_mul(new_len + 1) > MAX_LIST_DIFF_CELLS {
+        ops.extend((0..old_len).map(|i| remove(i, 0)));
+        ops.extend((0..new_len).map(|j| insert(j, j)));
+        return;
+    }
+
+    // `lengths[i][j]` is the length of the longest common subsequence of `old[i..]` and `new[j..]`.
+    let mut lengths = vec![vec![0usize; new_len + 1]; old_len + 1];
+    for i in (0..old_len).rev() {
+        for j in (0..new_len).rev() {
+            lengths[i][j] = if equal(i, j) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    // The operations are applied in order, so `index` tracks the position in the patched list.
+    let (mut i, mut j, mut index) = (0, 0, 0);
+    while i < old_len || j < new_len {
+        if i < old_len && j < new_len && equal(i, j) {
+            i += 1;
+            j += 1;
+            index += 1;
+        } else if j < new_len && (i == old_len || lengths[i][j + 1] >= lengths[i + 1][j]) {
+            ops.push(insert(j, index));
+            j += 1;
+            index += 1;
+        } else {
+            ops.push(remove(i, index));
+            i += 1;
+        }
+    }
+}
+
+fn diff_map(path: &str, old: &dyn Map, new: &dyn Map, ops: &mut Vec<PatchOp>) {
+    let mut inserted = Vec::new();
+    for (key, old_value) in old.iter() {
+        let new_value = new.get(key);
+        if let Some(new_value) = new_value {
+            if old_value.reflect_partial_eq(new_value) == Some(true) {
+                continue;
+            }
+            inserted.push((key, new_value));
+        }
+        ops.push(PatchOp::MapRemove {
+            path: path.to_string(),
+            key: key.clone_value(),
+            value: old_value.clone_value(),
+        });
+    }
+    inserted.extend(new.iter().filter(|(key, _)| old.get(*key).is_none()));
+    ops.extend(inserted.into_iter().map(|(key, value)| PatchOp::MapInsert {
+        path: path.to_string(),
+        key: key.clone_value(),
+        value: value.clone_value(),
+    }));
+}
+
+/// Appends a segment to `path` for the duration of `f`.
+fn with_segment<T>(
+    path: &mut String,
+    segment: impl std::fmt::Display,
+    f: impl FnOnce(&mut String) -> T,
+) -> T {
+    let len = path.len();
+    write!(path, "{segment}").unwrap();
+    let result = f(path);
+    path.truncate(len);
+    result
+}
+
+fn type_mismatch(path: &str, old: &dyn Reflect, new: &dyn Reflect) -> DiffError {
+    DiffError::TypeMismatch {
+        path: path.to_string(),
+        old: old.type_name().to_string(),
+        new: new.type_name().to_string(),
+    }
+}
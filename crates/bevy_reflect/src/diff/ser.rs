@@ -0,0 +1,90 @@
+use crate::diff::{Patch, PatchOp};
+use crate::serde::ReflectSerializer;
+use crate::TypeRegistry;
+use serde::ser::{SerializeSeq, SerializeStructVariant};
+use serde::{Serialize, Serializer};
+
+pub(super) const PATCH_OP: &str = "PatchOp";
+pub(super) const PATCH_OP_VARIANTS: &[&str] = &[
+    "Replace",
+    "ChangeVariant",
+    "ListInsert",
+    "ListRemove",
+    "MapInsert",
+    "MapRemove",
+];
+
+/// A serializer for a [`Patch`].
+///
+/// The values of the patch are serialized like a [`ReflectSerializer`] would,
+/// so every one of their types must be registered in the [`TypeRegistry`]
+/// along with the type data needed to serialize it.
+pub struct PatchSerializer<'a> {
+    pub patch: &'a Patch,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> PatchSerializer<'a> {
+    pub fn new(patch: &'a Patch, registry: &'a TypeRegistry) -> Self {
+        PatchSerializer { patch, registry }
+    }
+}
+
+impl<'a> Serialize for PatchSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.patch.len()))?;
+        for op in self.patch.ops() {
+            state.serialize_element(&PatchOpSerializer {
+                op,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct PatchOpSerializer<'a> {
+    op: &'a PatchOp,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for PatchOpSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let variant_index = match self.op {
+            PatchOp::Replace { .. } => 0,
+            PatchOp::ChangeVariant { .. } => 1,
+            PatchOp::ListInsert { .. } => 2,
+            PatchOp::ListRemove { .. } => 3,
+            PatchOp::MapInsert { .. } => 4,
+            PatchOp::MapRemove { .. } => 5,
+        };
+        let mut state = serializer.serialize_struct_variant(
+            PATCH_OP,
+            variant_index,
+            PATCH_OP_VARIANTS[variant_index as usize],
+            3,
+        )?;
+        state.serialize_field("path", self.op.path())?;
+        match self.op {
+            PatchOp::Replace { old, new, .. } | PatchOp::ChangeVariant { old, new, .. } => {
+                state.serialize_field("old", &ReflectSerializer::new(&**old, self.registry))?;
+                state.serialize_field("new", &ReflectSerializer::new(&**new, self.registry))?;
+            }
+            PatchOp::ListInsert { index, value, .. } | PatchOp::ListRemove { index, value, .. } => {
+                state.serialize_field("index", index)?;
+                state.serialize_field("value", &ReflectSerializer::new(&**value, self.registry))?;
+            }
+            PatchOp::MapInsert { key, value, .. } | PatchOp::MapRemove { key, value, .. } => {
+                state.serialize_field("key", &ReflectSerializer::new(&**key, self.registry))?;
+                state.serialize_field("value", &ReflectSerializer::new(&**value, self.registry))?;
+            }
+        }
+        state.end()
+    }
+}
@@ -0,0 +1,278 @@
+use crate::func::FunctionError;
+use crate::{FromReflect, Reflect, TypeInfo, Typed};
+use std::fmt::{Display, Formatter};
+
+/// How an argument is passed to a [`DynamicFunction`].
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ownership {
+    /// The argument is passed by value (i.e. `T`).
+    Owned,
+    /// The argument is passed by shared reference (i.e. `&T`).
+    Ref,
+    /// The argument is passed by mutable reference (i.e. `&mut T`).
+    Mut,
+}
+
+impl Display for Ownership {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Owned => write!(f, "owned"),
+            Self::Ref => write!(f, "a reference"),
+            Self::Mut => write!(f, "a mutable reference"),
+        }
+    }
+}
+
+/// A single argument passed to a [`DynamicFunction`].
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug)]
+pub enum Arg<'a> {
+    /// An owned value.
+    Owned(Box<dyn Reflect>),
+    /// A borrowed value.
+    Ref(&'a dyn Reflect),
+    /// A mutably borrowed value.
+    Mut(&'a mut dyn Reflect),
+}
+
+impl<'a> Arg<'a> {
+    /// Returns how this argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        match self {
+            Self::Owned(_) => Ownership::Owned,
+            Self::Ref(_) => Ownership::Ref,
+            Self::Mut(_) => Ownership::Mut,
+        }
+    }
+
+    /// Returns the underlying value of this argument.
+    pub fn value(&self) -> &dyn Reflect {
+        match self {
+            Self::Owned(value) => value.as_ref(),
+            Self::Ref(value) => *value,
+            Self::Mut(value) => &**value,
+        }
+    }
+}
+
+/// The ordered list of arguments passed to a [`DynamicFunction`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::func::ArgList;
+/// let mut name = String::from("Ferris");
+/// let args = ArgList::new()
+///     .push_owned(123_i32)
+///     .push_ref(&1.5_f32)
+///     .push_mut(&mut name);
+///
+/// assert_eq!(args.len(), 3);
+/// ```
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug, Default)]
+pub struct ArgList<'a>(Vec<Arg<'a>>);
+
+impl<'a> ArgList<'a> {
+    /// Creates an empty argument list.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Appends an [`Arg`] to the list.
+    pub fn push_arg(mut self, arg: Arg<'a>) -> Self {
+        self.0.push(arg);
+        self
+    }
+
+    /// Appends an owned value to the list.
+    pub fn push_owned<T: Reflect>(self, value: T) -> Self {
+        self.push_arg(Arg::Owned(Box::new(value)))
+    }
+
+    /// Appends an owned, boxed value to the list.
+    pub fn push_boxed(self, value: Box<dyn Reflect>) -> Self {
+        self.push_arg(Arg::Owned(value))
+    }
+
+    /// Appends a reference to the list.
+    pub fn push_ref(self, value: &'a dyn Reflect) -> Self {
+        self.push_arg(Arg::Ref(value))
+    }
+
+    /// Appends a mutable reference to the list.
+    pub fn push_mut(self, value: &'a mut dyn Reflect) -> Self {
+        self.push_arg(Arg::Mut(value))
+    }
+
+    /// Returns the number of arguments in the list.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the list contains no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for ArgList<'a> {
+    type Item = Arg<'a>;
+    type IntoIter = std::vec::IntoIter<Arg<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Information about an argument of a [`DynamicFunction`].
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug, Clone)]
+pub struct ArgInfo {
+    index: usize,
+    ownership: Ownership,
+    type_info: &'static TypeInfo,
+}
+
+impl ArgInfo {
+    /// Creates the info of an argument of type `T` passed with the given [`Ownership`].
+    ///
+    /// The index of the argument is set by [`FunctionInfo::new`](crate::func::FunctionInfo::new).
+    pub fn new<T: Typed>(ownership: Ownership) -> Self {
+        Self {
+            index: 0,
+            ownership,
+            type_info: T::type_info(),
+        }
+    }
+
+    pub(crate) fn with_index(mut self, index: usize) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// The position of the argument in the argument list.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// How the argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// The [`TypeInfo`] of the argument, without the reference.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// The [type name] of the argument, without the reference.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_info.type_name()
+    }
+
+    fn ownership_error(&self, received: Ownership) -> FunctionError {
+        FunctionError::ArgOwnership {
+            index: self.index,
+            expected: self.ownership,
+            received,
+        }
+    }
+
+    fn type_error(&self, received: &dyn Reflect) -> FunctionError {
+        FunctionError::ArgType {
+            index: self.index,
+            expected: self.type_name(),
+            received: received.type_name().to_string(),
+        }
+    }
+}
+
+/// A type that can be extracted from an [`Arg`].
+///
+/// This is implemented for owned values implementing [`FromReflect`],
+/// as well as for shared and mutable references to reflected values.
+/// The `Marker` parameter disambiguates these implementations and is always inferred.
+pub trait FromArg<Marker> {
+    /// The type extracted from an argument borrowed for `'a`.
+    type Item<'a>;
+
+    /// Returns the info of this argument type.
+    fn arg_info() -> ArgInfo;
+
+    /// Extracts the argument, checking its ownership and type against `info`.
+    fn from_arg<'a>(arg: Arg<'a>, info: &ArgInfo) -> Result<Self::Item<'a>, FunctionError>;
+}
+
+/// [`FromArg`] marker for arguments passed by value.
+pub struct OwnedArg;
+
+/// [`FromArg`] marker for arguments passed by shared reference.
+pub struct RefArg;
+
+/// [`FromArg`] marker for arguments passed by mutable reference.
+pub struct MutArg;
+
+impl<T: FromReflect + Typed> FromArg<OwnedArg> for T {
+    type Item<'a> = T;
+
+    fn arg_info() -> ArgInfo {
+        ArgInfo::new::<T>(Ownership::Owned)
+    }
+
+    fn from_arg<'a>(arg: Arg<'a>, info: &ArgInfo) -> Result<Self::Item<'a>, FunctionError> {
+        match arg {
+            Arg::Owned(value) => {
+                T::take_from_reflect(value).map_err(|value| info.type_error(value.as_ref()))
+            }
+            arg => Err(info.ownership_error(arg.ownership())),
+        }
+    }
+}
+
+impl<T: Reflect + Typed> FromArg<RefArg> for &T {
+    type Item<'a> = &'a T;
+
+    fn arg_info() -> ArgInfo {
+        ArgInfo::new::<T>(Ownership::Ref)
+    }
+
+    fn from_arg<'a>(arg: Arg<'a>, info: &ArgInfo) -> Result<Self::Item<'a>, FunctionError> {
+        let value: &'a dyn Reflect = match arg {
+            Arg::Ref(value) => value,
+            Arg::Mut(value) => value,
+            arg => return Err(info.ownership_error(arg.ownership())),
+        };
+        value
+            .downcast_ref::<T>()
+            .ok_or_else(|| info.type_error(value))
+    }
+}
+
+impl<T: Reflect + Typed> FromArg<MutArg> for &mut T {
+    type Item<'a> = &'a mut T;
+
+    fn arg_info() -> ArgInfo {
+        ArgInfo::new::<T>(Ownership::Mut)
+    }
+
+    fn from_arg<'a>(arg: Arg<'a>, info: &ArgInfo) -> Result<Self::Item<'a>, FunctionError> {
+        match arg {
+            Arg::Mut(value) => {
+                if value.is::<T>() {
+                    Ok(value.downcast_mut::<T>().unwrap())
+                } else {
+                    Err(info.type_error(value))
+                }
+            }
+            arg => Err(info.ownership_error(arg.ownership())),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::func::Ownership;
+use thiserror::Error;
+
+/// An error that occurs when calling a [`DynamicFunction`](crate::func::DynamicFunction).
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FunctionError {
+    #[error("expected {expected} arguments but received {received}")]
+    ArgCount { expected: usize, received: usize },
+
+    #[error("expected argument {index} to be of type `{expected}` but received `{received}`")]
+    ArgType {
+        index: usize,
+        expected: &'static str,
+        received: String,
+    },
+
+    #[error("expected argument {index} to be {expected} but received {received}")]
+    ArgOwnership {
+        index: usize,
+        expected: Ownership,
+        received: Ownership,
+    },
+}
@@ -0,0 +1,167 @@
+use crate::func::{ArgInfo, ArgList, FunctionError};
+use crate::{Reflect, TypeInfo, Typed};
+use std::borrow::Cow;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// The result of calling a [`DynamicFunction`].
+pub type FunctionResult = Result<Box<dyn Reflect>, FunctionError>;
+
+/// Information about the return value of a [`DynamicFunction`].
+#[derive(Debug, Clone)]
+pub struct ReturnInfo {
+    type_info: &'static TypeInfo,
+}
+
+impl ReturnInfo {
+    /// Creates the info of a return value of type `T`.
+    pub fn new<T: Typed>() -> Self {
+        Self {
+            type_info: T::type_info(),
+        }
+    }
+
+    /// The [`TypeInfo`] of the return value.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// The [type name] of the return value.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_info.type_name()
+    }
+}
+
+/// Information about a [`DynamicFunction`]: its name, arguments and return value.
+#[derive(Debug, Clone)]
+pub struct FunctionInfo {
+    name: Cow<'static, str>,
+    args: Vec<ArgInfo>,
+    return_info: ReturnInfo,
+}
+
+impl FunctionInfo {
+    /// Creates the info of a function with the given name, arguments and return value.
+    ///
+    /// The arguments are indexed in the order they are given.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        args: impl IntoIterator<Item = ArgInfo>,
+        return_info: ReturnInfo,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            args: args
+                .into_iter()
+                .enumerate()
+                .map(|(index, arg)| arg.with_index(index))
+                .collect(),
+            return_info,
+        }
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The arguments of the function, in order.
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    /// The number of arguments the function takes.
+    pub fn arg_count(&self) -> usize {
+        self.args.len()
+    }
+
+    /// The return value of the function.
+    pub fn return_info(&self) -> &ReturnInfo {
+        &self.return_info
+    }
+}
+
+/// A type-erased function that can be called with an [`ArgList`] of reflected values.
+///
+/// Functions and methods are most easily turned into a `DynamicFunction` with
+/// [`IntoFunction`](crate::func::IntoFunction).
+/// Calling it checks the number of arguments, and the ownership and type of each
+/// argument against its [`FunctionInfo`], before forwarding them to the underlying function.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::func::{ArgList, IntoFunction};
+/// fn add(a: i32, b: &i32) -> i32 {
+///     a + *b
+/// }
+///
+/// let function = add.into_function();
+/// let result = function
+///     .call(ArgList::new().push_owned(25_i32).push_ref(&75_i32))
+///     .unwrap();
+/// assert_eq!(result.downcast_ref::<i32>(), Some(&100));
+///
+/// assert!(function.call(ArgList::new().push_owned(25_i32)).is_err());
+/// ```
+#[derive(Clone)]
+pub struct DynamicFunction {
+    info: FunctionInfo,
+    func: Arc<dyn for<'a> Fn(ArgList<'a>, &FunctionInfo) -> FunctionResult + Send + Sync>,
+}
+
+impl DynamicFunction {
+    /// Creates a function from a closure and its [`FunctionInfo`].
+    ///
+    /// The closure is only called with the number of arguments described in `info`,
+    /// but must check their ownership and types itself.
+    pub fn new<F>(func: F, info: FunctionInfo) -> Self
+    where
+        F: for<'a> Fn(ArgList<'a>, &FunctionInfo) -> FunctionResult + Send + Sync + 'static,
+    {
+        Self {
+            info,
+            func: Arc::new(func),
+        }
+    }
+
+    /// Sets the name of this function.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.info.name = name.into();
+        self
+    }
+
+    /// The name of this function.
+    pub fn name(&self) -> &str {
+        self.info.name()
+    }
+
+    /// The [`FunctionInfo`] of this function.
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// Calls this function with the given arguments.
+    ///
+    /// Returns an error if the number of arguments doesn't match, or if any of them
+    /// has the wrong ownership or type.
+    pub fn call(&self, args: ArgList) -> FunctionResult {
+        if args.len() != self.info.arg_count() {
+            return Err(FunctionError::ArgCount {
+                expected: self.info.arg_count(),
+                received: args.len(),
+            });
+        }
+        (self.func)(args, &self.info)
+    }
+}
+
+impl Debug for DynamicFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicFunction")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
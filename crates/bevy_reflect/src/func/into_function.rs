@@ -0,0 +1,85 @@
+use crate::func::{DynamicFunction, FromArg, FunctionInfo, ReturnInfo};
+use crate::{Reflect, Typed};
+use bevy_utils::all_tuples;
+
+/// A trait for types that can be converted into a [`DynamicFunction`].
+///
+/// This is implemented for functions and closures taking up to 15 arguments, where every
+/// argument implements [`FromArg`] and the return type implements [`Reflect`] and [`Typed`].
+/// This covers methods as well, with the receiver as the first argument:
+///
+/// ```
+/// # use bevy_reflect::{func::{ArgList, IntoFunction}, Reflect};
+/// #[derive(Reflect)]
+/// struct Counter(u32);
+///
+/// impl Counter {
+///     fn increment(&mut self, amount: u32) -> u32 {
+///         self.0 += amount;
+///         self.0
+///     }
+/// }
+///
+/// let increment = Counter::increment.into_function();
+///
+/// let mut counter = Counter(1);
+/// let result = increment
+///     .call(ArgList::new().push_mut(&mut counter).push_owned(2_u32))
+///     .unwrap();
+/// assert_eq!(result.downcast_ref::<u32>(), Some(&3));
+/// ```
+///
+/// The name of the resulting function defaults to the [type name] of `Self`,
+/// which is the full path of the function for function items.
+/// It can be changed with [`DynamicFunction::with_name`].
+///
+/// Functions returning references aren't supported, as the returned value must be owned.
+///
+/// [type name]: std::any::type_name
+pub trait IntoFunction<Marker> {
+    /// Converts `self` into a [`DynamicFunction`].
+    fn into_function(self) -> DynamicFunction;
+}
+
+impl IntoFunction<()> for DynamicFunction {
+    fn into_function(self) -> DynamicFunction {
+        self
+    }
+}
+
+macro_rules! impl_into_function {
+    ($(($arg: ident, $marker: ident)),*) => {
+        impl<F, R, $($arg, $marker,)*> IntoFunction<fn($(($arg, $marker),)*) -> R> for F
+        where
+            F: Fn($($arg),*) -> R
+                + for<'a> Fn($(<$arg as FromArg<$marker>>::Item<'a>),*) -> R
+                + Send
+                + Sync
+                + 'static,
+            R: Reflect + Typed,
+            $($arg: FromArg<$marker>,)*
+        {
+            fn into_function(self) -> DynamicFunction {
+                let info = FunctionInfo::new(
+                    std::any::type_name::<F>(),
+                    [$($arg::arg_info()),*],
+                    ReturnInfo::new::<R>(),
+                );
+                DynamicFunction::new(
+                    #[allow(unused_variables, unused_mut, non_snake_case)]
+                    move |args, info| {
+                        let mut args = args.into_iter().zip(info.args());
+                        $(
+                            let (arg, arg_info) = args.next().unwrap();
+                            let $arg = $arg::from_arg(arg, arg_info)?;
+                        )*
+                        Ok(Box::new((self)($($arg),*)))
+                    },
+                    info,
+                )
+            }
+        }
+    };
+}
+
+all_tuples!(impl_into_function, 0, 15, A, M);
@@ -0,0 +1,197 @@
+//! Reflection for functions and methods.
+//!
+//! Any function or closure whose arguments implement [`FromArg`] and whose return type
+//! implements [`Reflect`] can be turned into a [`DynamicFunction`] using [`IntoFunction`].
+//! The resulting function can then be called with an [`ArgList`] of reflected values,
+//! which is validated against the [`FunctionInfo`] of the function.
+//!
+//! Free functions can be registered by name with [`TypeRegistry::register_function`],
+//! and methods can be registered on the [`TypeRegistration`] of their type,
+//! either manually with [`TypeRegistration::register_function`]
+//! or with the `#[reflect(functions(...))]` attribute of the [`Reflect`] derive:
+//!
+//! ```
+//! # use bevy_reflect::{func::ArgList, Reflect, TypeRegistry};
+//! #[derive(Reflect)]
+//! #[reflect(functions(damage, is_alive))]
+//! struct Health(f32);
+//!
+//! impl Health {
+//!     fn damage(&mut self, amount: f32) {
+//!         self.0 -= amount;
+//!     }
+//!
+//!     fn is_alive(&self) -> bool {
+//!         self.0 > 0.0
+//!     }
+//! }
+//!
+//! let mut registry = TypeRegistry::default();
+//! registry.register::<Health>();
+//!
+//! let registration = registry.get(std::any::TypeId::of::<Health>()).unwrap();
+//! let mut health = Health(10.0);
+//!
+//! let damage = registration.get_function("damage").unwrap();
+//! damage
+//!     .call(ArgList::new().push_mut(&mut health).push_owned(15.0_f32))
+//!     .unwrap();
+//!
+//! let is_alive = registration.get_function("is_alive").unwrap();
+//! let result = is_alive.call(ArgList::new().push_ref(&health)).unwrap();
+//! assert_eq!(result.downcast_ref::<bool>(), Some(&false));
+//! ```
+//!
+//! [`Reflect`]: crate::Reflect
+//! [`TypeRegistry::register_function`]: crate::TypeRegistry::register_function
+//! [`TypeRegistration`]: crate::TypeRegistration
+//! [`TypeRegistration::register_function`]: crate::TypeRegistration::register_function
+
+mod args;
+mod error;
+mod function;
+mod into_function;
+
+pub use args::*;
+pub use error::*;
+pub use function::*;
+pub use into_function::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{DynamicStruct, Reflect, TypeRegistry};
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Foo {
+        value: i32,
+    }
+
+    impl Foo {
+        fn get(&self) -> i32 {
+            self.value
+        }
+
+        fn set(&mut self, value: i32) {
+            self.value = value;
+        }
+    }
+
+    fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    #[test]
+    fn should_call_function() {
+        let function = add.into_function();
+        assert_eq!(function.info().arg_count(), 2);
+        assert_eq!(function.info().return_info().type_name(), "i32");
+
+        let result = function
+            .call(ArgList::new().push_owned(1_i32).push_owned(2_i32))
+            .unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&3));
+    }
+
+    #[test]
+    fn should_call_methods() {
+        let get = Foo::get.into_function();
+        let set = Foo::set.into_function();
+
+        let mut foo = Foo { value: 1 };
+        set.call(ArgList::new().push_mut(&mut foo).push_owned(5_i32))
+            .unwrap();
+        assert_eq!(foo, Foo { value: 5 });
+
+        let result = get.call(ArgList::new().push_ref(&foo)).unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&5));
+    }
+
+    #[test]
+    fn should_call_closure() {
+        let offset = 10;
+        let function = (move |value: i32| value + offset).into_function();
+        let result = function.call(ArgList::new().push_owned(1_i32)).unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&11));
+    }
+
+    #[test]
+    fn should_convert_dynamic_arguments() {
+        let function = (|foo: Foo| foo.value).into_function();
+
+        let mut dynamic = DynamicStruct::default();
+        dynamic.insert("value", 7_i32);
+        let result = function.call(ArgList::new().push_owned(dynamic)).unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&7));
+    }
+
+    #[test]
+    fn should_validate_arguments() {
+        let function = Foo::set.into_function();
+        let mut foo = Foo { value: 1 };
+
+        assert_eq!(
+            function
+                .call(ArgList::new().push_mut(&mut foo))
+                .unwrap_err(),
+            FunctionError::ArgCount {
+                expected: 2,
+                received: 1
+            }
+        );
+        assert_eq!(
+            function
+                .call(ArgList::new().push_ref(&foo).push_owned(1_i32))
+                .unwrap_err(),
+            FunctionError::ArgOwnership {
+                index: 0,
+                expected: Ownership::Mut,
+                received: Ownership::Ref,
+            }
+        );
+        assert_eq!(
+            function
+                .call(ArgList::new().push_mut(&mut foo).push_owned(1_u8))
+                .unwrap_err(),
+            FunctionError::ArgType {
+                index: 1,
+                expected: "i32",
+                received: "u8".to_string(),
+            }
+        );
+        assert_eq!(foo, Foo { value: 1 });
+    }
+
+    #[test]
+    fn should_register_functions() {
+        let mut registry = TypeRegistry::default();
+        registry.register_function(add.into_function().with_name("add"));
+        registry.register::<Foo>();
+        registry
+            .get_mut(std::any::TypeId::of::<Foo>())
+            .unwrap()
+            .register_function(Foo::get.into_function().with_name("get"));
+
+        let add = registry.get_function("add").unwrap();
+        let result = add
+            .call(ArgList::new().push_owned(1_i32).push_owned(1_i32))
+            .unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&2));
+
+        let registration = registry.get(std::any::TypeId::of::<Foo>()).unwrap();
+        assert_eq!(
+            registration
+                .functions()
+                .map(|f| f.name())
+                .collect::<Vec<_>>(),
+            vec!["get"]
+        );
+        let result = registration
+            .get_function("get")
+            .unwrap()
+            .call(ArgList::new().push_ref(&Foo { value: 3 }))
+            .unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&3));
+    }
+}
@@ -399,10 +399,11 @@
 //! Another limitation is the inability to fully reflect functions and methods.
 //! Most languages offer some way of calling methods dynamically,
 //! but Rust makes this very difficult to do.
-//! For non-generic methods, this can be done by registering custom [type data] that
-//! contains function pointers.
-//! For generic methods, the same can be done but will typically require manual monomorphization
-//! (i.e. manually specifying the types the generic method can take).
+//! Non-generic functions and methods taking and returning reflected values can be
+//! turned into a [`DynamicFunction`] and called by name (see the [`func`] module).
+//! Generic functions require manual monomorphization
+//! (i.e. manually registering each instance of the function for the types it can take),
+//! and functions returning references aren't supported.
 //!
 //! ## Manual Registration
 //!
@@ -447,6 +448,7 @@
 //! [derive macro documentation]: derive@crate::Reflect
 //! [deriving `Reflect`]: derive@crate::Reflect
 //! [type data]: TypeData
+//! [`DynamicFunction`]: func::DynamicFunction
 //! [`ReflectDefault`]: std_traits::ReflectDefault
 //! [object-safe]: https://doc.rust-lang.org/reference/items/traits.html#object-safety
 //! [`serde`]: ::serde
@@ -504,7 +506,9 @@ mod impls {
     pub use self::uuid::*;
 }
 
+pub mod diff;
 mod enums;
+pub mod func;
 pub mod serde;
 pub mod std_traits;
 pub mod utility;
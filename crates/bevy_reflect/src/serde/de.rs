@@ -159,7 +159,7 @@ impl<T: Display> Debug for ExpectedValues<T> {
 
 /// Represents a simple reflected identifier.
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Ident(String);
+pub(crate) struct Ident(pub(crate) String);
 
 impl<'de> Deserialize<'de> for Ident {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -1,8 +1,10 @@
 mod de;
+mod schema;
 mod ser;
 mod type_data;
 
 pub use de::*;
+pub use schema::*;
 pub use ser::*;
 pub use type_data::*;
 
@@ -0,0 +1,1106 @@
+use crate::serde::SerializationData;
+use crate::{
+    EnumInfo, NamedField, ReflectDeserialize, TypeInfo, TypeRegistration, TypeRegistry,
+    UnnamedField, VariantInfo,
+};
+use bevy_utils::HashMap;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::any::TypeId;
+use std::fmt::Write;
+
+/// The JSON Schema dialect used by the schemas exported by a [`JsonSchemaExporter`].
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A primitive type of the JSON data model, used by the `type` keyword of a [`JsonSchema`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonType {
+    /// The name of this type in a JSON Schema.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+}
+
+/// A [JSON Schema] describing some serialized data.
+///
+/// Only the keywords needed to describe the output of a [`ReflectSerializer`] are supported.
+/// Empty keywords are omitted when serializing the schema,
+/// so the default value is the schema accepting any value.
+///
+/// [JSON Schema]: https://json-schema.org
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JsonSchema {
+    /// The `$schema` keyword, set on the root of exported schemas.
+    pub schema: Option<String>,
+    /// The `$ref` keyword, see [`JsonSchema::definition`].
+    pub reference: Option<String>,
+    /// The `title` keyword.
+    pub title: Option<String>,
+    /// The `description` keyword, set from doc comments with the `documentation` feature.
+    pub description: Option<String>,
+    /// The `type` keyword.
+    pub json_type: Option<JsonType>,
+    /// The `const` keyword, restricted to strings.
+    pub constant: Option<String>,
+    /// The `format` keyword, like `uuid`.
+    pub format: Option<String>,
+    /// The `pattern` keyword, a regular expression strings must match.
+    pub pattern: Option<String>,
+    /// The `minimum` keyword, the inclusive lower bound of numbers.
+    pub minimum: Option<i64>,
+    /// The `maximum` keyword, the inclusive upper bound of numbers.
+    pub maximum: Option<i64>,
+    /// The `minLength` keyword, counted in characters.
+    pub min_length: Option<usize>,
+    /// The `maxLength` keyword, counted in characters.
+    pub max_length: Option<usize>,
+    /// The `properties` keyword: the schemas of the named properties of objects, in order.
+    pub properties: Vec<(String, JsonSchema)>,
+    /// The `required` keyword: the properties objects must have.
+    pub required: Vec<String>,
+    /// The `additionalProperties` keyword: the schema of the properties not in
+    /// [`properties`](Self::properties).
+    pub additional_properties: Option<Box<JsonSchema>>,
+    /// The `propertyNames` keyword: the schema the property names of objects must match.
+    pub property_names: Option<Box<JsonSchema>>,
+    /// The `minProperties` keyword.
+    pub min_properties: Option<usize>,
+    /// The `maxProperties` keyword.
+    pub max_properties: Option<usize>,
+    /// The `prefixItems` keyword: the schemas of the first items of arrays, in order.
+    pub prefix_items: Vec<JsonSchema>,
+    /// The `items` keyword: the schema of the items after the [`prefix_items`](Self::prefix_items).
+    pub items: Option<Box<JsonSchema>>,
+    /// The `minItems` keyword.
+    pub min_items: Option<usize>,
+    /// The `maxItems` keyword.
+    pub max_items: Option<usize>,
+    /// The `anyOf` keyword: values must match at least one of these schemas.
+    pub any_of: Vec<JsonSchema>,
+    /// The `oneOf` keyword: values must match exactly one of these schemas.
+    pub one_of: Vec<JsonSchema>,
+    /// The `not` keyword: values must not match this schema.
+    pub not: Option<Box<JsonSchema>>,
+    /// The `$defs` keyword, set on the root of exported schemas.
+    pub defs: Vec<(String, JsonSchema)>,
+}
+
+impl JsonSchema {
+    /// A schema accepting any value.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// A schema rejecting every value.
+    pub fn never() -> Self {
+        Self {
+            not: Some(Box::new(Self::any())),
+            ..Default::default()
+        }
+    }
+
+    /// A schema accepting any value of the given [`JsonType`].
+    pub fn of_type(json_type: JsonType) -> Self {
+        Self {
+            json_type: Some(json_type),
+            ..Default::default()
+        }
+    }
+
+    /// A schema referencing the definition of the type with the given [type name],
+    /// as found in the `$defs` of an exported schema.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn definition(type_name: &str) -> Self {
+        let mut reference = String::from("#/$defs/");
+        for char in type_name.chars() {
+            match char {
+                // JSON Pointer escapes
+                '~' => reference.push_str("~0"),
+                '/' => reference.push_str("~1"),
+                // Characters allowed in a URI fragment
+                'a'..='z' | 'A'..='Z' | '0'..='9' => reference.push(char),
+                '-' | '.' | '_' | '!' | '$' | '&' | '\'' | '(' | ')' | '*' | '+' | ',' | ';'
+                | '=' | ':' | '@' => reference.push(char),
+                _ => {
+                    let mut bytes = [0; 4];
+                    for byte in char.encode_utf8(&mut bytes).bytes() {
+                        write!(reference, "%{byte:02X}").unwrap();
+                    }
+                }
+            }
+        }
+        Self {
+            reference: Some(reference),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the `description` keyword of this schema.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// A schema accepting objects with the given properties and no others.
+    pub fn object(properties: Vec<(String, JsonSchema)>, required: Vec<String>) -> Self {
+        Self {
+            properties,
+            required,
+            additional_properties: Some(Box::new(Self::never())),
+            ..Self::of_type(JsonType::Object)
+        }
+    }
+
+    fn integer(minimum: Option<i64>, maximum: Option<i64>) -> Self {
+        Self {
+            minimum,
+            maximum,
+            ..Self::of_type(JsonType::Integer)
+        }
+    }
+
+    fn non_zero_integer() -> Self {
+        Self {
+            any_of: vec![Self::integer(None, Some(-1)), Self::integer(Some(1), None)],
+            ..Default::default()
+        }
+    }
+
+    fn string(format: Option<&str>) -> Self {
+        Self {
+            format: format.map(ToString::to_string),
+            ..Self::of_type(JsonType::String)
+        }
+    }
+
+    fn array(items: JsonSchema, len: Option<usize>) -> Self {
+        Self {
+            items: Some(Box::new(items)),
+            min_items: len,
+            max_items: len,
+            ..Self::of_type(JsonType::Array)
+        }
+    }
+
+    fn tuple(items: Vec<JsonSchema>) -> Self {
+        let len = items.len();
+        Self {
+            prefix_items: items,
+            items: Some(Box::new(Self::never())),
+            min_items: Some(len),
+            max_items: Some(len),
+            ..Self::of_type(JsonType::Array)
+        }
+    }
+}
+
+impl Serialize for JsonSchema {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct Entries<'a>(&'a [(String, JsonSchema)]);
+
+        impl<'a> Serialize for Entries<'a> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+            }
+        }
+
+        let mut state = serializer.serialize_map(None)?;
+        if let Some(schema) = &self.schema {
+            state.serialize_entry("$schema", schema)?;
+        }
+        if let Some(reference) = &self.reference {
+            state.serialize_entry("$ref", reference)?;
+        }
+        if let Some(title) = &self.title {
+            state.serialize_entry("title", title)?;
+        }
+        if let Some(description) = &self.description {
+            state.serialize_entry("description", description)?;
+        }
+        if let Some(json_type) = self.json_type {
+            state.serialize_entry("type", json_type.as_str())?;
+        }
+        if let Some(constant) = &self.constant {
+            state.serialize_entry("const", constant)?;
+        }
+        if let Some(format) = &self.format {
+            state.serialize_entry("format", format)?;
+        }
+        if let Some(pattern) = &self.pattern {
+            state.serialize_entry("pattern", pattern)?;
+        }
+        if let Some(minimum) = self.minimum {
+            state.serialize_entry("minimum", &minimum)?;
+        }
+        if let Some(maximum) = self.maximum {
+            state.serialize_entry("maximum", &maximum)?;
+        }
+        if let Some(min_length) = self.min_length {
+            state.serialize_entry("minLength", &min_length)?;
+        }
+        if let Some(max_length) = self.max_length {
+            state.serialize_entry("maxLength", &max_length)?;
+        }
+        if !self.properties.is_empty() {
+            state.serialize_entry("properties", &Entries(&self.properties))?;
+        }
+        if !self.required.is_empty() {
+            state.serialize_entry("required", &self.required)?;
+        }
+        if let Some(additional_properties) = &self.additional_properties {
+            state.serialize_entry("additionalProperties", additional_properties)?;
+        }
+        if let Some(property_names) = &self.property_names {
+            state.serialize_entry("propertyNames", property_names)?;
+        }
+        if let Some(min_properties) = self.min_properties {
+            state.serialize_entry("minProperties", &min_properties)?;
+        }
+        if let Some(max_properties) = self.max_properties {
+            state.serialize_entry("maxProperties", &max_properties)?;
+        }
+        if !self.prefix_items.is_empty() {
+            state.serialize_entry("prefixItems", &self.prefix_items)?;
+        }
+        if let Some(items) = &self.items {
+            state.serialize_entry("items", items)?;
+        }
+        if let Some(min_items) = self.min_items {
+            state.serialize_entry("minItems", &min_items)?;
+        }
+        if let Some(max_items) = self.max_items {
+            state.serialize_entry("maxItems", &max_items)?;
+        }
+        if !self.any_of.is_empty() {
+            state.serialize_entry("anyOf", &self.any_of)?;
+        }
+        if !self.one_of.is_empty() {
+            state.serialize_entry("oneOf", &self.one_of)?;
+        }
+        if let Some(not) = &self.not {
+            state.serialize_entry("not", not)?;
+        }
+        if !self.defs.is_empty() {
+            state.serialize_entry("$defs", &Entries(&self.defs))?;
+        }
+        state.end()
+    }
+}
+
+/// Exports the [`JsonSchema`] of the data produced by a [`ReflectSerializer`]
+/// and accepted by an [`UntypedReflectDeserializer`] for the types of a [`TypeRegistry`].
+///
+/// Every registered type gets a definition, keyed by its [type name],
+/// which describes how a [`TypedReflectSerializer`] serializes its values:
+/// - structs are objects of their fields, none of them required,
+/// - tuple structs, tuples and arrays are arrays of fixed length,
+///   and lists are arrays of any length,
+/// - maps are objects, whose keys are not validated since JSON keys are always strings,
+/// - enums are externally tagged, except for [`Option`] which is either `null` or its value,
+/// - primitives, strings, [`Duration`] and quaternions follow their [`Serialize`] implementation.
+///
+/// Fields whose type isn't registered cannot be deserialized and are rejected,
+/// as are value types which didn't register [`ReflectDeserialize`].
+/// Other value types with a custom [`Serialize`] implementation accept any value,
+/// unless given a schema with [`JsonSchemaExporter::with_type_schema`].
+///
+/// When the `documentation` feature is enabled,
+/// doc comments of types, fields and variants are used as descriptions.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeRegistry, serde::JsonSchemaExporter};
+/// #[derive(Reflect)]
+/// struct Player {
+///     name: String,
+///     health: Option<u32>,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Player>();
+/// registry.register::<Option<u32>>();
+///
+/// let schema = JsonSchemaExporter::new(&registry).export();
+/// let player = schema
+///     .defs
+///     .iter()
+///     .find(|(name, _)| name.ends_with("Player"))
+///     .map(|(_, schema)| schema)
+///     .unwrap();
+/// assert_eq!(player.properties[0].0, "name");
+/// ```
+///
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+/// [`UntypedReflectDeserializer`]: crate::serde::UntypedReflectDeserializer
+/// [`TypedReflectSerializer`]: crate::serde::TypedReflectSerializer
+/// [type name]: std::any::type_name
+/// [`Duration`]: std::time::Duration
+pub struct JsonSchemaExporter<'a> {
+    registry: &'a TypeRegistry,
+    type_schemas: HashMap<TypeId, JsonSchema>,
+}
+
+impl<'a> JsonSchemaExporter<'a> {
+    /// Creates an exporter for the types of the given registry.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self {
+            registry,
+            type_schemas: HashMap::default(),
+        }
+    }
+
+    /// Uses the given schema for the values of type `T`.
+    ///
+    /// This is needed to describe types serialized by a custom [`Serialize`] implementation.
+    pub fn with_type_schema<T: 'static>(mut self, schema: JsonSchema) -> Self {
+        self.type_schemas.insert(TypeId::of::<T>(), schema);
+        self
+    }
+
+    /// Returns the schema of a value of the given registered type,
+    /// as serialized by a [`TypedReflectSerializer`].
+    ///
+    /// Other types are referenced as [definitions](JsonSchema::definition).
+    ///
+    /// [`TypedReflectSerializer`]: crate::serde::TypedReflectSerializer
+    pub fn type_schema(&self, registration: &TypeRegistration) -> JsonSchema {
+        if let Some(schema) = self.type_schemas.get(&registration.type_id()) {
+            return schema.clone();
+        }
+
+        let deserializable = registration.data::<ReflectDeserialize>().is_some();
+        #[allow(unused_mut)]
+        let mut schema = deserializable
+            .then(|| value_schema(registration.type_id()))
+            .flatten()
+            .unwrap_or_else(|| self.reflected_schema(registration, deserializable));
+
+        #[cfg(feature = "documentation")]
+        {
+            schema.description = registration.type_info().docs().map(format_docs);
+        }
+        schema
+    }
+
+    /// The schema of a type serialized through its [`TypeInfo`].
+    fn reflected_schema(
+        &self,
+        registration: &TypeRegistration,
+        deserializable: bool,
+    ) -> JsonSchema {
+        match registration.type_info() {
+            TypeInfo::Value(_) if deserializable => JsonSchema::any(),
+            TypeInfo::Value(_) => JsonSchema::never(),
+            TypeInfo::Struct(info) => self.struct_schema(info.iter()),
+            TypeInfo::TupleStruct(info) => {
+                let serialization_data = registration.data::<SerializationData>();
+                let fields = info
+                    .iter()
+                    .filter(|field| {
+                        !serialization_data
+                            .map(|data| data.is_ignored_field(field.index()))
+                            .unwrap_or(false)
+                    })
+                    .map(|field| self.unnamed_field_schema(field))
+                    .collect();
+                JsonSchema::tuple(fields)
+            }
+            TypeInfo::Tuple(info) => JsonSchema::tuple(
+                info.iter()
+                    .map(|field| self.unnamed_field_schema(field))
+                    .collect(),
+            ),
+            TypeInfo::List(info) => {
+                JsonSchema::array(self.field_type_schema(info.item_type_id()), None)
+            }
+            TypeInfo::Array(info) => JsonSchema::array(
+                self.field_type_schema(info.item_type_id()),
+                Some(info.capacity()),
+            ),
+            TypeInfo::Map(info) => JsonSchema {
+                additional_properties: Some(Box::new(self.field_type_schema(info.value_type_id()))),
+                ..JsonSchema::of_type(JsonType::Object)
+            },
+            TypeInfo::Enum(info) if info.type_name().starts_with("core::option::Option") => {
+                self.option_schema(info)
+            }
+            TypeInfo::Enum(info) => self.enum_schema(info),
+        }
+    }
+
+    /// Returns the schemas of every registered type, keyed and sorted by [type name].
+    ///
+    /// [type name]: std::any::type_name
+    pub fn definitions(&self) -> Vec<(String, JsonSchema)> {
+        let mut definitions = self
+            .registry
+            .iter()
+            .map(|registration| {
+                (
+                    registration.type_name().to_string(),
+                    self.type_schema(registration),
+                )
+            })
+            .collect::<Vec<_>>();
+        definitions.sort_by(|(a, _), (b, _)| a.cmp(b));
+        definitions
+    }
+
+    /// Returns the schema of a map from [type names] to values of the corresponding types,
+    /// each referencing its [definition](JsonSchema::definition).
+    ///
+    /// This is the shape of the output of a [`ReflectSerializer`],
+    /// which contains exactly one entry,
+    /// and of other formats built on it such as scenes.
+    ///
+    /// [type names]: std::any::type_name
+    /// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+    pub fn reflect_map_schema(&self) -> JsonSchema {
+        let mut properties = self
+            .registry
+            .iter()
+            .map(|registration| {
+                (
+                    registration.type_name().to_string(),
+                    JsonSchema::definition(registration.type_name()),
+                )
+            })
+            .collect::<Vec<_>>();
+        properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+        JsonSchema::object(properties, Vec::new())
+    }
+
+    /// Exports the schema of the output of a [`ReflectSerializer`],
+    /// with the [definitions](Self::definitions) of every registered type.
+    ///
+    /// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+    pub fn export(&self) -> JsonSchema {
+        JsonSchema {
+            schema: Some(JSON_SCHEMA_DIALECT.to_string()),
+            min_properties: Some(1),
+            max_properties: Some(1),
+            defs: self.definitions(),
+            ..self.reflect_map_schema()
+        }
+    }
+
+    /// The schema of a field of the given type, which must be registered to be deserialized.
+    fn field_type_schema(&self, type_id: TypeId) -> JsonSchema {
+        self.registry
+            .get(type_id)
+            .map(|registration| JsonSchema::definition(registration.type_name()))
+            .unwrap_or_else(JsonSchema::never)
+    }
+
+    fn named_field_schema(&self, field: &NamedField) -> (String, JsonSchema) {
+        #[allow(unused_mut)]
+        let mut schema = self.field_type_schema(field.type_id());
+        #[cfg(feature = "documentation")]
+        {
+            schema.description = field.docs().map(format_docs);
+        }
+        (field.name().to_string(), schema)
+    }
+
+    fn unnamed_field_schema(&self, field: &UnnamedField) -> JsonSchema {
+        #[allow(unused_mut)]
+        let mut schema = self.field_type_schema(field.type_id());
+        #[cfg(feature = "documentation")]
+        {
+            schema.description = field.docs().map(format_docs);
+        }
+        schema
+    }
+
+    /// Struct fields may be omitted, and the fields skipped during serialization are still accepted.
+    fn struct_schema<'f>(&self, fields: impl Iterator<Item = &'f NamedField>) -> JsonSchema {
+        let properties = fields.map(|field| self.named_field_schema(field)).collect();
+        JsonSchema::object(properties, Vec::new())
+    }
+
+    fn option_schema(&self, info: &EnumInfo) -> JsonSchema {
+        let some = match info.variant("Some") {
+            Some(VariantInfo::Tuple(variant)) if variant.field_len() == 1 => {
+                self.unnamed_field_schema(variant.field_at(0).unwrap())
+            }
+            _ => JsonSchema::never(),
+        };
+        JsonSchema {
+            any_of: vec![JsonSchema::of_type(JsonType::Null), some],
+            ..Default::default()
+        }
+    }
+
+    fn enum_schema(&self, info: &EnumInfo) -> JsonSchema {
+        let variants = info
+            .iter()
+            .map(|variant| {
+                let name = variant.name().to_string();
+                let value = match variant {
+                    VariantInfo::Unit(_) => None,
+                    VariantInfo::Tuple(variant) if variant.field_len() == 1 => {
+                        Some(self.unnamed_field_schema(variant.field_at(0).unwrap()))
+                    }
+                    VariantInfo::Tuple(variant) => Some(JsonSchema::tuple(
+                        variant
+                            .iter()
+                            .map(|field| self.unnamed_field_schema(field))
+                            .collect(),
+                    )),
+                    VariantInfo::Struct(variant) => Some(self.struct_schema(variant.iter())),
+                };
+                #[allow(unused_mut)]
+                let mut schema = match value {
+                    Some(value) => JsonSchema::object(vec![(name.clone(), value)], vec![name]),
+                    None => JsonSchema {
+                        constant: Some(name),
+                        ..JsonSchema::of_type(JsonType::String)
+                    },
+                };
+                #[cfg(feature = "documentation")]
+                {
+                    schema.description = variant.docs().map(format_docs);
+                }
+                schema
+            })
+            .collect();
+        JsonSchema {
+            one_of: variants,
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns the schema of the known value types serialized by their [`Serialize`] implementation.
+fn value_schema(type_id: TypeId) -> Option<JsonSchema> {
+    macro_rules! schemas {
+        ($($ty:ty => $schema:expr),* $(,)?) => {
+            $(
+                if type_id == TypeId::of::<$ty>() {
+                    return Some($schema);
+                }
+            )*
+        };
+    }
+
+    schemas!(
+        bool => JsonSchema::of_type(JsonType::Boolean),
+        u8 => JsonSchema::integer(Some(0), Some(u8::MAX.into())),
+        u16 => JsonSchema::integer(Some(0), Some(u16::MAX.into())),
+        u32 => JsonSchema::integer(Some(0), Some(u32::MAX.into())),
+        u64 => JsonSchema::integer(Some(0), None),
+        u128 => JsonSchema::integer(Some(0), None),
+        usize => JsonSchema::integer(Some(0), None),
+        i8 => JsonSchema::integer(Some(i8::MIN.into()), Some(i8::MAX.into())),
+        i16 => JsonSchema::integer(Some(i16::MIN.into()), Some(i16::MAX.into())),
+        i32 => JsonSchema::integer(Some(i32::MIN.into()), Some(i32::MAX.into())),
+        i64 => JsonSchema::integer(None, None),
+        i128 => JsonSchema::integer(None, None),
+        isize => JsonSchema::integer(None, None),
+        std::num::NonZeroU8 => JsonSchema::integer(Some(1), Some(u8::MAX.into())),
+        std::num::NonZeroU16 => JsonSchema::integer(Some(1), Some(u16::MAX.into())),
+        std::num::NonZeroU32 => JsonSchema::integer(Some(1), Some(u32::MAX.into())),
+        std::num::NonZeroU64 => JsonSchema::integer(Some(1), None),
+        std::num::NonZeroU128 => JsonSchema::integer(Some(1), None),
+        std::num::NonZeroUsize => JsonSchema::integer(Some(1), None),
+        std::num::NonZeroI8 => JsonSchema {
+            minimum: Some(i8::MIN.into()),
+            maximum: Some(i8::MAX.into()),
+            ..JsonSchema::non_zero_integer()
+        },
+        std::num::NonZeroI16 => JsonSchema {
+            minimum: Some(i16::MIN.into()),
+            maximum: Some(i16::MAX.into()),
+            ..JsonSchema::non_zero_integer()
+        },
+        std::num::NonZeroI32 => JsonSchema {
+            minimum: Some(i32::MIN.into()),
+            maximum: Some(i32::MAX.into()),
+            ..JsonSchema::non_zero_integer()
+        },
+        std::num::NonZeroI64 => JsonSchema::non_zero_integer(),
+        std::num::NonZeroI128 => JsonSchema::non_zero_integer(),
+        std::num::NonZeroIsize => JsonSchema::non_zero_integer(),
+        f32 => JsonSchema::of_type(JsonType::Number),
+        f64 => JsonSchema::of_type(JsonType::Number),
+        char => JsonSchema {
+            min_length: Some(1),
+            max_length: Some(1),
+            ..JsonSchema::string(None)
+        },
+        String => JsonSchema::string(None),
+        std::borrow::Cow<'static, str> => JsonSchema::string(None),
+        std::path::PathBuf => JsonSchema::string(None),
+        std::borrow::Cow<'static, std::path::Path> => JsonSchema::string(None),
+        std::ffi::OsString => JsonSchema::string(None),
+        bevy_utils::Uuid => JsonSchema::string(Some("uuid")),
+        bevy_utils::Duration => JsonSchema::object(
+            vec![
+                ("secs".to_string(), JsonSchema::integer(Some(0), None)),
+                (
+                    "nanos".to_string(),
+                    JsonSchema::integer(Some(0), Some(999_999_999)),
+                ),
+            ],
+            vec!["secs".to_string(), "nanos".to_string()],
+        ),
+    );
+
+    #[cfg(feature = "glam")]
+    schemas!(
+        glam::Quat => JsonSchema::array(JsonSchema::of_type(JsonType::Number), Some(4)),
+        glam::DQuat => JsonSchema::array(JsonSchema::of_type(JsonType::Number), Some(4)),
+    );
+
+    None
+}
+
+/// Removes the leading space doc comments are collected with.
+#[cfg(feature = "documentation")]
+fn format_docs(docs: &str) -> String {
+    docs.lines()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::serde::ReflectSerializer;
+    use crate::Reflect;
+    use std::any::type_name;
+
+    #[derive(Reflect)]
+    struct Player {
+        name: String,
+        level: u8,
+        position: (f32, f32),
+        tags: Vec<String>,
+        slots: [Option<u32>; 2],
+        stats: HashMap<String, i32>,
+        state: State,
+        #[reflect(skip_serializing)]
+        cache: u32,
+    }
+
+    #[derive(Reflect)]
+    struct Health(u32, #[reflect(skip_serializing)] u32);
+
+    #[derive(Reflect)]
+    enum State {
+        Idle,
+        Moving { speed: f32 },
+        Attacking(u32),
+        Casting(u32, String),
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<Health>();
+        registry.register::<State>();
+        registry.register::<(f32, f32)>();
+        registry.register::<Vec<String>>();
+        registry.register::<[Option<u32>; 2]>();
+        registry.register::<Option<u32>>();
+        registry.register::<HashMap<String, i32>>();
+        registry
+    }
+
+    fn definition<'a>(schema: &'a JsonSchema, type_name: &str) -> &'a JsonSchema {
+        schema
+            .defs
+            .iter()
+            .find(|(name, _)| name == type_name)
+            .map(|(_, schema)| schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn should_export_struct_schema() {
+        let registry = registry();
+        let schema = JsonSchemaExporter::new(&registry).export();
+        let player = definition(&schema, type_name::<Player>());
+
+        assert_eq!(player.json_type, Some(JsonType::Object));
+        assert_eq!(
+            player.additional_properties,
+            Some(Box::new(JsonSchema::never()))
+        );
+        assert!(player.required.is_empty());
+        assert_eq!(
+            player.properties,
+            vec![
+                (
+                    "name".to_string(),
+                    JsonSchema::definition(type_name::<String>())
+                ),
+                ("level".to_string(), JsonSchema::definition("u8")),
+                ("position".to_string(), JsonSchema::definition("(f32, f32)")),
+                (
+                    "tags".to_string(),
+                    JsonSchema::definition(type_name::<Vec<String>>())
+                ),
+                (
+                    "slots".to_string(),
+                    JsonSchema::definition(type_name::<[Option<u32>; 2]>())
+                ),
+                (
+                    "stats".to_string(),
+                    JsonSchema::definition(type_name::<HashMap<String, i32>>())
+                ),
+                (
+                    "state".to_string(),
+                    JsonSchema::definition(type_name::<State>())
+                ),
+                ("cache".to_string(), JsonSchema::definition("u32")),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_export_sequence_schemas() {
+        let registry = registry();
+        let schema = JsonSchemaExporter::new(&registry).export();
+
+        let health = definition(&schema, type_name::<Health>());
+        assert_eq!(health.json_type, Some(JsonType::Array));
+        assert_eq!(health.prefix_items, vec![JsonSchema::definition("u32")]);
+        assert_eq!(health.max_items, Some(1));
+
+        let tuple = definition(&schema, "(f32, f32)");
+        assert_eq!(tuple.prefix_items.len(), 2);
+        assert_eq!(tuple.items, Some(Box::new(JsonSchema::never())));
+
+        let list = definition(&schema, type_name::<Vec<String>>());
+        assert_eq!(
+            list.items,
+            Some(Box::new(JsonSchema::definition(type_name::<String>())))
+        );
+        assert_eq!(list.max_items, None);
+
+        let array = definition(&schema, type_name::<[Option<u32>; 2]>());
+        assert_eq!(array.min_items, Some(2));
+        assert_eq!(array.max_items, Some(2));
+
+        let map = definition(&schema, type_name::<HashMap<String, i32>>());
+        assert_eq!(map.json_type, Some(JsonType::Object));
+        assert_eq!(
+            map.additional_properties,
+            Some(Box::new(JsonSchema::definition("i32")))
+        );
+    }
+
+    #[test]
+    fn should_export_enum_schema() {
+        let registry = registry();
+        let schema = JsonSchemaExporter::new(&registry).export();
+
+        let state = definition(&schema, type_name::<State>());
+        assert_eq!(state.one_of.len(), 4);
+        assert_eq!(state.one_of[0].constant.as_deref(), Some("Idle"));
+        assert_eq!(state.one_of[1].required, vec!["Moving"]);
+        assert_eq!(
+            state.one_of[1].properties[0].1.json_type,
+            Some(JsonType::Object)
+        );
+        assert_eq!(
+            state.one_of[2].properties[0].1,
+            JsonSchema::definition("u32")
+        );
+        assert_eq!(state.one_of[3].properties[0].1.prefix_items.len(), 2);
+
+        let option = definition(&schema, type_name::<Option<u32>>());
+        assert_eq!(
+            option.any_of,
+            vec![
+                JsonSchema::of_type(JsonType::Null),
+                JsonSchema::definition("u32")
+            ]
+        );
+    }
+
+    #[test]
+    fn should_export_value_schemas() {
+        #[derive(Reflect, Clone)]
+        #[reflect_value]
+        struct Opaque;
+
+        let mut registry = registry();
+        registry.register::<Opaque>();
+        let schema = JsonSchemaExporter::new(&registry).export();
+
+        let level = definition(&schema, "u8");
+        assert_eq!(level.json_type, Some(JsonType::Integer));
+        assert_eq!(level.maximum, Some(255));
+        assert_eq!(
+            definition(&schema, type_name::<String>()).json_type,
+            Some(JsonType::String)
+        );
+        assert_eq!(
+            definition(&schema, type_name::<Opaque>()),
+            &JsonSchema::never()
+        );
+
+        let custom = JsonSchema::of_type(JsonType::Null);
+        let schema = JsonSchemaExporter::new(&registry)
+            .with_type_schema::<Opaque>(custom.clone())
+            .export();
+        assert_eq!(definition(&schema, type_name::<Opaque>()), &custom);
+    }
+
+    /// Returns `true` if `value` is valid against `schema`,
+    /// resolving references against the definitions of `root`.
+    ///
+    /// Only the keywords produced by the exporter are supported, and `format` is ignored.
+    fn validate(root: &JsonSchema, schema: &JsonSchema, value: &serde_json::Value) -> bool {
+        use serde_json::Value;
+
+        if let Some(reference) = &schema.reference {
+            let (_, definition) = root
+                .defs
+                .iter()
+                .find(|(name, _)| {
+                    JsonSchema::definition(name).reference.as_ref() == Some(reference)
+                })
+                .unwrap_or_else(|| panic!("unresolved reference {reference}"));
+            if !validate(root, definition, value) {
+                return false;
+            }
+        }
+        if let Some(json_type) = schema.json_type {
+            let matches = match json_type {
+                JsonType::Null => value.is_null(),
+                JsonType::Boolean => value.is_boolean(),
+                JsonType::Integer => value.is_i64() || value.is_u64(),
+                JsonType::Number => value.is_number(),
+                JsonType::String => value.is_string(),
+                JsonType::Array => value.is_array(),
+                JsonType::Object => value.is_object(),
+            };
+            if !matches {
+                return false;
+            }
+        }
+        if let Some(constant) = &schema.constant {
+            if value.as_str() != Some(constant) {
+                return false;
+            }
+        }
+        if let Some(number) = value.as_i64() {
+            if schema.minimum.is_some_and(|minimum| number < minimum)
+                || schema.maximum.is_some_and(|maximum| number > maximum)
+            {
+                return false;
+            }
+        } else if value.is_u64() && schema.maximum.is_some() {
+            return false;
+        }
+        if let Value::String(string) = value {
+            let len = string.chars().count();
+            if schema.min_length.is_some_and(|min| len < min)
+                || schema.max_length.is_some_and(|max| len > max)
+            {
+                return false;
+            }
+        }
+        if let Value::Object(object) = value {
+            if schema.min_properties.is_some_and(|min| object.len() < min)
+                || schema.max_properties.is_some_and(|max| object.len() > max)
+                || !schema.required.iter().all(|name| object.contains_key(name))
+            {
+                return false;
+            }
+            for (key, property) in object {
+                if let Some(names) = &schema.property_names {
+                    if !validate(root, names, &Value::String(key.clone())) {
+                        return false;
+                    }
+                }
+                let property_schema = schema
+                    .properties
+                    .iter()
+                    .find(|(name, _)| name == key)
+                    .map(|(_, schema)| schema)
+                    .or(schema.additional_properties.as_deref());
+                if let Some(property_schema) = property_schema {
+                    if !validate(root, property_schema, property) {
+                        return false;
+                    }
+                }
+            }
+        }
+        if let Value::Array(array) = value {
+            if schema.min_items.is_some_and(|min| array.len() < min)
+                || schema.max_items.is_some_and(|max| array.len() > max)
+            {
+                return false;
+            }
+            for (index, item) in array.iter().enumerate() {
+                let item_schema = schema.prefix_items.get(index).or(schema.items.as_deref());
+                if let Some(item_schema) = item_schema {
+                    if !validate(root, item_schema, item) {
+                        return false;
+                    }
+                }
+            }
+        }
+        if !schema.any_of.is_empty()
+            && !schema
+                .any_of
+                .iter()
+                .any(|schema| validate(root, schema, value))
+        {
+            return false;
+        }
+        if !schema.one_of.is_empty()
+            && schema
+                .one_of
+                .iter()
+                .filter(|schema| validate(root, schema, value))
+                .count()
+                != 1
+        {
+            return false;
+        }
+        if let Some(not) = &schema.not {
+            if validate(root, not, value) {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn should_describe_reflect_serializer_output() {
+        let registry = registry();
+        let schema = JsonSchemaExporter::new(&registry).export();
+        assert_eq!(schema.schema.as_deref(), Some(JSON_SCHEMA_DIALECT));
+        assert_eq!(schema.min_properties, Some(1));
+        assert_eq!(schema.max_properties, Some(1));
+        assert!(schema
+            .properties
+            .iter()
+            .all(|(name, property)| *property == JsonSchema::definition(name)));
+
+        let mut stats = HashMap::default();
+        stats.insert("strength".to_string(), -3);
+        let player = Player {
+            name: "Ferris".to_string(),
+            level: 12,
+            position: (1.5, -2.0),
+            tags: vec!["crab".to_string()],
+            slots: [Some(7), None],
+            stats,
+            state: State::Moving { speed: 2.5 },
+            cache: 42,
+        };
+        let values: Vec<Box<dyn Reflect>> = vec![
+            Box::new(player),
+            Box::new(Health(100, 5)),
+            Box::new(State::Idle),
+            Box::new(State::Moving { speed: 0.5 }),
+            Box::new(State::Attacking(1)),
+            Box::new(State::Casting(3, "fireball".to_string())),
+            Box::new(Some(5_u32)),
+            Box::new(None::<u32>),
+            Box::new(vec!["a".to_string(), "b".to_string()]),
+            Box::new(HashMap::<String, i32>::default()),
+        ];
+        for value in &values {
+            let serialized =
+                serde_json::to_value(ReflectSerializer::new(&**value, &registry)).unwrap();
+            assert!(
+                validate(&schema, &schema, &serialized),
+                "{serialized} doesn't match its schema"
+            );
+        }
+
+        let invalid = [
+            serde_json::json!({ type_name::<State>(): "Flying" }),
+            serde_json::json!({ type_name::<State>(): { "Attacking": -1 } }),
+            serde_json::json!({ type_name::<Health>(): [100, 5] }),
+            serde_json::json!({ type_name::<Player>(): { "level": 256 } }),
+            serde_json::json!({ type_name::<Option<u32>>(): "none" }),
+            serde_json::json!({ "unknown::Type": 0 }),
+        ];
+        for value in &invalid {
+            assert!(
+                !validate(&schema, &schema, value),
+                "{value} shouldn't match the schema"
+            );
+        }
+    }
+
+    #[test]
+    fn should_serialize_schema() {
+        let schema = JsonSchema::array(JsonSchema::definition(type_name::<Option<u32>>()), Some(2))
+            .with_description("Two slots");
+        assert_eq!(
+            serde_json::to_value(&schema).unwrap(),
+            serde_json::json!({
+                "description": "Two slots",
+                "type": "array",
+                "items": { "$ref": "#/$defs/core::option::Option%3Cu32%3E" },
+                "minItems": 2,
+                "maxItems": 2,
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(JsonSchema::never()).unwrap(),
+            serde_json::json!({ "not": {} })
+        );
+    }
+
+    #[cfg(feature = "documentation")]
+    #[test]
+    fn should_export_docs() {
+        /// A player.
+        #[derive(Reflect)]
+        struct Documented {
+            /// The player's name.
+            name: String,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Documented>();
+        let schema = JsonSchemaExporter::new(&registry).export();
+        let documented = definition(&schema, type_name::<Documented>());
+        assert_eq!(documented.description.as_deref(), Some("A player."));
+        assert_eq!(
+            documented.properties[0].1.description.as_deref(),
+            Some("The player's name.")
+        );
+    }
+}
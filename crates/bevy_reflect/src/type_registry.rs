@@ -1,4 +1,4 @@
-use crate::{serde::Serializable, Reflect, TypeInfo, Typed};
+use crate::{func::DynamicFunction, serde::Serializable, Reflect, TypeInfo, Typed};
 use bevy_ptr::{Ptr, PtrMut};
 use bevy_utils::{HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
@@ -23,6 +23,7 @@ pub struct TypeRegistry {
     short_name_to_id: HashMap<String, TypeId>,
     full_name_to_id: HashMap<String, TypeId>,
     ambiguous_names: HashSet<String>,
+    functions: HashMap<String, DynamicFunction>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -66,6 +67,7 @@ impl TypeRegistry {
             short_name_to_id: Default::default(),
             full_name_to_id: Default::default(),
             ambiguous_names: Default::default(),
+            functions: Default::default(),
         }
     }
 
@@ -262,6 +264,28 @@ impl TypeRegistry {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut TypeRegistration> {
         self.registrations.values_mut()
     }
+
+    /// Registers a free function under its [name].
+    ///
+    /// Methods should instead be registered on the [`TypeRegistration`] of their type.
+    /// If a function with the same name was previously registered, it is replaced.
+    ///
+    /// [name]: DynamicFunction::name
+    pub fn register_function(&mut self, function: DynamicFunction) {
+        self.functions.insert(function.name().to_string(), function);
+    }
+
+    /// Returns a reference to the free function registered with the given name.
+    ///
+    /// If no function with the given name has been registered, returns `None`.
+    pub fn get_function(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over the registered free functions.
+    pub fn functions(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.values()
+    }
 }
 
 impl TypeRegistryArc {
@@ -305,6 +329,7 @@ impl TypeRegistryArc {
 pub struct TypeRegistration {
     short_name: String,
     data: HashMap<TypeId, Box<dyn TypeData>>,
+    functions: HashMap<String, DynamicFunction>,
     type_info: &'static TypeInfo,
 }
 
@@ -313,6 +338,7 @@ impl Debug for TypeRegistration {
         f.debug_struct("TypeRegistration")
             .field("short_name", &self.short_name)
             .field("type_info", &self.type_info)
+            .field("functions", &self.functions.keys())
             .finish()
     }
 }
@@ -358,11 +384,35 @@ impl TypeRegistration {
         self.data.insert(TypeId::of::<T>(), Box::new(data));
     }
 
+    /// Registers a function, usually a method of the type, under its [name].
+    ///
+    /// If a function with the same name was previously registered, it is replaced.
+    /// Methods can also be registered with the `#[reflect(functions(...))]` attribute
+    /// of [`#[derive(Reflect)]`](derive@crate::Reflect).
+    ///
+    /// [name]: DynamicFunction::name
+    pub fn register_function(&mut self, function: DynamicFunction) {
+        self.functions.insert(function.name().to_string(), function);
+    }
+
+    /// Returns a reference to the function registered with the given name.
+    ///
+    /// Returns `None` if no such function exists.
+    pub fn get_function(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over the functions registered for the type.
+    pub fn functions(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.values()
+    }
+
     /// Creates type registration information for `T`.
     pub fn of<T: Reflect + Typed>() -> Self {
         let type_name = std::any::type_name::<T>();
         Self {
             data: HashMap::default(),
+            functions: HashMap::default(),
             short_name: bevy_utils::get_short_name(type_name),
             type_info: T::type_info(),
         }
@@ -392,6 +442,7 @@ impl Clone for TypeRegistration {
 
         TypeRegistration {
             data,
+            functions: self.functions.clone(),
             short_name: self.short_name.clone(),
             type_info: self.type_info,
         }
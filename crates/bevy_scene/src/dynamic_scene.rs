@@ -50,6 +50,8 @@ impl DynamicScene {
     }
 
     /// Create a new dynamic scene from a given world.
+    ///
+    /// [`Disabled`](bevy_ecs::entity::Disabled) entities are not extracted.
     pub fn from_world(world: &World) -> Self {
         let mut builder = DynamicSceneBuilder::from_world(world);
 
@@ -134,15 +136,19 @@ impl DynamicScene {
             }
         }
 
-        // Updates references to entities in the scene to entities in the world
-        for (type_id, entities) in scene_mappings.into_iter() {
-            let registration = type_registry.get(type_id).expect(
-                "we should be getting TypeId from this TypeRegistration in the first place",
-            );
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect.map_entities(world, entity_map, &entities);
+        // Updates references to entities in the scene to entities in the world, moving the
+        // spawned relation sources to the sources of their mapped targets
+        let spawned_entities: Vec<_> = entity_map.values().collect();
+        world.remap_relations(&spawned_entities, |world| {
+            for (type_id, entities) in scene_mappings.into_iter() {
+                let registration = type_registry.get(type_id).expect(
+                    "we should be getting TypeId from this TypeRegistration in the first place",
+                );
+                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                    map_entities_reflect.map_entities(world, entity_map, &entities);
+                }
             }
-        }
+        });
 
         Ok(())
     }
@@ -183,10 +189,15 @@ where
 
 #[cfg(test)]
 mod tests {
-    use bevy_ecs::{entity::EntityMap, reflect::AppTypeRegistry, system::Command, world::World};
+    use bevy_ecs::{
+        entity::{Disabled, EntityMap},
+        reflect::AppTypeRegistry,
+        system::Command,
+        world::World,
+    };
     use bevy_hierarchy::{AddChild, Parent};
 
-    use crate::dynamic_scene_builder::DynamicSceneBuilder;
+    use crate::{dynamic_scene_builder::DynamicSceneBuilder, DynamicScene};
 
     #[test]
     fn components_not_defined_in_scene_should_not_be_affected_by_scene_entity_map() {
@@ -264,4 +275,16 @@ mod tests {
             "something is wrong with the this test or the code reloading scenes since the relationship between scene entities is broken"
         );
     }
+
+    #[test]
+    fn from_world_skips_disabled_entities() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        let enabled = world.spawn_empty().id();
+        world.spawn(Disabled);
+
+        let scene = DynamicScene::from_world(&world);
+        let entities: Vec<_> = scene.entities.iter().map(|e| e.entity).collect();
+        assert_eq!(entities, vec![enabled]);
+    }
 }
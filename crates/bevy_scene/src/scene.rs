@@ -117,11 +117,15 @@ impl Scene {
             }
         }
 
-        for registration in type_registry.iter() {
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect.map_all_entities(world, &mut instance_info.entity_map);
+        // Moves the spawned relation sources to the sources of their mapped targets
+        let spawned_entities: Vec<_> = instance_info.entity_map.values().collect();
+        world.remap_relations(&spawned_entities, |world| {
+            for registration in type_registry.iter() {
+                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                    map_entities_reflect.map_all_entities(world, &mut instance_info.entity_map);
+                }
             }
-        }
+        });
 
         Ok(instance_info)
     }
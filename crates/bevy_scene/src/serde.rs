@@ -1,7 +1,10 @@
 use crate::{DynamicEntity, DynamicScene};
 use anyhow::Result;
 use bevy_ecs::entity::Entity;
-use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy_reflect::serde::{
+    JsonSchema, JsonSchemaExporter, JsonType, TypedReflectDeserializer, TypedReflectSerializer,
+    JSON_SCHEMA_DIALECT,
+};
 use bevy_reflect::{
     serde::{TypeRegistrationDeserializer, UntypedReflectDeserializer},
     Reflect, TypeRegistry, TypeRegistryArc,
@@ -420,15 +423,58 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     }
 }
 
+/// Exports the [`JsonSchema`] of the scenes serialized by a [`SceneSerializer`],
+/// such as `.scn.ron` files, to let external tools validate them.
+///
+/// Resources and components are described by the definitions of the given [`JsonSchemaExporter`],
+/// which may be given custom schemas for the types it cannot describe.
+/// [`Entity`] is described as its bits, which are also the keys of the scene entities.
+pub fn scene_json_schema(exporter: JsonSchemaExporter) -> JsonSchema {
+    let exporter = exporter.with_type_schema::<Entity>(JsonSchema {
+        minimum: Some(0),
+        ..JsonSchema::of_type(JsonType::Integer)
+    });
+
+    let entity = JsonSchema::object(
+        vec![(
+            ENTITY_FIELD_COMPONENTS.to_string(),
+            exporter.reflect_map_schema(),
+        )],
+        vec![ENTITY_FIELD_COMPONENTS.to_string()],
+    );
+    let entities = JsonSchema {
+        additional_properties: Some(Box::new(entity)),
+        property_names: Some(Box::new(JsonSchema {
+            pattern: Some("^[0-9]+$".to_string()),
+            ..JsonSchema::of_type(JsonType::String)
+        })),
+        ..JsonSchema::of_type(JsonType::Object)
+    };
+
+    JsonSchema {
+        schema: Some(JSON_SCHEMA_DIALECT.to_string()),
+        title: Some(SCENE_STRUCT.to_string()),
+        defs: exporter.definitions(),
+        ..JsonSchema::object(
+            vec![
+                (SCENE_RESOURCES.to_string(), exporter.reflect_map_schema()),
+                (SCENE_ENTITIES.to_string(), entities),
+            ],
+            vec![SCENE_RESOURCES.to_string(), SCENE_ENTITIES.to_string()],
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::serde::{SceneDeserializer, SceneSerializer};
+    use crate::serde::{scene_json_schema, SceneDeserializer, SceneSerializer};
     use crate::{DynamicScene, DynamicSceneBuilder};
     use bevy_ecs::entity::{Entity, EntityMap, EntityMapper, MapEntities};
     use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource, World};
     use bevy_ecs::query::{With, Without};
     use bevy_ecs::reflect::{AppTypeRegistry, ReflectMapEntities};
     use bevy_ecs::world::FromWorld;
+    use bevy_reflect::serde::{JsonSchema, JsonSchemaExporter, JsonType};
     use bevy_reflect::{Reflect, ReflectSerialize};
     use bincode::Options;
     use serde::de::DeserializeSeed;
@@ -555,6 +601,42 @@ mod tests {
         assert_eq!(expected, output);
     }
 
+    #[test]
+    fn should_export_json_schema() {
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let schema = scene_json_schema(JsonSchemaExporter::new(&registry));
+
+        assert_eq!(schema.required, vec!["resources", "entities"]);
+        let (_, resources) = &schema.properties[0];
+        assert!(resources.properties.iter().any(|(name, property)| {
+            name == "bevy_scene::serde::tests::MyResource"
+                && *property == JsonSchema::definition(name)
+        }));
+
+        let (_, entities) = &schema.properties[1];
+        let entity = entities.additional_properties.as_ref().unwrap();
+        assert_eq!(entity.required, vec!["components"]);
+        assert_eq!(&entity.properties[0].1, resources);
+
+        let definition = |type_name: &str| {
+            schema
+                .defs
+                .iter()
+                .find(|(name, _)| name == type_name)
+                .map(|(_, schema)| schema)
+                .unwrap()
+        };
+        assert_eq!(
+            definition("bevy_ecs::entity::Entity").json_type,
+            Some(JsonType::Integer)
+        );
+        assert_eq!(
+            definition("bevy_scene::serde::tests::MyEntityRef").prefix_items,
+            vec![JsonSchema::definition("bevy_ecs::entity::Entity")]
+        );
+    }
+
     #[test]
     fn should_deserialize() {
         let world = create_world();
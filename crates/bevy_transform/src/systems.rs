@@ -1,6 +1,7 @@
 use crate::components::{GlobalTransform, Transform};
 use bevy_ecs::{
     change_detection::Ref,
+    entity::Disabled,
     prelude::{Changed, DetectChanges, Entity, Query, With, Without},
     query::{Added, Or},
     removal_detection::RemovedComponents,
@@ -10,6 +11,8 @@ use bevy_hierarchy::{Children, Parent};
 
 /// Update [`GlobalTransform`] component of entities that aren't in the hierarchy
 ///
+/// [`Disabled`] entities are skipped, and updated again once they are re-enabled.
+///
 /// Third party plugins should ensure that this is used in concert with [`propagate_transforms`].
 pub fn sync_simple_transforms(
     mut query: ParamSet<(
@@ -21,9 +24,10 @@ pub fn sync_simple_transforms(
                 Without<Children>,
             ),
         >,
-        Query<(Ref<Transform>, &mut GlobalTransform), Without<Children>>,
+        Query<(Ref<Transform>, &mut GlobalTransform), (Without<Parent>, Without<Children>)>,
     )>,
     mut orphaned: RemovedComponents<Parent>,
+    mut enabled: RemovedComponents<Disabled>,
 ) {
     // Update changed entities.
     query
@@ -32,9 +36,9 @@ pub fn sync_simple_transforms(
         .for_each(|(transform, mut global_transform)| {
             *global_transform = GlobalTransform::from(*transform);
         });
-    // Update orphaned entities.
+    // Update orphaned and re-enabled entities.
     let mut query = query.p1();
-    let mut iter = query.iter_many_mut(orphaned.iter());
+    let mut iter = query.iter_many_mut(orphaned.iter().chain(enabled.iter()));
     while let Some((transform, mut global_transform)) = iter.fetch_next() {
         if !transform.is_changed() && !global_transform.is_added() {
             *global_transform = GlobalTransform::from(*transform);
@@ -45,6 +49,9 @@ pub fn sync_simple_transforms(
 /// Update [`GlobalTransform`] component of entities based on entity hierarchy and
 /// [`Transform`] component.
 ///
+/// [`Disabled`] entities and their descendants are skipped, and updated again once they are
+/// re-enabled.
+///
 /// Third party plugins should ensure that this is used in concert with [`sync_simple_transforms`].
 pub fn propagate_transforms(
     mut root_query: Query<
@@ -52,16 +59,24 @@ pub fn propagate_transforms(
         Without<Parent>,
     >,
     mut orphaned: RemovedComponents<Parent>,
+    mut enabled: RemovedComponents<Disabled>,
     transform_query: Query<(Ref<Transform>, &mut GlobalTransform, Option<&Children>), With<Parent>>,
     parent_query: Query<(Entity, Ref<Parent>)>,
     mut orphaned_entities: Local<Vec<Entity>>,
+    mut enabled_entities: Local<Vec<Entity>>,
 ) {
     orphaned_entities.clear();
     orphaned_entities.extend(orphaned.iter());
     orphaned_entities.sort_unstable();
+    enabled_entities.clear();
+    enabled_entities.extend(enabled.iter());
+    enabled_entities.sort_unstable();
     root_query.par_iter_mut().for_each(
         |(entity, children, transform, mut global_transform)| {
-            let changed = transform.is_changed() || global_transform.is_added() || orphaned_entities.binary_search(&entity).is_ok();
+            let changed = transform.is_changed()
+                || global_transform.is_added()
+                || orphaned_entities.binary_search(&entity).is_ok()
+                || enabled_entities.binary_search(&entity).is_ok();
             if changed {
                 *global_transform = GlobalTransform::from(*transform);
             }
@@ -84,6 +99,7 @@ pub fn propagate_transforms(
                         &global_transform,
                         &transform_query,
                         &parent_query,
+                        &enabled_entities,
                         child,
                         changed || actual_parent.is_changed(),
                     );
@@ -113,6 +129,7 @@ unsafe fn propagate_recursive(
         With<Parent>,
     >,
     parent_query: &Query<(Entity, Ref<Parent>)>,
+    enabled_entities: &[Entity],
     entity: Entity,
     mut changed: bool,
 ) {
@@ -148,7 +165,9 @@ unsafe fn propagate_recursive(
                 return;
             };
 
-        changed |= transform.is_changed() || global_transform.is_added();
+        changed |= transform.is_changed()
+            || global_transform.is_added()
+            || enabled_entities.binary_search(&entity).is_ok();
         if changed {
             *global_transform = parent.mul_transform(*transform);
         }
@@ -171,6 +190,7 @@ unsafe fn propagate_recursive(
                 &global_matrix,
                 transform_query,
                 parent_query,
+                enabled_entities,
                 child,
                 changed || actual_parent.is_changed(),
             );
@@ -181,6 +201,7 @@ unsafe fn propagate_recursive(
 #[cfg(test)]
 mod test {
     use bevy_app::prelude::*;
+    use bevy_ecs::entity::Disabled;
     use bevy_ecs::prelude::*;
     use bevy_ecs::system::CommandQueue;
     use bevy_math::vec3;
@@ -285,6 +306,57 @@ mod test {
         );
     }
 
+    #[test]
+    fn disabled_entities_are_updated_when_re_enabled() {
+        ComputeTaskPool::init(TaskPool::default);
+        let mut world = World::default();
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems((sync_simple_transforms, propagate_transforms));
+
+        let simple = world
+            .spawn((
+                TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)),
+                Disabled,
+            ))
+            .id();
+        let mut child = Entity::PLACEHOLDER;
+        let parent = world
+            .spawn(TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)))
+            .with_children(|parent| {
+                child = parent
+                    .spawn((
+                        TransformBundle::from(Transform::from_xyz(0.0, 2.0, 0.0)),
+                        Disabled,
+                    ))
+                    .id();
+            })
+            .id();
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(simple).unwrap(),
+            GlobalTransform::IDENTITY
+        );
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::IDENTITY
+        );
+
+        world.entity_mut(simple).remove::<Disabled>();
+        world.entity_mut(child).remove::<Disabled>();
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(simple).unwrap(),
+            GlobalTransform::from_xyz(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            *world.get::<GlobalTransform>(parent).unwrap() * Transform::from_xyz(0.0, 2.0, 0.0)
+        );
+    }
+
     #[test]
     fn did_propagate_command_buffer() {
         let mut world = World::default();